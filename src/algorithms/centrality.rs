@@ -0,0 +1,231 @@
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+
+const ITERATIONS: usize = 200;
+const TOLERANCE: f32 = 1e-6;
+
+/// Distinct neighbors every node can reach in one step, without loops.
+/// Directed edges are followed forward only.
+fn successors(graph: &Graph) -> Vec<Vec<usize>> {
+    graph
+        .directed_adjacency()
+        .into_iter()
+        .enumerate()
+        .map(|(node, list)| {
+            let mut neighbors = list
+                .into_iter()
+                .map(|(neighbor, _)| neighbor)
+                .filter(|&neighbor| neighbor != node)
+                .collect::<Vec<_>>();
+
+            neighbors.sort_unstable();
+            neighbors.dedup();
+
+            neighbors
+        })
+        .collect()
+}
+
+fn distances_from(successors: &[Vec<usize>], source: usize) -> Vec<Option<usize>> {
+    let mut distances = vec![None; successors.len()];
+    let mut queue = VecDeque::from([source]);
+
+    distances[source] = Some(0);
+
+    while let Some(node) = queue.pop_front() {
+        let distance = distances[node].unwrap();
+
+        for &neighbor in successors[node].iter() {
+            if distances[neighbor].is_none() {
+                distances[neighbor] = Some(distance + 1);
+                queue.push_back(neighbor);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Number of edges touching every node, over the `n - 1` other nodes.
+/// Loops count twice.
+pub fn degree(graph: &Graph) -> Vec<f32> {
+    let n = graph.nodes.len();
+    let mut degrees = vec![0.0; n];
+
+    for edge in graph.edges.iter() {
+        degrees[edge.source] += 1.0;
+        degrees[edge.target] += 1.0;
+    }
+
+    let scale = (n.max(2) - 1) as f32;
+
+    degrees.into_iter().map(|degree| degree / scale).collect()
+}
+
+/// Inverse average distance to the reachable nodes, scaled by the share of
+/// nodes that are reachable (Wasserman and Faust) so that disconnected
+/// graphs get meaningful values. Edges have length 1.
+pub fn closeness(graph: &Graph) -> Vec<f32> {
+    let n = graph.nodes.len();
+    let successors = successors(graph);
+
+    (0..n)
+        .map(|node| {
+            let reachable = distances_from(&successors, node)
+                .into_iter()
+                .flatten()
+                .filter(|&distance| distance > 0)
+                .collect::<Vec<_>>();
+
+            let total = reachable.iter().sum::<usize>();
+
+            if total == 0 {
+                return 0.0;
+            }
+
+            let count = reachable.len() as f32;
+
+            (count / (n - 1) as f32) * (count / total as f32)
+        })
+        .collect()
+}
+
+/// Share of the shortest paths between other nodes that go through every
+/// node, with Brandes' algorithm. Edges have length 1.
+pub fn betweenness(graph: &Graph) -> Vec<f32> {
+    let n = graph.nodes.len();
+    let successors = successors(graph);
+    let mut centrality = vec![0.0; n];
+
+    for source in 0..n {
+        let mut order = Vec::with_capacity(n);
+        let mut predecessors = vec![Vec::new(); n];
+        let mut paths = vec![0.0f32; n];
+        let mut distances = vec![usize::MAX; n];
+        let mut queue = VecDeque::from([source]);
+
+        paths[source] = 1.0;
+        distances[source] = 0;
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for &neighbor in successors[node].iter() {
+                if distances[neighbor] == usize::MAX {
+                    distances[neighbor] = distances[node] + 1;
+                    queue.push_back(neighbor);
+                }
+
+                if distances[neighbor] == distances[node] + 1 {
+                    paths[neighbor] += paths[node];
+                    predecessors[neighbor].push(node);
+                }
+            }
+        }
+
+        let mut dependencies = vec![0.0f32; n];
+
+        for &node in order.iter().rev() {
+            for &predecessor in predecessors[node].iter() {
+                dependencies[predecessor] +=
+                    paths[predecessor] / paths[node] * (1.0 + dependencies[node]);
+            }
+
+            if node != source {
+                centrality[node] += dependencies[node];
+            }
+        }
+    }
+
+    // Ordered pairs are counted, so undirected paths count twice and the
+    // scale is the same for both kinds of graphs.
+    let pairs = ((n.max(3) - 1) * (n.max(3) - 2)) as f32;
+
+    centrality.into_iter().map(|value| value / pairs).collect()
+}
+
+/// Power iteration where every node takes the scores of the nodes pointing
+/// to it, scaled so that the highest score is 1. The graph is shifted by
+/// the identity so that the iteration also settles on bipartite graphs.
+pub fn eigenvector(graph: &Graph) -> Vec<f32> {
+    let n = graph.nodes.len();
+    let successors = successors(graph);
+    let mut scores = vec![1.0; n];
+
+    for _ in 0..ITERATIONS {
+        let mut next = scores.clone();
+
+        for node in 0..n {
+            for &neighbor in successors[node].iter() {
+                next[neighbor] += scores[node];
+            }
+        }
+
+        let highest = next.iter().copied().fold(0.0, f32::max);
+
+        if highest == 0.0 {
+            break;
+        }
+
+        for score in next.iter_mut() {
+            *score /= highest;
+        }
+
+        let change = scores
+            .iter()
+            .zip(next.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f32>();
+
+        scores = next;
+
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    scores
+}
+
+/// PageRank with the usual damping of 0.85. Nodes without outgoing edges
+/// spread their score over every node.
+pub fn page_rank(graph: &Graph) -> Vec<f32> {
+    const DAMPING: f32 = 0.85;
+
+    let n = graph.nodes.len();
+    let successors = successors(graph);
+    let mut ranks = vec![1.0 / n.max(1) as f32; n];
+
+    for _ in 0..ITERATIONS {
+        let dangling = (0..n)
+            .filter(|&node| successors[node].is_empty())
+            .map(|node| ranks[node])
+            .sum::<f32>();
+
+        let base = (1.0 - DAMPING + DAMPING * dangling) / n as f32;
+        let mut next = vec![base; n];
+
+        for node in 0..n {
+            let share = DAMPING * ranks[node] / successors[node].len().max(1) as f32;
+
+            for &neighbor in successors[node].iter() {
+                next[neighbor] += share;
+            }
+        }
+
+        let change = ranks
+            .iter()
+            .zip(next.iter())
+            .map(|(a, b)| (a - b).abs())
+            .sum::<f32>();
+
+        ranks = next;
+
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    ranks
+}
@@ -0,0 +1,210 @@
+use std::{
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Instant,
+};
+
+use crate::graph::Graph;
+
+const UNCOLORED: usize = usize::MAX;
+
+pub struct ExactColoring {
+    pub colors: Vec<usize>,
+    pub optimal: bool,
+}
+
+/// Neighbor lists without loops or repeated neighbors. Edge directions do
+/// not matter for coloring.
+fn neighbors(graph: &Graph) -> Vec<Vec<usize>> {
+    let mut neighbors = vec![Vec::new(); graph.nodes.len()];
+
+    for edge in graph.edges.iter() {
+        if edge.source != edge.target {
+            neighbors[edge.source].push(edge.target);
+            neighbors[edge.target].push(edge.source);
+        }
+    }
+
+    for list in neighbors.iter_mut() {
+        list.sort_unstable();
+        list.dedup();
+    }
+
+    neighbors
+}
+
+fn smallest_free_color(neighbors: &[usize], colors: &[usize]) -> usize {
+    let mut taken = neighbors
+        .iter()
+        .map(|&neighbor| colors[neighbor])
+        .filter(|&color| color != UNCOLORED)
+        .collect::<Vec<_>>();
+
+    taken.sort_unstable();
+    taken.dedup();
+
+    taken
+        .iter()
+        .enumerate()
+        .find(|&(index, &color)| index != color)
+        .map_or(taken.len(), |(index, _)| index)
+}
+
+fn color_in_order(neighbors: &[Vec<usize>], order: impl IntoIterator<Item = usize>) -> Vec<usize> {
+    let mut colors = vec![UNCOLORED; neighbors.len()];
+
+    for node in order {
+        colors[node] = smallest_free_color(&neighbors[node], &colors);
+    }
+
+    colors
+}
+
+pub fn greedy(graph: &Graph) -> Vec<usize> {
+    color_in_order(&neighbors(graph), 0..graph.nodes.len())
+}
+
+pub fn welsh_powell(graph: &Graph) -> Vec<usize> {
+    let neighbors = neighbors(graph);
+    let mut order = (0..graph.nodes.len()).collect::<Vec<_>>();
+
+    order.sort_by_key(|&node| std::cmp::Reverse(neighbors[node].len()));
+
+    color_in_order(&neighbors, order)
+}
+
+fn saturation(neighbors: &[usize], colors: &[usize]) -> usize {
+    let mut seen = neighbors
+        .iter()
+        .map(|&neighbor| colors[neighbor])
+        .filter(|&color| color != UNCOLORED)
+        .collect::<Vec<_>>();
+
+    seen.sort_unstable();
+    seen.dedup();
+
+    seen.len()
+}
+
+/// Picks the uncolored node with the most differently colored neighbors,
+/// breaking ties by degree.
+fn most_saturated(neighbors: &[Vec<usize>], colors: &[usize]) -> Option<usize> {
+    (0..neighbors.len())
+        .filter(|&node| colors[node] == UNCOLORED)
+        .max_by_key(|&node| {
+            (
+                saturation(&neighbors[node], colors),
+                neighbors[node].len(),
+                std::cmp::Reverse(node),
+            )
+        })
+}
+
+pub fn dsatur(graph: &Graph) -> Vec<usize> {
+    let neighbors = neighbors(graph);
+    let mut colors = vec![UNCOLORED; neighbors.len()];
+
+    while let Some(node) = most_saturated(&neighbors, &colors) {
+        colors[node] = smallest_free_color(&neighbors[node], &colors);
+    }
+
+    colors
+}
+
+struct Search<'a> {
+    neighbors: Vec<Vec<usize>>,
+    colors: Vec<usize>,
+    best: Vec<usize>,
+    best_count: usize,
+    deadline: Instant,
+    progress: &'a AtomicU64,
+    cancel: &'a AtomicBool,
+    timed_out: bool,
+}
+
+impl Search<'_> {
+    fn search(&mut self, used: usize) {
+        let visited = self.progress.fetch_add(1, Ordering::Relaxed) + 1;
+
+        if self.cancel.load(Ordering::Relaxed)
+            || visited % 1024 == 0 && Instant::now() >= self.deadline
+        {
+            self.timed_out = true;
+        }
+
+        if self.timed_out || used >= self.best_count {
+            return;
+        }
+
+        let Some(node) = most_saturated(&self.neighbors, &self.colors) else {
+            self.best = self.colors.clone();
+            self.best_count = used;
+
+            return;
+        };
+
+        // Trying at most one new color avoids exploring colorings that only
+        // differ by a renaming of the colors.
+        for color in 0..(used + 1).min(self.best_count - 1) {
+            if self.neighbors[node]
+                .iter()
+                .any(|&neighbor| self.colors[neighbor] == color)
+            {
+                continue;
+            }
+
+            self.colors[node] = color;
+            self.search(used.max(color + 1));
+            self.colors[node] = UNCOLORED;
+        }
+    }
+}
+
+/// Searches for a coloring with the fewest colors with DSatur branch and
+/// bound. When the deadline passes or `cancel` is set, the best coloring
+/// found so far is returned and is not known to be optimal. `progress`
+/// counts the partial colorings tried so far.
+pub fn exact(
+    graph: &Graph,
+    deadline: Instant,
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+) -> ExactColoring {
+    let best = dsatur(graph);
+
+    let mut search = Search {
+        neighbors: neighbors(graph),
+        colors: vec![UNCOLORED; graph.nodes.len()],
+        best_count: color_count(&best),
+        best,
+        deadline,
+        progress,
+        cancel,
+        timed_out: false,
+    };
+
+    search.search(0);
+
+    ExactColoring {
+        colors: search.best,
+        optimal: !search.timed_out,
+    }
+}
+
+pub fn color_count(colors: &[usize]) -> usize {
+    colors.iter().max().map_or(0, |max| max + 1)
+}
+
+/// Returns the edges whose two different endpoints have the same color.
+pub fn conflicts(graph: &Graph, colors: &[Option<usize>]) -> Vec<usize> {
+    graph
+        .edges
+        .iter()
+        .enumerate()
+        .filter(|(_, edge)| {
+            edge.source != edge.target
+                && colors[edge.source].is_some()
+                && colors[edge.source] == colors[edge.target]
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
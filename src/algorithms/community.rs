@@ -0,0 +1,401 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use crate::graph::Graph;
+
+use super::UnionFind;
+
+const MAX_ROUNDS: usize = 100;
+const EPSILON: f64 = 1e-12;
+
+/// Numbers the communities from 0 in the order their first node appears.
+fn renumber(communities: &[usize]) -> Vec<usize> {
+    let mut numbers = HashMap::new();
+
+    communities
+        .iter()
+        .map(|&community| {
+            let next = numbers.len();
+
+            *numbers.entry(community).or_insert(next)
+        })
+        .collect()
+}
+
+pub fn community_count(communities: &[usize]) -> usize {
+    communities.iter().max().map_or(0, |&highest| highest + 1)
+}
+
+/// Newman's modularity of a partition, ignoring edge directions and
+/// weights. Loops count twice in the degree of their node.
+pub fn modularity(graph: &Graph, communities: &[usize]) -> f64 {
+    let m = graph.edges.len() as f64;
+
+    if m == 0.0 {
+        return 0.0;
+    }
+
+    let count = community_count(communities);
+    let mut inside = vec![0.0; count];
+    let mut degrees = vec![0.0; count];
+
+    for edge in graph.edges.iter() {
+        let (source, target) = (communities[edge.source], communities[edge.target]);
+
+        degrees[source] += 1.0;
+        degrees[target] += 1.0;
+
+        if source == target {
+            inside[source] += 1.0;
+        }
+    }
+
+    (0..count)
+        .map(|community| inside[community] / m - (degrees[community] / (2.0 * m)).powi(2))
+        .sum()
+}
+
+/// Undirected graph with weighted edges between the communities of the
+/// level below, as built by Louvain.
+struct Level {
+    neighbors: Vec<Vec<(usize, f64)>>,
+    loops: Vec<f64>,
+}
+
+impl Level {
+    fn new(graph: &Graph) -> Self {
+        let n = graph.nodes.len();
+        let mut level = Self {
+            neighbors: vec![Vec::new(); n],
+            loops: vec![0.0; n],
+        };
+
+        for edge in graph.edges.iter() {
+            if edge.source == edge.target {
+                level.loops[edge.source] += 1.0;
+            } else {
+                level.neighbors[edge.source].push((edge.target, 1.0));
+                level.neighbors[edge.target].push((edge.source, 1.0));
+            }
+        }
+
+        level
+    }
+
+    fn degree(&self, node: usize) -> f64 {
+        self.neighbors[node]
+            .iter()
+            .map(|&(_, weight)| weight)
+            .sum::<f64>()
+            + 2.0 * self.loops[node]
+    }
+
+    /// Moves single nodes to the neighboring community that increases the
+    /// modularity the most, until no move helps. Returns the communities
+    /// and whether any node moved.
+    fn move_nodes(&self) -> (Vec<usize>, bool) {
+        let n = self.neighbors.len();
+        let degrees = (0..n).map(|node| self.degree(node)).collect::<Vec<_>>();
+        let total = degrees.iter().sum::<f64>();
+        let mut communities = (0..n).collect::<Vec<_>>();
+        let mut totals = degrees.clone();
+        let mut improved = false;
+
+        if total == 0.0 {
+            return (communities, false);
+        }
+
+        for _ in 0..MAX_ROUNDS {
+            let mut moved = false;
+
+            for node in 0..n {
+                let current = communities[node];
+                let mut weights = HashMap::new();
+
+                for &(neighbor, weight) in self.neighbors[node].iter() {
+                    *weights.entry(communities[neighbor]).or_insert(0.0) += weight;
+                }
+
+                totals[current] -= degrees[node];
+
+                let gain = |community: usize, weight: f64| {
+                    weight - totals[community] * degrees[node] / total
+                };
+
+                let mut best = current;
+                let mut best_gain = gain(current, weights.get(&current).copied().unwrap_or(0.0));
+
+                // Sorted so that ties always go the same way.
+                let mut candidates = weights.into_iter().collect::<Vec<_>>();
+
+                candidates.sort_by_key(|&(community, _)| community);
+
+                for (community, weight) in candidates {
+                    let community_gain = gain(community, weight);
+
+                    if community_gain > best_gain + EPSILON {
+                        best = community;
+                        best_gain = community_gain;
+                    }
+                }
+
+                totals[best] += degrees[node];
+
+                if best != current {
+                    communities[node] = best;
+                    moved = true;
+                    improved = true;
+                }
+            }
+
+            if !moved {
+                break;
+            }
+        }
+
+        (renumber(&communities), improved)
+    }
+
+    /// Merges every community into a single node.
+    fn aggregate(&self, communities: &[usize]) -> Self {
+        let count = community_count(communities);
+        let mut weights = vec![HashMap::new(); count];
+        let mut loops = vec![0.0; count];
+
+        for (node, neighbors) in self.neighbors.iter().enumerate() {
+            let community = communities[node];
+
+            loops[community] += self.loops[node];
+
+            for &(neighbor, weight) in neighbors.iter() {
+                let other = communities[neighbor];
+
+                if other != community {
+                    *weights[community].entry(other).or_insert(0.0) += weight;
+                } else if node < neighbor {
+                    loops[community] += weight;
+                }
+            }
+        }
+
+        Self {
+            neighbors: weights
+                .into_iter()
+                .map(|weights| {
+                    let mut neighbors = weights.into_iter().collect::<Vec<_>>();
+
+                    neighbors.sort_by_key(|&(neighbor, _)| neighbor);
+
+                    neighbors
+                })
+                .collect(),
+            loops,
+        }
+    }
+}
+
+/// Louvain method: moves nodes between communities greedily, then merges
+/// every community into a single node and starts again, until nothing
+/// moves. Edge directions and weights are ignored.
+pub fn louvain(graph: &Graph) -> Vec<usize> {
+    let mut level = Level::new(graph);
+    let mut communities = (0..graph.nodes.len()).collect::<Vec<_>>();
+
+    loop {
+        let (level_communities, improved) = level.move_nodes();
+
+        if !improved {
+            break;
+        }
+
+        for community in communities.iter_mut() {
+            *community = level_communities[*community];
+        }
+
+        level = level.aggregate(&level_communities);
+    }
+
+    renumber(&communities)
+}
+
+/// Every node takes the label most of its neighbors have, keeping its own
+/// when it is one of the most common, until the labels settle. The nodes
+/// are visited in a shuffled order and other ties are broken at random,
+/// from a fixed seed so that runs on the same graph agree.
+pub fn label_propagation(graph: &Graph) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let adjacency = graph.undirected_adjacency();
+    let mut labels = (0..n).collect::<Vec<_>>();
+    let mut order = (0..n).collect::<Vec<_>>();
+    let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+
+    let mut random = move |bound: usize| {
+        seed ^= seed << 13;
+        seed ^= seed >> 7;
+        seed ^= seed << 17;
+
+        (seed % bound as u64) as usize
+    };
+
+    for _ in 0..MAX_ROUNDS {
+        let mut changed = false;
+
+        for index in (1..n).rev() {
+            order.swap(index, random(index + 1));
+        }
+
+        for &node in order.iter() {
+            let mut counts = HashMap::new();
+
+            for &(neighbor, _) in adjacency[node].iter() {
+                if neighbor != node {
+                    *counts.entry(labels[neighbor]).or_insert(0) += 1;
+                }
+            }
+
+            let Some(&most) = counts.values().max() else {
+                continue;
+            };
+
+            if counts.get(&labels[node]) == Some(&most) {
+                continue;
+            }
+
+            let mut candidates = counts
+                .into_iter()
+                .filter(|&(_, count)| count == most)
+                .map(|(label, _)| label)
+                .collect::<Vec<_>>();
+
+            candidates.sort_unstable();
+
+            labels[node] = candidates[random(candidates.len())];
+            changed = true;
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    renumber(&labels)
+}
+
+/// Number of shortest paths going through every edge, ignoring directions,
+/// over the edges not removed yet.
+fn edge_betweenness(adjacency: &[Vec<(usize, usize)>], removed: &[bool]) -> Vec<f64> {
+    let n = adjacency.len();
+    let mut betweenness = vec![0.0; removed.len()];
+
+    for source in 0..n {
+        let mut order = Vec::new();
+        let mut predecessors = vec![Vec::new(); n];
+        let mut paths = vec![0.0f64; n];
+        let mut distances = vec![usize::MAX; n];
+        let mut queue = VecDeque::from([source]);
+
+        paths[source] = 1.0;
+        distances[source] = 0;
+
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+
+            for &(neighbor, edge) in adjacency[node].iter() {
+                if removed[edge] || neighbor == node {
+                    continue;
+                }
+
+                if distances[neighbor] == usize::MAX {
+                    distances[neighbor] = distances[node] + 1;
+                    queue.push_back(neighbor);
+                }
+
+                if distances[neighbor] == distances[node] + 1 {
+                    paths[neighbor] += paths[node];
+                    predecessors[neighbor].push((node, edge));
+                }
+            }
+        }
+
+        let mut dependencies = vec![0.0f64; n];
+
+        for &node in order.iter().rev() {
+            for &(predecessor, edge) in predecessors[node].iter() {
+                let share = paths[predecessor] / paths[node] * (1.0 + dependencies[node]);
+
+                betweenness[edge] += share;
+                dependencies[predecessor] += share;
+            }
+        }
+    }
+
+    betweenness
+}
+
+/// Girvan-Newman: removes the edge with the highest betweenness over and
+/// over, and keeps the split into connected components with the highest
+/// modularity. `progress` counts the edges removed so far and setting
+/// `cancel` stops the search, which then returns `None`.
+pub fn girvan_newman(
+    graph: &Graph,
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+) -> Option<Vec<usize>> {
+    let n = graph.nodes.len();
+    let adjacency = graph.undirected_adjacency();
+    let mut removed = vec![false; graph.edges.len()];
+
+    let components = |removed: &[bool]| {
+        let mut components = UnionFind::new(n);
+
+        for (index, edge) in graph.edges.iter().enumerate() {
+            if !removed[index] {
+                components.union(edge.source, edge.target);
+            }
+        }
+
+        renumber(&(0..n).map(|node| components.find(node)).collect::<Vec<_>>())
+    };
+
+    let mut best = components(&removed);
+    let mut best_modularity = modularity(graph, &best);
+    let mut count = community_count(&best);
+
+    for _ in 0..graph.edges.len() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        let betweenness = edge_betweenness(&adjacency, &removed);
+
+        let Some(edge) = (0..graph.edges.len())
+            .filter(|&edge| !removed[edge])
+            .max_by(|&a, &b| betweenness[a].total_cmp(&betweenness[b]).then(b.cmp(&a)))
+        else {
+            break;
+        };
+
+        removed[edge] = true;
+        progress.fetch_add(1, Ordering::Relaxed);
+
+        let communities = components(&removed);
+        let communities_count = community_count(&communities);
+
+        if communities_count == count {
+            continue;
+        }
+
+        count = communities_count;
+
+        let communities_modularity = modularity(graph, &communities);
+
+        if communities_modularity > best_modularity + EPSILON {
+            best = communities;
+            best_modularity = communities_modularity;
+        }
+    }
+
+    Some(best)
+}
@@ -0,0 +1,242 @@
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+
+use super::UnionFind;
+
+const UNVISITED: usize = usize::MAX;
+
+pub struct CutElements {
+    pub bridges: Vec<usize>,
+    pub articulation_points: Vec<usize>,
+}
+
+/// Returns the connected component of every node, numbered from 0 in the
+/// order of their first node. Edge directions are ignored.
+pub fn connected_components(graph: &Graph) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let mut union_find = UnionFind::new(n);
+
+    for edge in graph.edges.iter() {
+        union_find.union(edge.source, edge.target);
+    }
+
+    let mut numbers = vec![UNVISITED; n];
+    let mut count = 0;
+
+    (0..n)
+        .map(|node| {
+            let root = union_find.find(node);
+
+            if numbers[root] == UNVISITED {
+                numbers[root] = count;
+                count += 1;
+            }
+
+            numbers[root]
+        })
+        .collect()
+}
+
+/// Finds the bridges and articulation points with Tarjan's lowlink values,
+/// using an explicit stack so that long paths do not overflow the call stack.
+pub fn cut_elements(graph: &Graph) -> CutElements {
+    let n = graph.nodes.len();
+    let adjacency = graph.undirected_adjacency();
+
+    let mut order = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut is_articulation_point = vec![false; n];
+    let mut bridges = Vec::new();
+    let mut counter = 0;
+
+    for root in 0..n {
+        if order[root] != UNVISITED {
+            continue;
+        }
+
+        order[root] = counter;
+        low[root] = counter;
+        counter += 1;
+
+        let mut root_children = 0;
+        // Each frame holds a node, the edge used to reach it and the next
+        // neighbor to look at.
+        let mut stack = vec![(root, UNVISITED, 0)];
+
+        while let Some(frame) = stack.last_mut() {
+            let (node, parent_edge, next) = *frame;
+
+            if let Some(&(neighbor, edge)) = adjacency[node].get(next) {
+                frame.2 += 1;
+
+                // Skipping the edge itself, not the parent node, keeps
+                // parallel edges from being reported as bridges.
+                if edge == parent_edge {
+                    continue;
+                }
+
+                if order[neighbor] == UNVISITED {
+                    order[neighbor] = counter;
+                    low[neighbor] = counter;
+                    counter += 1;
+
+                    if node == root {
+                        root_children += 1;
+                    }
+
+                    stack.push((neighbor, edge, 0));
+                } else {
+                    low[node] = low[node].min(order[neighbor]);
+                }
+
+                continue;
+            }
+
+            stack.pop();
+
+            let Some(&(parent, _, _)) = stack.last() else {
+                continue;
+            };
+
+            low[parent] = low[parent].min(low[node]);
+
+            if low[node] > order[parent] {
+                bridges.push(parent_edge);
+            }
+
+            if parent != root && low[node] >= order[parent] {
+                is_articulation_point[parent] = true;
+            }
+        }
+
+        if root_children > 1 {
+            is_articulation_point[root] = true;
+        }
+    }
+
+    bridges.sort_unstable();
+
+    CutElements {
+        bridges,
+        articulation_points: (0..n).filter(|&node| is_articulation_point[node]).collect(),
+    }
+}
+
+/// Returns the strongly connected component of every node with Tarjan's
+/// algorithm. Undirected edges can be followed both ways.
+pub fn strongly_connected_components(graph: &Graph) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let adjacency = graph.directed_adjacency();
+
+    let mut order = vec![UNVISITED; n];
+    let mut low = vec![0; n];
+    let mut on_stack = vec![false; n];
+    let mut components = vec![UNVISITED; n];
+    let mut component_stack = Vec::new();
+    let mut counter = 0;
+    let mut count = 0;
+
+    for root in 0..n {
+        if order[root] != UNVISITED {
+            continue;
+        }
+
+        order[root] = counter;
+        low[root] = counter;
+        counter += 1;
+        on_stack[root] = true;
+        component_stack.push(root);
+
+        let mut stack = vec![(root, 0)];
+
+        while let Some(frame) = stack.last_mut() {
+            let (node, next) = *frame;
+
+            if let Some(&(neighbor, _)) = adjacency[node].get(next) {
+                frame.1 += 1;
+
+                if order[neighbor] == UNVISITED {
+                    order[neighbor] = counter;
+                    low[neighbor] = counter;
+                    counter += 1;
+                    on_stack[neighbor] = true;
+                    component_stack.push(neighbor);
+
+                    stack.push((neighbor, 0));
+                } else if on_stack[neighbor] {
+                    low[node] = low[node].min(order[neighbor]);
+                }
+
+                continue;
+            }
+
+            stack.pop();
+
+            if low[node] == order[node] {
+                while let Some(member) = component_stack.pop() {
+                    on_stack[member] = false;
+                    components[member] = count;
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                count += 1;
+            }
+
+            if let Some(&(parent, _)) = stack.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+        }
+    }
+
+    components
+}
+
+pub fn component_count(components: &[usize]) -> usize {
+    components.iter().max().map_or(0, |max| max + 1)
+}
+
+/// Length of the shortest cycle, ignoring edge directions, with a
+/// breadth-first search from every node. Loops are cycles of length 1 and
+/// parallel edges cycles of length 2. `None` when the graph has no cycle.
+pub fn girth(graph: &Graph) -> Option<usize> {
+    let n = graph.nodes.len();
+    let adjacency = graph.undirected_adjacency();
+    let mut shortest = None::<usize>;
+
+    for source in 0..n {
+        let mut distances = vec![UNVISITED; n];
+        let mut parent_edges = vec![UNVISITED; n];
+        let mut queue = VecDeque::from([source]);
+
+        distances[source] = 0;
+
+        while let Some(node) = queue.pop_front() {
+            // Cycles found further away can not be shorter.
+            if shortest.map_or(false, |shortest| 2 * distances[node] + 1 >= shortest) {
+                break;
+            }
+
+            for &(neighbor, edge) in adjacency[node].iter() {
+                if edge == parent_edges[node] {
+                    continue;
+                }
+
+                if distances[neighbor] == UNVISITED {
+                    distances[neighbor] = distances[node] + 1;
+                    parent_edges[neighbor] = edge;
+                    queue.push_back(neighbor);
+                } else {
+                    let length = distances[node] + distances[neighbor] + 1;
+
+                    shortest = Some(shortest.map_or(length, |shortest| shortest.min(length)));
+                }
+            }
+        }
+    }
+
+    shortest
+}
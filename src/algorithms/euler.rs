@@ -0,0 +1,182 @@
+use std::fmt;
+
+use crate::graph::Graph;
+
+use super::UnionFind;
+
+const NONE: usize = usize::MAX;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerKind {
+    Circuit,
+    Path,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EulerStep {
+    /// Follows an unused edge.
+    Walk(usize),
+    /// Goes back from a node with no unused edges left, adding it to the
+    /// front of the trail.
+    Close(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct EulerRun {
+    pub kind: EulerKind,
+    pub directed: bool,
+    pub steps: Vec<EulerStep>,
+    pub trail: Vec<usize>,
+    pub nodes: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EulerError {
+    NoEdges,
+    Disconnected,
+    OddDegrees(Vec<usize>),
+    Unbalanced(Vec<usize>),
+}
+
+impl fmt::Display for EulerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NoEdges => write!(f, "the graph has no edges"),
+            Self::Disconnected => write!(f, "the edges are not all connected"),
+            Self::OddDegrees(nodes) => write!(
+                f,
+                "{} nodes have an odd degree, at most 2 are allowed",
+                nodes.len()
+            ),
+            Self::Unbalanced(nodes) => {
+                write!(f, "{} nodes have different in and out degrees", nodes.len())
+            }
+        }
+    }
+}
+
+/// Graphs where every edge is directed follow the edge directions, any
+/// other graph is treated as undirected.
+pub fn is_directed(graph: &Graph) -> bool {
+    !graph.edges.is_empty() && graph.edges.iter().all(|edge| edge.directed)
+}
+
+fn start_node(graph: &Graph, directed: bool) -> Result<(EulerKind, usize), EulerError> {
+    let n = graph.nodes.len();
+    let mut out_degrees = vec![0i64; n];
+    let mut in_degrees = vec![0i64; n];
+
+    for edge in graph.edges.iter() {
+        out_degrees[edge.source] += 1;
+        in_degrees[edge.target] += 1;
+    }
+
+    let first = graph.edges.first().ok_or(EulerError::NoEdges)?.source;
+
+    if directed {
+        let unbalanced = (0..n)
+            .filter(|&node| out_degrees[node] != in_degrees[node])
+            .collect::<Vec<_>>();
+
+        let starts = unbalanced
+            .iter()
+            .filter(|&&node| out_degrees[node] - in_degrees[node] == 1)
+            .count();
+        let ends = unbalanced
+            .iter()
+            .filter(|&&node| in_degrees[node] - out_degrees[node] == 1)
+            .count();
+
+        return match unbalanced.len() {
+            0 => Ok((EulerKind::Circuit, first)),
+            2 if starts == 1 && ends == 1 => Ok((
+                EulerKind::Path,
+                unbalanced
+                    .into_iter()
+                    .find(|&node| out_degrees[node] > in_degrees[node])
+                    .unwrap(),
+            )),
+            _ => Err(EulerError::Unbalanced(unbalanced)),
+        };
+    }
+
+    let odd = (0..n)
+        .filter(|&node| (out_degrees[node] + in_degrees[node]) % 2 == 1)
+        .collect::<Vec<_>>();
+
+    match odd.len() {
+        0 => Ok((EulerKind::Circuit, first)),
+        2 => Ok((EulerKind::Path, odd[0])),
+        _ => Err(EulerError::OddDegrees(odd)),
+    }
+}
+
+/// Checks the degree conditions and that all edges are connected, then
+/// builds the trail with Hierholzer's algorithm, recording every step.
+pub fn hierholzer(graph: &Graph) -> Result<EulerRun, EulerError> {
+    let n = graph.nodes.len();
+    let directed = is_directed(graph);
+    let (kind, start) = start_node(graph, directed)?;
+
+    let mut components = UnionFind::new(n);
+
+    for edge in graph.edges.iter() {
+        components.union(edge.source, edge.target);
+    }
+
+    let root = components.find(start);
+
+    if graph
+        .edges
+        .iter()
+        .any(|edge| components.find(edge.source) != root)
+    {
+        return Err(EulerError::Disconnected);
+    }
+
+    let adjacency = if directed {
+        graph.directed_adjacency()
+    } else {
+        graph.undirected_adjacency()
+    };
+
+    let mut used = vec![false; graph.edges.len()];
+    let mut next = vec![0; n];
+    let mut stack = vec![(start, NONE)];
+
+    let mut run = EulerRun {
+        kind,
+        directed,
+        steps: Vec::new(),
+        trail: Vec::new(),
+        nodes: Vec::new(),
+    };
+
+    while let Some(&(node, _)) = stack.last() {
+        while next[node] < adjacency[node].len() && used[adjacency[node][next[node]].1] {
+            next[node] += 1;
+        }
+
+        if let Some(&(neighbor, edge)) = adjacency[node].get(next[node]) {
+            used[edge] = true;
+            run.steps.push(EulerStep::Walk(edge));
+            stack.push((neighbor, edge));
+
+            continue;
+        }
+
+        let (node, edge) = stack.pop().unwrap();
+
+        run.steps.push(EulerStep::Close(node));
+        run.nodes.push(node);
+
+        if edge != NONE {
+            run.trail.push(edge);
+        }
+    }
+
+    run.trail.reverse();
+    run.nodes.reverse();
+
+    Ok(run)
+}
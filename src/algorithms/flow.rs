@@ -0,0 +1,250 @@
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+
+const EPSILON: f32 = 1e-6;
+const UNREACHED: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+pub struct FlowStep {
+    pub phase: usize,
+    pub path: Vec<usize>,
+    pub amount: f32,
+    pub flows: Vec<f32>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct FlowRun {
+    pub steps: Vec<FlowStep>,
+    pub value: f32,
+    pub flows: Vec<f32>,
+    pub source_side: Vec<bool>,
+}
+
+/// Residual network where edge `i` is split into arc `2 * i`, from its
+/// source to its target, and arc `2 * i + 1` going back. Undirected edges
+/// have the same capacity both ways.
+struct Network {
+    heads: Vec<usize>,
+    capacities: Vec<f32>,
+    flows: Vec<f32>,
+    outgoing: Vec<Vec<usize>>,
+}
+
+impl Network {
+    fn new(graph: &Graph) -> Self {
+        let mut network = Self {
+            heads: Vec::with_capacity(graph.edges.len() * 2),
+            capacities: Vec::with_capacity(graph.edges.len() * 2),
+            flows: vec![0.0; graph.edges.len() * 2],
+            outgoing: vec![Vec::new(); graph.nodes.len()],
+        };
+
+        for (index, edge) in graph.edges.iter().enumerate() {
+            let capacity = graph.weight(index).max(0.0);
+
+            network.heads.push(edge.target);
+            network.heads.push(edge.source);
+            network.capacities.push(capacity);
+            network
+                .capacities
+                .push(if edge.directed { 0.0 } else { capacity });
+
+            if edge.source != edge.target {
+                network.outgoing[edge.source].push(2 * index);
+                network.outgoing[edge.target].push(2 * index + 1);
+            }
+        }
+
+        network
+    }
+
+    fn residual(&self, arc: usize) -> f32 {
+        self.capacities[arc] - self.flows[arc]
+    }
+
+    fn augment(&mut self, arcs: &[usize]) -> f32 {
+        let amount = arcs
+            .iter()
+            .map(|&arc| self.residual(arc))
+            .fold(f32::INFINITY, f32::min);
+
+        for &arc in arcs {
+            self.flows[arc] += amount;
+            self.flows[arc ^ 1] -= amount;
+        }
+
+        amount
+    }
+
+    /// Net flow of every edge, negative when an undirected edge carries
+    /// flow from its target to its source.
+    fn edge_flows(&self) -> Vec<f32> {
+        self.flows.iter().step_by(2).copied().collect()
+    }
+
+    fn levels(&self, source: usize) -> Vec<usize> {
+        let mut levels = vec![UNREACHED; self.outgoing.len()];
+        let mut queue = VecDeque::from([source]);
+
+        levels[source] = 0;
+
+        while let Some(node) = queue.pop_front() {
+            for &arc in self.outgoing[node].iter() {
+                let head = self.heads[arc];
+
+                if levels[head] == UNREACHED && self.residual(arc) > EPSILON {
+                    levels[head] = levels[node] + 1;
+                    queue.push_back(head);
+                }
+            }
+        }
+
+        levels
+    }
+
+    fn record(&self, run: &mut FlowRun, phase: usize, arcs: &[usize], amount: f32) {
+        run.value += amount;
+        run.steps.push(FlowStep {
+            phase,
+            path: arcs.iter().map(|&arc| arc / 2).collect(),
+            amount,
+            flows: self.edge_flows(),
+        });
+    }
+
+    fn finish(self, mut run: FlowRun, source: usize) -> FlowRun {
+        run.flows = self.edge_flows();
+        run.source_side = self
+            .levels(source)
+            .into_iter()
+            .map(|level| level != UNREACHED)
+            .collect();
+
+        run
+    }
+}
+
+/// Augments along shortest paths found with breadth-first search.
+pub fn edmonds_karp(graph: &Graph, source: usize, sink: usize) -> FlowRun {
+    let n = graph.nodes.len();
+    let mut network = Network::new(graph);
+    let mut run = FlowRun::default();
+
+    if source == sink {
+        return network.finish(run, source);
+    }
+
+    loop {
+        let mut parents = vec![UNREACHED; n];
+        let mut queue = VecDeque::from([source]);
+        let mut reached = false;
+
+        while let Some(node) = queue.pop_front() {
+            if node == sink {
+                reached = true;
+
+                break;
+            }
+
+            for &arc in network.outgoing[node].iter() {
+                let head = network.heads[arc];
+
+                if head != source && parents[head] == UNREACHED && network.residual(arc) > EPSILON {
+                    parents[head] = arc;
+                    queue.push_back(head);
+                }
+            }
+        }
+
+        if !reached {
+            break;
+        }
+
+        let mut arcs = Vec::new();
+        let mut node = sink;
+
+        while node != source {
+            let arc = parents[node];
+
+            arcs.push(arc);
+            node = network.heads[arc ^ 1];
+        }
+
+        arcs.reverse();
+
+        let amount = network.augment(&arcs);
+        let phase = run.steps.len();
+
+        network.record(&mut run, phase, &arcs, amount);
+    }
+
+    network.finish(run, source)
+}
+
+/// Finds blocking flows on the level graph, one augmenting path at a time so
+/// that every path can be shown.
+pub fn dinic(graph: &Graph, source: usize, sink: usize) -> FlowRun {
+    let n = graph.nodes.len();
+    let mut network = Network::new(graph);
+    let mut run = FlowRun::default();
+
+    if source == sink {
+        return network.finish(run, source);
+    }
+
+    for phase in 0.. {
+        let mut levels = network.levels(source);
+
+        if levels[sink] == UNREACHED {
+            break;
+        }
+
+        let mut next = vec![0; n];
+
+        'paths: loop {
+            let mut arcs = Vec::new();
+            let mut node = source;
+
+            while node != sink {
+                let found = network.outgoing[node][next[node]..]
+                    .iter()
+                    .position(|&arc| {
+                        let head = network.heads[arc];
+
+                        levels[head] == levels[node] + 1 && network.residual(arc) > EPSILON
+                    });
+
+                match found {
+                    Some(offset) => {
+                        next[node] += offset;
+
+                        let arc = network.outgoing[node][next[node]];
+
+                        arcs.push(arc);
+                        node = network.heads[arc];
+                    }
+                    None => {
+                        next[node] = network.outgoing[node].len();
+
+                        // A dead end is removed from the level graph and the
+                        // search goes back one arc.
+                        let Some(arc) = arcs.pop() else {
+                            break 'paths;
+                        };
+
+                        levels[node] = UNREACHED;
+                        node = network.heads[arc ^ 1];
+                        next[node] += 1;
+                    }
+                }
+            }
+
+            let amount = network.augment(&arcs);
+
+            network.record(&mut run, phase, &arcs, amount);
+        }
+    }
+
+    network.finish(run, source)
+}
@@ -0,0 +1,190 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+
+use crate::graph::Graph;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HamiltonianResult {
+    Found {
+        nodes: Vec<usize>,
+        edges: Vec<usize>,
+    },
+    NotFound,
+    Cancelled,
+}
+
+struct Search<'a> {
+    adjacency: Vec<Vec<(usize, usize)>>,
+    cycle: bool,
+    start: usize,
+    visited: Vec<bool>,
+    nodes: Vec<usize>,
+    edges: Vec<usize>,
+    progress: &'a AtomicU64,
+    cancel: &'a AtomicBool,
+}
+
+impl Search<'_> {
+    fn extend(&mut self, node: usize) -> Option<bool> {
+        self.progress.fetch_add(1, Ordering::Relaxed);
+
+        if self.cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        if self.nodes.len() == self.visited.len() {
+            if !self.cycle {
+                return Some(true);
+            }
+
+            // The edge that led to the last node can not close the cycle
+            // too, but a parallel edge can.
+            let closing = self.adjacency[node].iter().find(|&&(neighbor, edge)| {
+                neighbor == self.start && self.edges.last() != Some(&edge)
+            });
+
+            return Some(match closing {
+                Some(&(_, edge)) => {
+                    self.edges.push(edge);
+
+                    true
+                }
+                None => false,
+            });
+        }
+
+        for index in 0..self.adjacency[node].len() {
+            let (neighbor, edge) = self.adjacency[node][index];
+
+            if self.visited[neighbor] {
+                continue;
+            }
+
+            self.visited[neighbor] = true;
+            self.nodes.push(neighbor);
+            self.edges.push(edge);
+
+            if self.extend(neighbor)? {
+                return Some(true);
+            }
+
+            self.visited[neighbor] = false;
+            self.nodes.pop();
+            self.edges.pop();
+        }
+
+        Some(false)
+    }
+}
+
+/// Searches for a path, or a cycle, going through every node once with
+/// backtracking. Directed edges can only be followed forward. `progress`
+/// counts the partial paths tried so far and setting `cancel` stops the
+/// search.
+pub fn search(
+    graph: &Graph,
+    cycle: bool,
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+) -> HamiltonianResult {
+    let n = graph.nodes.len();
+
+    if n == 0 {
+        return HamiltonianResult::NotFound;
+    }
+
+    let mut adjacency = graph.directed_adjacency();
+
+    // Trying the neighbors with the fewest options first finds paths
+    // sooner on most graphs (Warnsdorff's rule).
+    let degrees = adjacency.iter().map(Vec::len).collect::<Vec<_>>();
+
+    for list in adjacency.iter_mut() {
+        list.sort_by_key(|&(neighbor, _)| degrees[neighbor]);
+    }
+
+    // Every cycle goes through the first node, so it is the only start
+    // worth trying.
+    let starts = if cycle { 0..1 } else { 0..n };
+
+    for start in starts {
+        let mut search = Search {
+            adjacency: adjacency.clone(),
+            cycle,
+            start,
+            visited: vec![false; n],
+            nodes: vec![start],
+            edges: Vec::new(),
+            progress,
+            cancel,
+        };
+
+        search.visited[start] = true;
+
+        match search.extend(start) {
+            None => return HamiltonianResult::Cancelled,
+            Some(true) => {
+                return HamiltonianResult::Found {
+                    nodes: search.nodes,
+                    edges: search.edges,
+                }
+            }
+            Some(false) => {}
+        }
+    }
+
+    HamiltonianResult::NotFound
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphEdge;
+
+    fn run(n: usize, edges: &[(usize, usize)], cycle: bool) -> HamiltonianResult {
+        let mut graph = Graph::default();
+
+        for index in 0..n {
+            graph.add_node(index.to_string(), 0.0, 0.0);
+        }
+
+        for &(source, target) in edges {
+            graph.add_edge(GraphEdge::new(source, target, false));
+        }
+
+        search(&graph, cycle, &AtomicU64::new(0), &AtomicBool::new(false))
+    }
+
+    #[test]
+    fn single_edge_is_not_a_cycle() {
+        assert_eq!(run(2, &[(0, 1)], true), HamiltonianResult::NotFound);
+        assert_eq!(
+            run(2, &[(0, 1)], false),
+            HamiltonianResult::Found {
+                nodes: vec![0, 1],
+                edges: vec![0],
+            }
+        );
+    }
+
+    #[test]
+    fn parallel_edges_make_a_cycle() {
+        assert_eq!(
+            run(2, &[(0, 1), (0, 1)], true),
+            HamiltonianResult::Found {
+                nodes: vec![0, 1],
+                edges: vec![0, 1],
+            }
+        );
+    }
+
+    #[test]
+    fn triangle_is_a_cycle() {
+        let HamiltonianResult::Found { nodes, edges } = run(3, &[(0, 1), (1, 2), (2, 0)], true)
+        else {
+            panic!("no cycle in a triangle");
+        };
+
+        assert_eq!(nodes.len(), 3);
+        assert_eq!(edges.len(), 3);
+    }
+}
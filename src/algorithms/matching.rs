@@ -0,0 +1,444 @@
+use std::collections::{HashMap, VecDeque};
+
+use crate::graph::Graph;
+
+const NONE: usize = usize::MAX;
+
+pub enum Bipartition {
+    Sides(Vec<bool>),
+    OddCycle {
+        nodes: Vec<usize>,
+        edges: Vec<usize>,
+    },
+}
+
+/// Neighbor lists with the edge used to reach every neighbor. Loops are
+/// left out since they can not be part of a matching.
+fn neighbors(graph: &Graph) -> Vec<Vec<(usize, usize)>> {
+    let mut neighbors = vec![Vec::new(); graph.nodes.len()];
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if edge.source != edge.target {
+            neighbors[edge.source].push((edge.target, index));
+            neighbors[edge.target].push((edge.source, index));
+        }
+    }
+
+    neighbors
+}
+
+/// Two-colors the graph with breadth-first search, ignoring edge
+/// directions. When two neighbors end up with the same color, their paths
+/// to the closest common ancestor in the search tree close an odd cycle.
+pub fn bipartition(graph: &Graph) -> Bipartition {
+    let n = graph.nodes.len();
+
+    if let Some(index) = graph
+        .edges
+        .iter()
+        .position(|edge| edge.source == edge.target)
+    {
+        return Bipartition::OddCycle {
+            nodes: vec![graph.edges[index].source],
+            edges: vec![index],
+        };
+    }
+
+    let neighbors = neighbors(graph);
+    let mut sides = vec![false; n];
+    let mut depths = vec![NONE; n];
+    let mut parents = vec![(NONE, NONE); n];
+
+    for root in 0..n {
+        if depths[root] != NONE {
+            continue;
+        }
+
+        depths[root] = 0;
+
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(node) = queue.pop_front() {
+            for &(neighbor, edge) in neighbors[node].iter() {
+                if depths[neighbor] == NONE {
+                    depths[neighbor] = depths[node] + 1;
+                    sides[neighbor] = !sides[node];
+                    parents[neighbor] = (node, edge);
+                    queue.push_back(neighbor);
+                } else if sides[neighbor] == sides[node] {
+                    return odd_cycle(&depths, &parents, node, neighbor, edge);
+                }
+            }
+        }
+    }
+
+    Bipartition::Sides(sides)
+}
+
+fn odd_cycle(
+    depths: &[usize],
+    parents: &[(usize, usize)],
+    mut a: usize,
+    mut b: usize,
+    closing_edge: usize,
+) -> Bipartition {
+    let (mut path_a, mut path_b) = (vec![a], vec![b]);
+    let (mut edges_a, mut edges_b) = (Vec::new(), Vec::new());
+
+    while a != b {
+        if depths[a] >= depths[b] {
+            edges_a.push(parents[a].1);
+            a = parents[a].0;
+            path_a.push(a);
+        } else {
+            edges_b.push(parents[b].1);
+            b = parents[b].0;
+            path_b.push(b);
+        }
+    }
+
+    // Both paths end with the common ancestor, which is kept only once.
+    path_b.pop();
+    path_a.extend(path_b.into_iter().rev());
+    edges_a.extend(edges_b.into_iter().rev());
+    edges_a.push(closing_edge);
+
+    Bipartition::OddCycle {
+        nodes: path_a,
+        edges: edges_a,
+    }
+}
+
+fn matched_edges(graph: &Graph, mates: &[usize]) -> Vec<usize> {
+    let mut edges = HashMap::new();
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        edges
+            .entry((edge.source.min(edge.target), edge.source.max(edge.target)))
+            .or_insert(index);
+    }
+
+    let mut matching = (0..mates.len())
+        .filter(|&node| mates[node] != NONE && node < mates[node])
+        .map(|node| edges[&(node, mates[node])])
+        .collect::<Vec<_>>();
+
+    matching.sort_unstable();
+
+    matching
+}
+
+struct HopcroftKarp<'a> {
+    neighbors: &'a [Vec<(usize, usize)>],
+    mates: Vec<usize>,
+    distances: Vec<usize>,
+}
+
+impl HopcroftKarp<'_> {
+    fn layers(&mut self, left: &[usize]) -> bool {
+        let mut queue = VecDeque::new();
+        let mut found = false;
+
+        for &node in left {
+            if self.mates[node] == NONE {
+                self.distances[node] = 0;
+                queue.push_back(node);
+            } else {
+                self.distances[node] = NONE;
+            }
+        }
+
+        while let Some(node) = queue.pop_front() {
+            for &(neighbor, _) in self.neighbors[node].iter() {
+                let mate = self.mates[neighbor];
+
+                if mate == NONE {
+                    found = true;
+                } else if self.distances[mate] == NONE {
+                    self.distances[mate] = self.distances[node] + 1;
+                    queue.push_back(mate);
+                }
+            }
+        }
+
+        found
+    }
+
+    fn augment(&mut self, node: usize) -> bool {
+        for index in 0..self.neighbors[node].len() {
+            let neighbor = self.neighbors[node][index].0;
+            let mate = self.mates[neighbor];
+
+            let reachable = mate == NONE
+                || (self.distances[mate] == self.distances[node] + 1 && self.augment(mate));
+
+            if reachable {
+                self.mates[node] = neighbor;
+                self.mates[neighbor] = node;
+
+                return true;
+            }
+        }
+
+        self.distances[node] = NONE;
+
+        false
+    }
+}
+
+/// Maximum matching of a bipartite graph, given the side of every node.
+pub fn hopcroft_karp(graph: &Graph, sides: &[bool]) -> Vec<usize> {
+    let n = graph.nodes.len();
+    let neighbors = neighbors(graph);
+    let left = (0..n).filter(|&node| !sides[node]).collect::<Vec<_>>();
+
+    let mut search = HopcroftKarp {
+        neighbors: &neighbors,
+        mates: vec![NONE; n],
+        distances: vec![NONE; n],
+    };
+
+    while search.layers(&left) {
+        for &node in left.iter() {
+            if search.mates[node] == NONE {
+                search.augment(node);
+            }
+        }
+    }
+
+    matched_edges(graph, &search.mates)
+}
+
+struct Blossom {
+    neighbors: Vec<Vec<usize>>,
+    mates: Vec<usize>,
+    parents: Vec<usize>,
+    bases: Vec<usize>,
+    used: Vec<bool>,
+    in_blossom: Vec<bool>,
+}
+
+impl Blossom {
+    fn lowest_common_ancestor(&self, mut a: usize, mut b: usize) -> usize {
+        let mut on_path = vec![false; self.mates.len()];
+
+        loop {
+            a = self.bases[a];
+            on_path[a] = true;
+
+            if self.mates[a] == NONE {
+                break;
+            }
+
+            a = self.parents[self.mates[a]];
+        }
+
+        loop {
+            b = self.bases[b];
+
+            if on_path[b] {
+                return b;
+            }
+
+            b = self.parents[self.mates[b]];
+        }
+    }
+
+    fn mark_path(&mut self, mut node: usize, base: usize, mut child: usize) {
+        while self.bases[node] != base {
+            let mate = self.mates[node];
+
+            self.in_blossom[self.bases[node]] = true;
+            self.in_blossom[self.bases[mate]] = true;
+            self.parents[node] = child;
+            child = mate;
+            node = self.parents[mate];
+        }
+    }
+
+    /// Searches for an augmenting path from `root`, shrinking the odd
+    /// cycles (blossoms) it meets, and returns the free node it ends at.
+    fn find_path(&mut self, root: usize) -> Option<usize> {
+        let n = self.mates.len();
+
+        self.used.fill(false);
+        self.parents.fill(NONE);
+
+        for (node, base) in self.bases.iter_mut().enumerate() {
+            *base = node;
+        }
+
+        self.used[root] = true;
+
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(node) = queue.pop_front() {
+            for index in 0..self.neighbors[node].len() {
+                let neighbor = self.neighbors[node][index];
+
+                if self.bases[node] == self.bases[neighbor] || self.mates[node] == neighbor {
+                    continue;
+                }
+
+                if neighbor == root
+                    || (self.mates[neighbor] != NONE && self.parents[self.mates[neighbor]] != NONE)
+                {
+                    let base = self.lowest_common_ancestor(node, neighbor);
+
+                    self.in_blossom.fill(false);
+                    self.mark_path(node, base, neighbor);
+                    self.mark_path(neighbor, base, node);
+
+                    for other in 0..n {
+                        if self.in_blossom[self.bases[other]] {
+                            self.bases[other] = base;
+
+                            if !self.used[other] {
+                                self.used[other] = true;
+                                queue.push_back(other);
+                            }
+                        }
+                    }
+                } else if self.parents[neighbor] == NONE {
+                    self.parents[neighbor] = node;
+
+                    if self.mates[neighbor] == NONE {
+                        return Some(neighbor);
+                    }
+
+                    let mate = self.mates[neighbor];
+
+                    self.used[mate] = true;
+                    queue.push_back(mate);
+                }
+            }
+        }
+
+        None
+    }
+}
+
+/// Maximum matching of any graph with Edmonds' blossom algorithm.
+pub fn blossom(graph: &Graph) -> Vec<usize> {
+    let n = graph.nodes.len();
+
+    let mut search = Blossom {
+        neighbors: neighbors(graph)
+            .into_iter()
+            .map(|list| list.into_iter().map(|(neighbor, _)| neighbor).collect())
+            .collect(),
+        mates: vec![NONE; n],
+        parents: vec![NONE; n],
+        bases: (0..n).collect(),
+        used: vec![false; n],
+        in_blossom: vec![false; n],
+    };
+
+    for root in 0..n {
+        if search.mates[root] != NONE {
+            continue;
+        }
+
+        let Some(mut node) = search.find_path(root) else {
+            continue;
+        };
+
+        while node != NONE {
+            let parent = search.parents[node];
+            let next = search.mates[parent];
+
+            search.mates[node] = parent;
+            search.mates[parent] = node;
+            node = next;
+        }
+    }
+
+    matched_edges(graph, &search.mates)
+}
+
+/// Returns the nodes that are not an endpoint of any matched edge.
+pub fn unmatched_nodes(graph: &Graph, matching: &[usize]) -> Vec<usize> {
+    let mut matched = vec![false; graph.nodes.len()];
+
+    for &edge in matching {
+        matched[graph.edges[edge].source] = true;
+        matched[graph.edges[edge].target] = true;
+    }
+
+    (0..graph.nodes.len())
+        .filter(|&node| !matched[node])
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphEdge;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::default();
+
+        for index in 0..n {
+            graph.add_node(index.to_string(), 0.0, 0.0);
+        }
+
+        for &(source, target) in edges {
+            graph.add_edge(GraphEdge::new(source, target, false));
+        }
+
+        graph
+    }
+
+    /// Checks that no two matched edges share a node and returns the
+    /// number of matched edges.
+    fn matching_size(graph: &Graph, matching: &[usize]) -> usize {
+        let mut matched = vec![false; graph.nodes.len()];
+
+        for &edge in matching {
+            let edge = &graph.edges[edge];
+
+            for node in [edge.source, edge.target] {
+                assert!(!matched[node], "node {node} is matched twice");
+
+                matched[node] = true;
+            }
+        }
+
+        matching.len()
+    }
+
+    #[test]
+    fn odd_cycle_leaves_one_node_unmatched() {
+        let graph = graph(5, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0)]);
+        let matching = blossom(&graph);
+
+        assert_eq!(matching_size(&graph, &matching), 2);
+        assert_eq!(unmatched_nodes(&graph, &matching).len(), 1);
+    }
+
+    #[test]
+    fn odd_cycle_with_pendant_is_perfectly_matched() {
+        // The first two roots match 0 - 1 and 2 - 3, so the augmenting
+        // path from 4 to the pendant node 5 goes through the contracted
+        // cycle.
+        let graph = graph(6, &[(0, 1), (1, 2), (2, 3), (3, 4), (4, 0), (1, 5)]);
+        let matching = blossom(&graph);
+
+        assert_eq!(matching_size(&graph, &matching), 3);
+        assert_eq!(unmatched_nodes(&graph, &matching), []);
+    }
+
+    #[test]
+    fn blossom_agrees_with_hopcroft_karp_on_bipartite_graphs() {
+        let graph = graph(7, &[(0, 4), (0, 5), (1, 4), (2, 5), (2, 6), (3, 6), (3, 4)]);
+
+        let Bipartition::Sides(sides) = bipartition(&graph) else {
+            panic!("the graph is bipartite");
+        };
+
+        assert_eq!(
+            matching_size(&graph, &blossom(&graph)),
+            matching_size(&graph, &hopcroft_karp(&graph, &sides))
+        );
+    }
+}
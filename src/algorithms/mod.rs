@@ -0,0 +1,65 @@
+pub mod centrality;
+pub mod coloring;
+pub mod community;
+pub mod connectivity;
+pub mod euler;
+pub mod flow;
+pub mod hamiltonian;
+pub mod matching;
+pub mod mst;
+pub mod planarity;
+pub mod shortest_paths;
+pub mod topological;
+pub mod transform;
+
+pub struct UnionFind {
+    parents: Vec<usize>,
+    ranks: Vec<u8>,
+}
+
+impl UnionFind {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parents: (0..n).collect(),
+            ranks: vec![0; n],
+        }
+    }
+
+    pub fn find(&mut self, node: usize) -> usize {
+        let mut root = node;
+
+        while self.parents[root] != root {
+            root = self.parents[root];
+        }
+
+        let mut node = node;
+
+        while self.parents[node] != root {
+            let parent = self.parents[node];
+
+            self.parents[node] = root;
+            node = parent;
+        }
+
+        root
+    }
+
+    pub fn union(&mut self, a: usize, b: usize) -> bool {
+        let (a, b) = (self.find(a), self.find(b));
+
+        if a == b {
+            return false;
+        }
+
+        match self.ranks[a].cmp(&self.ranks[b]) {
+            std::cmp::Ordering::Less => self.parents[a] = b,
+            std::cmp::Ordering::Greater => self.parents[b] = a,
+            std::cmp::Ordering::Equal => {
+                self.parents[b] = a;
+                self.ranks[a] += 1;
+            }
+        }
+
+        true
+    }
+}
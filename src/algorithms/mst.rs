@@ -0,0 +1,158 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::graph::Graph;
+
+use super::UnionFind;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MstAction {
+    Start(usize),
+    Accept(usize),
+    Reject(usize),
+}
+
+#[derive(Clone, Debug)]
+pub struct MstStep {
+    pub action: MstAction,
+    pub queue: Vec<(f32, usize)>,
+    pub components: Vec<usize>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct MstRun {
+    pub steps: Vec<MstStep>,
+    pub sorted_edges: Vec<usize>,
+    pub tree_edges: Vec<usize>,
+    pub total_weight: f32,
+}
+
+#[derive(PartialEq)]
+struct QueuedEdge {
+    weight: f32,
+    edge: usize,
+    node: usize,
+}
+
+impl Eq for QueuedEdge {}
+
+impl Ord for QueuedEdge {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .weight
+            .total_cmp(&self.weight)
+            .then_with(|| other.edge.cmp(&self.edge))
+    }
+}
+
+impl PartialOrd for QueuedEdge {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+fn queue_snapshot(queue: &BinaryHeap<QueuedEdge>) -> Vec<(f32, usize)> {
+    let mut snapshot = queue
+        .iter()
+        .map(|queued| (queued.weight, queued.edge))
+        .collect::<Vec<_>>();
+
+    snapshot.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    snapshot
+}
+
+fn add_to_tree(
+    graph: &Graph,
+    adjacency: &[Vec<(usize, usize)>],
+    node: usize,
+    in_tree: &mut [bool],
+    queue: &mut BinaryHeap<QueuedEdge>,
+) {
+    in_tree[node] = true;
+
+    for &(neighbor, edge) in adjacency[node].iter() {
+        if !in_tree[neighbor] {
+            queue.push(QueuedEdge {
+                weight: graph.weight(edge),
+                edge,
+                node: neighbor,
+            });
+        }
+    }
+}
+
+pub fn prim(graph: &Graph, start: usize) -> MstRun {
+    let n = graph.nodes.len();
+    let adjacency = graph.undirected_adjacency();
+
+    let mut run = MstRun::default();
+    let mut in_tree = vec![false; n];
+    let mut queue = BinaryHeap::new();
+
+    for root in (start..n).chain(0..start.min(n)) {
+        if in_tree[root] {
+            continue;
+        }
+
+        add_to_tree(graph, &adjacency, root, &mut in_tree, &mut queue);
+
+        run.steps.push(MstStep {
+            action: MstAction::Start(root),
+            queue: queue_snapshot(&queue),
+            components: Vec::new(),
+        });
+
+        while let Some(QueuedEdge { weight, edge, node }) = queue.pop() {
+            let action = if in_tree[node] {
+                MstAction::Reject(edge)
+            } else {
+                add_to_tree(graph, &adjacency, node, &mut in_tree, &mut queue);
+
+                run.tree_edges.push(edge);
+                run.total_weight += weight;
+
+                MstAction::Accept(edge)
+            };
+
+            run.steps.push(MstStep {
+                action,
+                queue: queue_snapshot(&queue),
+                components: Vec::new(),
+            });
+        }
+    }
+
+    run
+}
+
+pub fn kruskal(graph: &Graph) -> MstRun {
+    let n = graph.nodes.len();
+
+    let mut run = MstRun::default();
+    let mut components = UnionFind::new(n);
+
+    run.sorted_edges = (0..graph.edges.len()).collect();
+    run.sorted_edges
+        .sort_by(|&a, &b| graph.weight(a).total_cmp(&graph.weight(b)).then(a.cmp(&b)));
+
+    for &edge in run.sorted_edges.iter() {
+        let (source, target) = (graph.edges[edge].source, graph.edges[edge].target);
+
+        let action = if components.union(source, target) {
+            run.tree_edges.push(edge);
+            run.total_weight += graph.weight(edge);
+
+            MstAction::Accept(edge)
+        } else {
+            MstAction::Reject(edge)
+        };
+
+        run.steps.push(MstStep {
+            action,
+            queue: Vec::new(),
+            components: (0..n).map(|node| components.find(node)).collect(),
+        });
+    }
+
+    run
+}
@@ -0,0 +1,1264 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+};
+
+use crate::graph::Graph;
+
+const NONE: usize = usize::MAX;
+
+/// Return edges, given by their lowest and highest edge, that have to be
+/// on the same side.
+#[derive(Clone, Copy, Default)]
+struct Interval {
+    low: Option<usize>,
+    high: Option<usize>,
+}
+
+impl Interval {
+    fn is_empty(&self) -> bool {
+        self.low.is_none() && self.high.is_none()
+    }
+}
+
+/// Two intervals that have to be on different sides.
+#[derive(Clone, Copy, Default)]
+struct ConflictPair {
+    left: Interval,
+    right: Interval,
+}
+
+impl ConflictPair {
+    fn swap(&mut self) {
+        std::mem::swap(&mut self.left, &mut self.right);
+    }
+}
+
+/// Clockwise order of the neighbors around every node of a simple graph,
+/// stored as a doubly linked list per node.
+struct Embedding {
+    clockwise: HashMap<(usize, usize), usize>,
+    counterclockwise: HashMap<(usize, usize), usize>,
+    first: Vec<Option<usize>>,
+}
+
+impl Embedding {
+    fn new(n: usize) -> Self {
+        Self {
+            clockwise: HashMap::new(),
+            counterclockwise: HashMap::new(),
+            first: vec![None; n],
+        }
+    }
+
+    fn has_edge(&self, start: usize, end: usize) -> bool {
+        self.clockwise.contains_key(&(start, end))
+    }
+
+    /// Inserts `end` right after `reference` in the clockwise order around
+    /// `start`.
+    fn add_half_edge_cw(&mut self, start: usize, end: usize, reference: Option<usize>) {
+        let Some(reference) = reference else {
+            self.clockwise.insert((start, end), end);
+            self.counterclockwise.insert((start, end), end);
+            self.first[start] = Some(end);
+
+            return;
+        };
+
+        let next = self.clockwise[&(start, reference)];
+
+        self.clockwise.insert((start, reference), end);
+        self.clockwise.insert((start, end), next);
+        self.counterclockwise.insert((start, next), end);
+        self.counterclockwise.insert((start, end), reference);
+    }
+
+    /// Inserts `end` right before `reference` in the clockwise order around
+    /// `start`.
+    fn add_half_edge_ccw(&mut self, start: usize, end: usize, reference: Option<usize>) {
+        let Some(reference) = reference else {
+            self.add_half_edge_cw(start, end, None);
+
+            return;
+        };
+
+        let previous = self.counterclockwise[&(start, reference)];
+
+        self.add_half_edge_cw(start, end, Some(previous));
+
+        if self.first[start] == Some(reference) {
+            self.first[start] = Some(end);
+        }
+    }
+
+    fn add_half_edge_first(&mut self, start: usize, end: usize) {
+        self.add_half_edge_ccw(start, end, self.first[start]);
+    }
+
+    /// Adds an edge from `start` to `end` inside the face that follows the
+    /// half-edge from `start` to `reference`.
+    fn add_edge_in_face(&mut self, start: usize, end: usize, reference: usize) {
+        self.add_half_edge_cw(start, end, Some(reference));
+        self.add_half_edge_ccw(end, start, Some(reference));
+    }
+
+    /// The half-edge after `start` to `end` along their face.
+    fn next_face_half_edge(&self, start: usize, end: usize) -> (usize, usize) {
+        (end, self.counterclockwise[&(end, start)])
+    }
+
+    fn neighbors(&self, node: usize) -> Vec<usize> {
+        let Some(first) = self.first[node] else {
+            return Vec::new();
+        };
+
+        let mut neighbors = vec![first];
+        let mut current = self.clockwise[&(node, first)];
+
+        while current != first {
+            neighbors.push(current);
+            current = self.clockwise[&(node, current)];
+        }
+
+        neighbors
+    }
+}
+
+/// The left-right planarity test of de Fraysseix and Rosenstiehl, as
+/// described by Brandes. The graph is first oriented by a depth-first
+/// search, then a second search checks that the return edges of every
+/// node can be split between its left and right side.
+struct LeftRight {
+    neighbors: Vec<Vec<usize>>,
+    edge_ids: HashMap<(usize, usize), usize>,
+    sources: Vec<usize>,
+    targets: Vec<usize>,
+    heights: Vec<usize>,
+    parent_edges: Vec<Option<usize>>,
+    lowpt: Vec<usize>,
+    lowpt2: Vec<usize>,
+    nesting_depth: Vec<usize>,
+    outgoing: Vec<Vec<usize>>,
+    stack: Vec<ConflictPair>,
+    stack_bottom: Vec<usize>,
+    lowpt_edge: Vec<usize>,
+    refs: Vec<Option<usize>>,
+    sides: Vec<i8>,
+    roots: Vec<usize>,
+}
+
+impl LeftRight {
+    /// Keeps a single edge between any two nodes and drops loops, neither
+    /// of which changes planarity.
+    fn new(graph: &Graph) -> Self {
+        let n = graph.nodes.len();
+        let mut neighbors = vec![Vec::new(); n];
+        let mut pairs = graph
+            .edges
+            .iter()
+            .filter(|edge| edge.source != edge.target)
+            .map(|edge| (edge.source.min(edge.target), edge.source.max(edge.target)))
+            .collect::<Vec<_>>();
+
+        pairs.sort_unstable();
+        pairs.dedup();
+
+        for (a, b) in pairs {
+            neighbors[a].push(b);
+            neighbors[b].push(a);
+        }
+
+        Self {
+            neighbors,
+            edge_ids: HashMap::new(),
+            sources: Vec::new(),
+            targets: Vec::new(),
+            heights: vec![NONE; n],
+            parent_edges: vec![None; n],
+            lowpt: Vec::new(),
+            lowpt2: Vec::new(),
+            nesting_depth: Vec::new(),
+            outgoing: vec![Vec::new(); n],
+            stack: Vec::new(),
+            stack_bottom: Vec::new(),
+            lowpt_edge: Vec::new(),
+            refs: Vec::new(),
+            sides: Vec::new(),
+            roots: Vec::new(),
+        }
+    }
+
+    fn edge_count(&self) -> usize {
+        self.neighbors.iter().map(Vec::len).sum::<usize>() / 2
+    }
+
+    /// Orients the edges away from the roots of a depth-first search and
+    /// computes the lowpoints, using an explicit stack so that long paths
+    /// do not overflow the call stack.
+    fn orient(&mut self, root: usize) {
+        let mut stack = vec![(root, 0)];
+
+        while let Some((node, index)) = stack.last_mut() {
+            let node = *node;
+
+            let Some(&neighbor) = self.neighbors[node].get(*index) else {
+                stack.pop();
+
+                if let Some(edge) = self.parent_edges[node] {
+                    self.finish_edge(edge);
+                }
+
+                continue;
+            };
+
+            *index += 1;
+
+            let key = (node.min(neighbor), node.max(neighbor));
+
+            if self.edge_ids.contains_key(&key) {
+                continue;
+            }
+
+            let edge = self.sources.len();
+
+            self.edge_ids.insert(key, edge);
+            self.sources.push(node);
+            self.targets.push(neighbor);
+            self.lowpt.push(self.heights[node]);
+            self.lowpt2.push(self.heights[node]);
+            self.nesting_depth.push(0);
+            self.outgoing[node].push(edge);
+
+            if self.heights[neighbor] == NONE {
+                self.parent_edges[neighbor] = Some(edge);
+                self.heights[neighbor] = self.heights[node] + 1;
+                stack.push((neighbor, 0));
+            } else {
+                self.lowpt[edge] = self.heights[neighbor];
+                self.finish_edge(edge);
+            }
+        }
+    }
+
+    /// Called once the lowpoints of the edge are known.
+    fn finish_edge(&mut self, edge: usize) {
+        let source = self.sources[edge];
+
+        // Edges whose return edges reach lower come first, and of two edges
+        // with the same lowpoint the one that also returns higher (a
+        // chordal one) comes second.
+        self.nesting_depth[edge] = 2 * self.lowpt[edge];
+
+        if self.lowpt2[edge] < self.heights[source] {
+            self.nesting_depth[edge] += 1;
+        }
+
+        let Some(parent) = self.parent_edges[source] else {
+            return;
+        };
+
+        if self.lowpt[edge] < self.lowpt[parent] {
+            self.lowpt2[parent] = self.lowpt[parent].min(self.lowpt2[edge]);
+            self.lowpt[parent] = self.lowpt[edge];
+        } else if self.lowpt[edge] > self.lowpt[parent] {
+            self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt[edge]);
+        } else {
+            self.lowpt2[parent] = self.lowpt2[parent].min(self.lowpt2[edge]);
+        }
+    }
+
+    fn conflicting(&self, interval: &Interval, edge: usize) -> bool {
+        interval
+            .high
+            .map_or(false, |high| self.lowpt[high] > self.lowpt[edge])
+    }
+
+    fn lowest(&self, pair: &ConflictPair) -> usize {
+        [pair.left.low, pair.right.low]
+            .into_iter()
+            .flatten()
+            .map(|edge| self.lowpt[edge])
+            .min()
+            .unwrap_or(NONE)
+    }
+
+    /// Second depth-first search, along the edges in nesting order, that
+    /// fails as soon as the return edges can not be split between the two
+    /// sides.
+    fn test(&mut self, root: usize) -> bool {
+        let mut stack = vec![(root, 0)];
+
+        while let Some((node, index)) = stack.last_mut() {
+            let node = *node;
+
+            let Some(&edge) = self.outgoing[node].get(*index) else {
+                stack.pop();
+
+                let Some(parent) = self.parent_edges[node] else {
+                    continue;
+                };
+
+                self.remove_back_edges(parent);
+
+                if !self.integrate(parent) {
+                    return false;
+                }
+
+                continue;
+            };
+
+            *index += 1;
+
+            let target = self.targets[edge];
+
+            self.stack_bottom[edge] = self.stack.len();
+
+            if self.parent_edges[target] == Some(edge) {
+                stack.push((target, 0));
+
+                continue;
+            }
+
+            self.lowpt_edge[edge] = edge;
+            self.stack.push(ConflictPair {
+                left: Interval::default(),
+                right: Interval {
+                    low: Some(edge),
+                    high: Some(edge),
+                },
+            });
+
+            if !self.integrate(edge) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Adds the return edges of an edge to the constraints of its source,
+    /// once every edge below it is done.
+    fn integrate(&mut self, edge: usize) -> bool {
+        let source = self.sources[edge];
+
+        if self.lowpt[edge] >= self.heights[source] {
+            return true;
+        }
+
+        // The edge has return edges, which is never the case at a root.
+        let parent = self.parent_edges[source].unwrap();
+
+        if self.outgoing[source][0] == edge {
+            self.lowpt_edge[parent] = self.lowpt_edge[edge];
+
+            true
+        } else {
+            self.add_constraints(edge, parent)
+        }
+    }
+
+    fn add_constraints(&mut self, edge: usize, parent: usize) -> bool {
+        let mut pair = ConflictPair::default();
+
+        // Return edges of this edge all go on the right.
+        loop {
+            let Some(mut other) = self.stack.pop() else {
+                break;
+            };
+
+            if !other.left.is_empty() {
+                other.swap();
+            }
+
+            if !other.left.is_empty() {
+                return false;
+            }
+
+            let low = other.right.low.unwrap();
+
+            if self.lowpt[low] > self.lowpt[parent] {
+                if pair.right.is_empty() {
+                    pair.right = other.right;
+                } else if let Some(pair_low) = pair.right.low {
+                    self.refs[pair_low] = other.right.high;
+                }
+
+                pair.right.low = other.right.low;
+            } else {
+                self.refs[low] = Some(self.lowpt_edge[parent]);
+            }
+
+            if self.stack.len() == self.stack_bottom[edge] {
+                break;
+            }
+        }
+
+        // Return edges of the earlier edges that conflict with them go on
+        // the left.
+        while let Some(top) = self.stack.last() {
+            if !self.conflicting(&top.left, edge) && !self.conflicting(&top.right, edge) {
+                break;
+            }
+
+            let mut other = self.stack.pop().unwrap();
+
+            if self.conflicting(&other.right, edge) {
+                other.swap();
+            }
+
+            if self.conflicting(&other.right, edge) {
+                return false;
+            }
+
+            if let Some(pair_low) = pair.right.low {
+                self.refs[pair_low] = other.right.high;
+            }
+
+            if other.right.low.is_some() {
+                pair.right.low = other.right.low;
+            }
+
+            if pair.left.is_empty() {
+                pair.left = other.left;
+            } else if let Some(pair_low) = pair.left.low {
+                self.refs[pair_low] = other.left.high;
+            }
+
+            pair.left.low = other.left.low;
+        }
+
+        if !pair.left.is_empty() || !pair.right.is_empty() {
+            self.stack.push(pair);
+        }
+
+        true
+    }
+
+    fn remove_back_edges(&mut self, edge: usize) {
+        let source = self.sources[edge];
+
+        // Pairs whose return edges all end at the source are done.
+        while let Some(top) = self.stack.last() {
+            if self.lowest(top) != self.heights[source] {
+                break;
+            }
+
+            let pair = self.stack.pop().unwrap();
+
+            if let Some(low) = pair.left.low {
+                self.sides[low] = -1;
+            }
+        }
+
+        if let Some(mut pair) = self.stack.pop() {
+            while let Some(high) = pair.left.high {
+                if self.targets[high] != source {
+                    break;
+                }
+
+                pair.left.high = self.refs[high];
+            }
+
+            if pair.left.high.is_none() {
+                if let Some(low) = pair.left.low.take() {
+                    self.refs[low] = pair.right.low;
+                    self.sides[low] = -1;
+                }
+            }
+
+            while let Some(high) = pair.right.high {
+                if self.targets[high] != source {
+                    break;
+                }
+
+                pair.right.high = self.refs[high];
+            }
+
+            if pair.right.high.is_none() {
+                if let Some(low) = pair.right.low.take() {
+                    self.refs[low] = pair.left.low;
+                    self.sides[low] = -1;
+                }
+            }
+
+            self.stack.push(pair);
+        }
+
+        // The edge goes on the side of its highest return edge.
+        if self.lowpt[edge] < self.heights[source] {
+            if let Some(top) = self.stack.last() {
+                let (left, right) = (top.left.high, top.right.high);
+
+                self.refs[edge] = match (left, right) {
+                    (Some(left), Some(right)) if self.lowpt[left] > self.lowpt[right] => Some(left),
+                    (Some(left), None) => Some(left),
+                    _ => right,
+                };
+            }
+        }
+    }
+
+    fn run(&mut self) -> bool {
+        let n = self.neighbors.len();
+
+        if n > 2 && self.edge_count() > 3 * n - 6 {
+            return false;
+        }
+
+        for node in 0..n {
+            if self.heights[node] == NONE {
+                self.heights[node] = 0;
+                self.roots.push(node);
+                self.orient(node);
+            }
+        }
+
+        for list in self.outgoing.iter_mut() {
+            list.sort_by_key(|&edge| self.nesting_depth[edge]);
+        }
+
+        let m = self.sources.len();
+
+        self.stack_bottom = vec![0; m];
+        self.lowpt_edge = vec![NONE; m];
+        self.refs = vec![None; m];
+        self.sides = vec![1; m];
+
+        for index in 0..self.roots.len() {
+            if !self.test(self.roots[index]) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Turns the side of an edge relative to the edge it refers to into an
+    /// absolute side.
+    fn sign(&mut self, edge: usize) -> i8 {
+        let mut chain = vec![edge];
+
+        while let Some(next) = self.refs[*chain.last().unwrap()] {
+            chain.push(next);
+        }
+
+        for index in (0..chain.len() - 1).rev() {
+            let (edge, next) = (chain[index], chain[index + 1]);
+
+            self.sides[edge] *= self.sides[next];
+            self.refs[edge] = None;
+        }
+
+        self.sides[edge]
+    }
+
+    /// Builds the embedding once the test succeeded: the outgoing edges of
+    /// every node are ordered by their side and nesting depth, then a last
+    /// depth-first search inserts every back edge next to the tree edges
+    /// it has to go around.
+    fn embed(mut self) -> Embedding {
+        let n = self.neighbors.len();
+        let mut embedding = Embedding::new(n);
+
+        let depths = (0..self.sources.len())
+            .map(|edge| self.sign(edge) as isize * self.nesting_depth[edge] as isize)
+            .collect::<Vec<_>>();
+
+        for node in 0..n {
+            self.outgoing[node].sort_by_key(|&edge| depths[edge]);
+
+            let mut previous = None;
+
+            for &edge in self.outgoing[node].iter() {
+                let target = self.targets[edge];
+
+                embedding.add_half_edge_cw(node, target, previous);
+                previous = Some(target);
+            }
+        }
+
+        let mut left_refs = vec![NONE; n];
+        let mut right_refs = vec![NONE; n];
+
+        for index in 0..self.roots.len() {
+            let mut stack = vec![(self.roots[index], 0)];
+
+            while let Some((node, index)) = stack.last_mut() {
+                let node = *node;
+
+                let Some(&edge) = self.outgoing[node].get(*index) else {
+                    stack.pop();
+
+                    continue;
+                };
+
+                *index += 1;
+
+                let target = self.targets[edge];
+
+                if self.parent_edges[target] == Some(edge) {
+                    embedding.add_half_edge_first(target, node);
+                    left_refs[node] = target;
+                    right_refs[node] = target;
+                    stack.push((target, 0));
+                } else if self.sides[edge] == 1 {
+                    embedding.add_half_edge_cw(target, node, Some(right_refs[target]));
+                } else {
+                    embedding.add_half_edge_ccw(target, node, Some(left_refs[target]));
+                    left_refs[target] = node;
+                }
+            }
+        }
+
+        embedding
+    }
+}
+
+/// Whether the graph can be drawn without crossing edges. Edge directions,
+/// loops and parallel edges do not matter.
+pub fn is_planar(graph: &Graph) -> bool {
+    LeftRight::new(graph).run()
+}
+
+/// Walks the face to the right of the half-edge from `start` to `next` and
+/// adds an edge wherever a node comes back, so that the boundary of every
+/// face becomes a simple cycle. Returns the nodes of the face, or `None`
+/// when the face was already walked.
+fn make_biconnected(
+    embedding: &mut Embedding,
+    start: usize,
+    next: usize,
+    walked: &mut HashSet<(usize, usize)>,
+) -> Option<Vec<usize>> {
+    if !walked.insert((start, next)) {
+        return None;
+    }
+
+    let mut face = vec![start];
+    let mut face_set = HashSet::from([start]);
+    let (mut v1, mut v2) = (start, next);
+    let (_, mut v3) = embedding.next_face_half_edge(v1, v2);
+
+    while v2 != start || v3 != next {
+        if face_set.contains(&v2) {
+            embedding.add_edge_in_face(v1, v3, v2);
+            walked.insert((v2, v3));
+            walked.insert((v3, v1));
+            v2 = v1;
+        } else {
+            face_set.insert(v2);
+            face.push(v2);
+        }
+
+        v1 = v2;
+        (v2, v3) = embedding.next_face_half_edge(v2, v3);
+        walked.insert((v1, v2));
+    }
+
+    Some(face)
+}
+
+/// Splits a face bounded by a simple cycle into triangles, skipping the
+/// chords that already exist elsewhere so that no parallel edge is made.
+fn triangulate_face(embedding: &mut Embedding, mut v1: usize, mut v2: usize) {
+    let (_, mut v3) = embedding.next_face_half_edge(v1, v2);
+    let (_, mut v4) = embedding.next_face_half_edge(v2, v3);
+
+    if v1 == v2 || v1 == v3 {
+        return;
+    }
+
+    while v1 != v4 {
+        if embedding.has_edge(v1, v3) {
+            (v1, v2, v3) = (v2, v3, v4);
+        } else {
+            embedding.add_edge_in_face(v1, v3, v2);
+            (v2, v3) = (v3, v4);
+        }
+
+        (_, v4) = embedding.next_face_half_edge(v2, v3);
+    }
+}
+
+/// Adds edges to the embedding until every face is a triangle, and returns
+/// one of these faces.
+fn triangulate(embedding: &mut Embedding, roots: &[usize]) -> [usize; 3] {
+    let n = embedding.first.len();
+
+    // One node of every component is linked to one of the next, which also
+    // gives every isolated node a neighbor.
+    for pair in roots.windows(2) {
+        embedding.add_half_edge_first(pair[0], pair[1]);
+        embedding.add_half_edge_first(pair[1], pair[0]);
+    }
+
+    let mut walked = HashSet::new();
+    let mut faces = Vec::new();
+
+    for node in 0..n {
+        let first = embedding.first[node].unwrap();
+        let mut neighbor = first;
+
+        // New neighbors can show up while walking, so the order is read
+        // again at every step.
+        loop {
+            if let Some(face) = make_biconnected(embedding, node, neighbor, &mut walked) {
+                faces.push(face);
+            }
+
+            neighbor = embedding.clockwise[&(node, neighbor)];
+
+            if neighbor == first {
+                break;
+            }
+        }
+    }
+
+    for face in faces.iter() {
+        triangulate_face(embedding, face[0], face[1]);
+    }
+
+    let (v1, v2) = (faces[0][0], faces[0][1]);
+    let (_, v3) = embedding.next_face_half_edge(v1, v2);
+
+    [v1, v2, v3]
+}
+
+/// Orders the nodes of a triangulation so that every node, added in turn,
+/// sees the nodes already placed as a contour along the outer face.
+/// Returns every node with its neighbors on that contour, from left to
+/// right, starting with the two bottom nodes of the outer triangle.
+fn canonical_ordering(embedding: &Embedding, outer: [usize; 3]) -> Vec<(usize, Vec<usize>)> {
+    let n = embedding.first.len();
+    let [v1, v2, v3] = outer;
+
+    let mut chords = vec![0usize; n];
+    let mut removed = vec![false; n];
+    let mut ready = BTreeSet::from(outer);
+
+    // Neighbors along the outer face, without the bottom edge from v1 to v2.
+    let mut counterclockwise = vec![NONE; n];
+    let mut clockwise = vec![NONE; n];
+
+    counterclockwise[v2] = v3;
+    counterclockwise[v3] = v1;
+    clockwise[v1] = v3;
+    clockwise[v3] = v2;
+
+    let is_outer_neighbor = |counterclockwise: &[usize], clockwise: &[usize], a, b| {
+        counterclockwise[a] == b || clockwise[a] == b
+    };
+
+    let is_on_outer_face = |removed: &[bool], counterclockwise: &[usize], node: usize| {
+        !removed[node] && (counterclockwise[node] != NONE || node == v1)
+    };
+
+    for node in outer {
+        for neighbor in embedding.neighbors(node) {
+            if is_on_outer_face(&removed, &counterclockwise, neighbor)
+                && !is_outer_neighbor(&counterclockwise, &clockwise, node, neighbor)
+            {
+                chords[node] += 1;
+                ready.remove(&node);
+            }
+        }
+    }
+
+    ready.remove(&v1);
+    ready.remove(&v2);
+
+    let mut order = vec![(NONE, Vec::new()); n];
+
+    order[0] = (v1, Vec::new());
+    order[1] = (v2, Vec::new());
+
+    for k in (2..n).rev() {
+        let node = ready.pop_last().unwrap();
+
+        removed[node] = true;
+
+        let (mut wp, mut wq) = (NONE, NONE);
+
+        for neighbor in embedding.neighbors(node) {
+            if removed[neighbor] || !is_on_outer_face(&removed, &counterclockwise, neighbor) {
+                continue;
+            }
+
+            if neighbor == v1 {
+                wp = v1;
+            } else if neighbor == v2 {
+                wq = v2;
+            } else if clockwise[neighbor] == node {
+                wp = neighbor;
+            } else {
+                wq = neighbor;
+            }
+        }
+
+        // The neighbors from wp to wq become the new outer face.
+        let mut contour = vec![wp];
+        let mut neighbor = wp;
+
+        while neighbor != wq {
+            let next = embedding.counterclockwise[&(node, neighbor)];
+
+            contour.push(next);
+            clockwise[neighbor] = next;
+            counterclockwise[next] = neighbor;
+            neighbor = next;
+        }
+
+        if contour.len() == 2 {
+            for end in [wp, wq] {
+                chords[end] -= 1;
+
+                if chords[end] == 0 {
+                    ready.insert(end);
+                }
+            }
+        } else {
+            let inner = &contour[1..contour.len() - 1];
+
+            for &node in inner.iter() {
+                ready.insert(node);
+
+                for neighbor in embedding.neighbors(node) {
+                    if is_on_outer_face(&removed, &counterclockwise, neighbor)
+                        && !is_outer_neighbor(&counterclockwise, &clockwise, node, neighbor)
+                    {
+                        chords[node] += 1;
+                        ready.remove(&node);
+
+                        if !inner.contains(&neighbor) {
+                            chords[neighbor] += 1;
+                            ready.remove(&neighbor);
+                        }
+                    }
+                }
+            }
+        }
+
+        order[k] = (node, contour);
+    }
+
+    order
+}
+
+/// Places the nodes of a planar graph so that no two straight edges cross,
+/// inside the unit square, or returns `None` when the graph is not planar.
+///
+/// The embedding is completed into a triangulation, and the nodes are then
+/// added in canonical order with the shift method of de Fraysseix, Pach and
+/// Pollack (as improved by Chrobak and Payne): every node goes above the
+/// contour of the nodes placed so far, and the contour is stretched to make
+/// room for it. The nodes end up on a grid of `2n - 4` by `n - 2` cells,
+/// so that no two of them are closer than a cell.
+pub fn straight_line_drawing(graph: &Graph) -> Option<Vec<(f32, f32)>> {
+    let n = graph.nodes.len();
+    let mut left_right = LeftRight::new(graph);
+
+    if !left_right.run() {
+        return None;
+    }
+
+    match n {
+        0 => return Some(Vec::new()),
+        1 => return Some(vec![(0.5, 0.5)]),
+        2 => return Some(vec![(0.0, 0.5), (1.0, 0.5)]),
+        3 => return Some(vec![(0.0, 0.0), (1.0, 0.0), (0.5, 1.0)]),
+        _ => {}
+    }
+
+    let roots = left_right.roots.clone();
+    let mut embedding = left_right.embed();
+    let outer = triangulate(&mut embedding, &roots);
+    let order = canonical_ordering(&embedding, outer);
+
+    // Every node is placed relative to its parent in a binary tree, so
+    // that shifting a node also shifts everything that hangs from it.
+    let mut left_children = vec![NONE; n];
+    let mut right_children = vec![NONE; n];
+    let mut offsets = vec![0i64; n];
+    let mut heights = vec![0i64; n];
+
+    let (v1, v2, v3) = (order[0].0, order[1].0, order[2].0);
+
+    right_children[v1] = v3;
+    offsets[v2] = 1;
+    offsets[v3] = 1;
+    heights[v3] = 1;
+    right_children[v3] = v2;
+
+    for (node, contour) in order.iter().skip(3) {
+        let node = *node;
+        let (wp, wp1) = (contour[0], contour[1]);
+        let (wq, wq1) = (contour[contour.len() - 1], contour[contour.len() - 2]);
+        let covers = contour.len() > 2;
+
+        // Stretch the contour on both sides of the new node.
+        offsets[wp1] += 1;
+        offsets[wq] += 1;
+
+        let width = contour[1..].iter().map(|&node| offsets[node]).sum::<i64>();
+
+        offsets[node] = (width - heights[wp] + heights[wq]).div_euclid(2);
+        heights[node] = (width + heights[wp] + heights[wq]).div_euclid(2);
+        offsets[wq] = width - offsets[node];
+
+        if covers {
+            offsets[wp1] -= offsets[node];
+        }
+
+        right_children[wp] = node;
+        right_children[node] = wq;
+
+        if covers {
+            left_children[node] = wp1;
+            right_children[wq1] = NONE;
+        } else {
+            left_children[node] = NONE;
+        }
+    }
+
+    let mut positions = vec![(0i64, 0i64); n];
+    let mut stack = vec![v1];
+
+    while let Some(parent) = stack.pop() {
+        for child in [left_children[parent], right_children[parent]] {
+            if child != NONE {
+                positions[child] = (positions[parent].0 + offsets[child], heights[child]);
+                stack.push(child);
+            }
+        }
+    }
+
+    let width = positions.iter().map(|&(x, _)| x).max().unwrap_or(0).max(1) as f32;
+    let height = positions.iter().map(|&(_, y)| y).max().unwrap_or(0).max(1) as f32;
+
+    Some(
+        positions
+            .into_iter()
+            .map(|(x, y)| (x as f32 / width, y as f32 / height))
+            .collect(),
+    )
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KuratowskiKind {
+    K5,
+    K33,
+}
+
+impl KuratowskiKind {
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::K5 => "K5",
+            Self::K33 => "K3,3",
+        }
+    }
+}
+
+/// A subdivision of K5 or K3,3 found in a graph that is not planar.
+#[derive(Clone, Debug)]
+pub struct KuratowskiSubgraph {
+    pub kind: KuratowskiKind,
+    /// The nodes of K5 or K3,3, the other nodes of the subgraph only
+    /// subdivide its edges.
+    pub branch_nodes: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+/// Finds a Kuratowski subgraph, or returns `None` when the graph is planar.
+///
+/// Edges are dropped as long as the rest is still not planar. What is left
+/// is a minimal graph that is not planar, which by Kuratowski's theorem is
+/// a subdivision of K5 or K3,3. `progress` counts the planarity tests run
+/// so far and setting `cancel` stops the search, which then returns `None`.
+pub fn kuratowski_subgraph(
+    graph: &Graph,
+    progress: &AtomicU64,
+    cancel: &AtomicBool,
+) -> Option<KuratowskiSubgraph> {
+    if is_planar(graph) {
+        return None;
+    }
+
+    let mut pairs = HashMap::new();
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if edge.source != edge.target {
+            pairs
+                .entry((edge.source.min(edge.target), edge.source.max(edge.target)))
+                .or_insert(index);
+        }
+    }
+
+    let mut edges = pairs.into_values().collect::<Vec<_>>();
+
+    edges.sort_unstable();
+
+    let mut subgraph = Graph {
+        nodes: graph.nodes.clone(),
+        edges: Vec::new(),
+    };
+
+    // Edges are dropped in chunks that grow while dropping them keeps the
+    // graph non-planar, and shrink down to single edges otherwise.
+    let mut index = 0;
+    let mut chunk = 1;
+
+    while index < edges.len() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+
+        progress.fetch_add(1, Ordering::Relaxed);
+
+        let end = (index + chunk).min(edges.len());
+
+        subgraph.edges = edges[..index]
+            .iter()
+            .chain(edges[end..].iter())
+            .map(|&edge| graph.edges[edge].clone())
+            .collect();
+
+        if !is_planar(&subgraph) {
+            edges.drain(index..end);
+            chunk *= 2;
+        } else if chunk > 1 {
+            chunk /= 2;
+        } else {
+            index += 1;
+        }
+    }
+
+    let mut degrees = vec![0; graph.nodes.len()];
+
+    for &edge in edges.iter() {
+        degrees[graph.edges[edge].source] += 1;
+        degrees[graph.edges[edge].target] += 1;
+    }
+
+    let branch_nodes = (0..graph.nodes.len())
+        .filter(|&node| degrees[node] > 2)
+        .collect::<Vec<_>>();
+
+    let kind = if branch_nodes.len() == 5 {
+        KuratowskiKind::K5
+    } else {
+        KuratowskiKind::K33
+    };
+
+    Some(KuratowskiSubgraph {
+        kind,
+        branch_nodes,
+        edges,
+    })
+}
+
+/// Pairs of edges whose straight segments cross in the current drawing.
+/// Edges sharing a node and loops never count.
+pub fn crossings(graph: &Graph) -> Vec<(usize, usize)> {
+    let position = |node: usize| {
+        let node = &graph.nodes[node];
+
+        (node.x as f64, node.y as f64)
+    };
+
+    let orientation = |a: (f64, f64), b: (f64, f64), c: (f64, f64)| {
+        let cross = (b.0 - a.0) * (c.1 - a.1) - (b.1 - a.1) * (c.0 - a.0);
+
+        if cross > 0.0 {
+            1
+        } else if cross < 0.0 {
+            -1
+        } else {
+            0
+        }
+    };
+
+    let mut crossings = Vec::new();
+
+    for (first, a) in graph.edges.iter().enumerate() {
+        if a.source == a.target {
+            continue;
+        }
+
+        for (second, b) in graph.edges.iter().enumerate().skip(first + 1) {
+            if b.source == b.target
+                || [b.source, b.target].contains(&a.source)
+                || [b.source, b.target].contains(&a.target)
+            {
+                continue;
+            }
+
+            let (p1, p2) = (position(a.source), position(a.target));
+            let (q1, q2) = (position(b.source), position(b.target));
+
+            if orientation(p1, p2, q1) * orientation(p1, p2, q2) < 0
+                && orientation(q1, q2, p1) * orientation(q1, q2, p2) < 0
+            {
+                crossings.push((first, second));
+            }
+        }
+    }
+
+    crossings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphEdge;
+
+    fn graph(n: usize, edges: &[(usize, usize)]) -> Graph {
+        let mut graph = Graph::default();
+
+        for index in 0..n {
+            graph.add_node(index.to_string(), 0.0, 0.0);
+        }
+
+        for &(source, target) in edges {
+            graph.add_edge(GraphEdge::new(source, target, false));
+        }
+
+        graph
+    }
+
+    fn complete(n: usize) -> Graph {
+        let edges = (0..n)
+            .flat_map(|source| (source + 1..n).map(move |target| (source, target)))
+            .collect::<Vec<_>>();
+
+        graph(n, &edges)
+    }
+
+    fn complete_bipartite(a: usize, b: usize) -> Graph {
+        let edges = (0..a)
+            .flat_map(|source| (a..a + b).map(move |target| (source, target)))
+            .collect::<Vec<_>>();
+
+        graph(a + b, &edges)
+    }
+
+    /// Moves the nodes to the straight-line drawing and checks that no
+    /// edges cross.
+    fn assert_drawn_without_crossings(mut graph: Graph) {
+        assert!(is_planar(&graph));
+
+        let positions = straight_line_drawing(&graph).expect("no drawing of a planar graph");
+
+        for (node, (x, y)) in graph.nodes.iter_mut().zip(positions) {
+            node.x = x;
+            node.y = y;
+        }
+
+        assert_eq!(crossings(&graph), []);
+    }
+
+    #[test]
+    fn k5_is_not_planar() {
+        let graph = complete(5);
+        let subgraph = kuratowski_subgraph(&graph, &AtomicU64::new(0), &AtomicBool::new(false))
+            .expect("K5 is planar");
+
+        assert!(!is_planar(&graph));
+        assert_eq!(subgraph.kind, KuratowskiKind::K5);
+        assert_eq!(subgraph.branch_nodes, [0, 1, 2, 3, 4]);
+        assert_eq!(subgraph.edges.len(), 10);
+    }
+
+    #[test]
+    fn k33_is_not_planar() {
+        let graph = complete_bipartite(3, 3);
+        let subgraph = kuratowski_subgraph(&graph, &AtomicU64::new(0), &AtomicBool::new(false))
+            .expect("K3,3 is planar");
+
+        assert!(!is_planar(&graph));
+        assert_eq!(subgraph.kind, KuratowskiKind::K33);
+        assert_eq!(subgraph.branch_nodes.len(), 6);
+        assert_eq!(subgraph.edges.len(), 9);
+    }
+
+    #[test]
+    fn subdivided_k5_keeps_its_branch_nodes() {
+        let mut graph = complete(5);
+
+        // Replaces the edge 0 - 1 by 0 - 5 - 1.
+        graph.edges.remove(0);
+        graph.add_node("5".to_string(), 0.0, 0.0);
+        graph.add_edge(GraphEdge::new(0, 5, false));
+        graph.add_edge(GraphEdge::new(5, 1, false));
+
+        let subgraph = kuratowski_subgraph(&graph, &AtomicU64::new(0), &AtomicBool::new(false))
+            .expect("a subdivided K5 is planar");
+
+        assert_eq!(subgraph.kind, KuratowskiKind::K5);
+        assert_eq!(subgraph.branch_nodes, [0, 1, 2, 3, 4]);
+        assert_eq!(subgraph.edges.len(), 11);
+    }
+
+    #[test]
+    fn cancelled_search_finds_nothing() {
+        let progress = AtomicU64::new(0);
+        let subgraph = kuratowski_subgraph(&complete(5), &progress, &AtomicBool::new(true));
+
+        assert!(subgraph.is_none());
+        assert_eq!(progress.load(Ordering::Relaxed), 0);
+    }
+
+    #[test]
+    fn planar_graphs_are_drawn_without_crossings() {
+        assert_drawn_without_crossings(complete(4));
+
+        // The octahedron: every pair of nodes but the opposite ones.
+        let octahedron = (0..6)
+            .flat_map(|source| (source + 1..6).map(move |target| (source, target)))
+            .filter(|&(source, target)| target != source + 3)
+            .collect::<Vec<_>>();
+
+        assert_drawn_without_crossings(graph(6, &octahedron));
+
+        // A 3 by 3 grid with one diagonal in every square.
+        let mut grid = Vec::new();
+
+        for row in 0..3 {
+            for column in 0..3 {
+                let node = row * 3 + column;
+
+                if column < 2 {
+                    grid.push((node, node + 1));
+                }
+
+                if row < 2 {
+                    grid.push((node, node + 3));
+                }
+
+                if row < 2 && column < 2 {
+                    grid.push((node, node + 4));
+                }
+            }
+        }
+
+        assert_drawn_without_crossings(graph(9, &grid));
+        assert_drawn_without_crossings(complete_bipartite(2, 4));
+    }
+
+    #[test]
+    fn crossing_diagonals_are_found() {
+        let mut square = graph(4, &[(0, 2), (1, 3)]);
+
+        for (node, (x, y)) in
+            square
+                .nodes
+                .iter_mut()
+                .zip([(0.0, 0.0), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0)])
+        {
+            node.x = x;
+            node.y = y;
+        }
+
+        assert_eq!(crossings(&square), [(0, 1)]);
+    }
+}
@@ -0,0 +1,389 @@
+use std::{cmp::Ordering, collections::BinaryHeap};
+
+use crate::graph::Graph;
+
+const NONE: usize = usize::MAX;
+
+#[derive(Clone, Debug)]
+pub struct NegativeCycle {
+    pub nodes: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct AllPairs {
+    /// `distances[source][target]`, infinite when the target can not be
+    /// reached.
+    pub distances: Vec<Vec<f32>>,
+    /// The node before the target and the edge between them on a shortest
+    /// path from the source.
+    predecessors: Vec<Vec<(usize, usize)>>,
+}
+
+impl AllPairs {
+    /// Nodes and edges of a shortest path from `source` to `target`.
+    pub fn path(&self, source: usize, target: usize) -> Option<(Vec<usize>, Vec<usize>)> {
+        if !self.distances[source][target].is_finite() {
+            return None;
+        }
+
+        let mut nodes = vec![target];
+        let mut edges = Vec::new();
+        let mut node = target;
+
+        while node != source {
+            let (previous, edge) = self.predecessors[source][node];
+
+            nodes.push(previous);
+            edges.push(edge);
+            node = previous;
+        }
+
+        nodes.reverse();
+        edges.reverse();
+
+        Some((nodes, edges))
+    }
+
+    /// Distance from every node to the node farthest from it.
+    pub fn eccentricities(&self) -> Vec<f32> {
+        self.distances
+            .iter()
+            .map(|row| row.iter().copied().fold(0.0, f32::max))
+            .collect()
+    }
+}
+
+/// Edges as `(from, to, weight, edge)`, with undirected edges going both
+/// ways.
+fn arcs(graph: &Graph) -> Vec<(usize, usize, f32, usize)> {
+    let mut arcs = Vec::new();
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let weight = graph.weight(index);
+
+        arcs.push((edge.source, edge.target, weight, index));
+
+        if !edge.directed && edge.source != edge.target {
+            arcs.push((edge.target, edge.source, weight, index));
+        }
+    }
+
+    arcs
+}
+
+pub fn has_negative_weights(graph: &Graph) -> bool {
+    (0..graph.edges.len()).any(|edge| graph.weight(edge) < 0.0)
+}
+
+pub fn floyd_warshall(graph: &Graph) -> Result<AllPairs, NegativeCycle> {
+    let n = graph.nodes.len();
+    let mut distances = vec![vec![f32::INFINITY; n]; n];
+    let mut predecessors = vec![vec![(NONE, NONE); n]; n];
+
+    for (node, row) in distances.iter_mut().enumerate() {
+        row[node] = 0.0;
+    }
+
+    for (from, to, weight, edge) in arcs(graph) {
+        if weight < distances[from][to] {
+            distances[from][to] = weight;
+            predecessors[from][to] = (from, edge);
+        }
+    }
+
+    for middle in 0..n {
+        for source in 0..n {
+            let to_middle = distances[source][middle];
+
+            if !to_middle.is_finite() {
+                continue;
+            }
+
+            for target in 0..n {
+                let through = to_middle + distances[middle][target];
+
+                if through < distances[source][target] {
+                    distances[source][target] = through;
+                    predecessors[source][target] = predecessors[middle][target];
+                }
+            }
+        }
+    }
+
+    // A node with a negative distance to itself is on a negative cycle,
+    // which its own predecessors lead back to.
+    if let Some(cycle) = (0..n)
+        .filter(|&node| distances[node][node] < 0.0)
+        .find_map(|node| trace_cycle(&predecessors[node], node))
+    {
+        return Err(cycle);
+    }
+
+    Ok(AllPairs {
+        distances,
+        predecessors,
+    })
+}
+
+#[derive(PartialEq)]
+struct QueuedNode {
+    distance: f32,
+    node: usize,
+}
+
+impl Eq for QueuedNode {}
+
+impl Ord for QueuedNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .distance
+            .total_cmp(&self.distance)
+            .then_with(|| other.node.cmp(&self.node))
+    }
+}
+
+impl PartialOrd for QueuedNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+type SingleSource = (Vec<f32>, Vec<(usize, usize)>);
+
+fn dijkstra(outgoing: &[Vec<(usize, f32, usize)>], source: usize) -> SingleSource {
+    let n = outgoing.len();
+    let mut distances = vec![f32::INFINITY; n];
+    let mut predecessors = vec![(NONE, NONE); n];
+    let mut queue = BinaryHeap::from([QueuedNode {
+        distance: 0.0,
+        node: source,
+    }]);
+
+    distances[source] = 0.0;
+
+    while let Some(QueuedNode { distance, node }) = queue.pop() {
+        if distance > distances[node] {
+            continue;
+        }
+
+        for &(neighbor, weight, edge) in outgoing[node].iter() {
+            let through = distance + weight;
+
+            if through < distances[neighbor] {
+                distances[neighbor] = through;
+                predecessors[neighbor] = (node, edge);
+                queue.push(QueuedNode {
+                    distance: through,
+                    node: neighbor,
+                });
+            }
+        }
+    }
+
+    (distances, predecessors)
+}
+
+fn bellman_ford(
+    n: usize,
+    arcs: &[(usize, usize, f32, usize)],
+    source: usize,
+) -> Result<SingleSource, NegativeCycle> {
+    let mut distances = vec![f32::INFINITY; n];
+    let mut predecessors = vec![(NONE, NONE); n];
+
+    distances[source] = 0.0;
+
+    for _ in 1..n.max(1) {
+        let mut changed = false;
+
+        for &(from, to, weight, edge) in arcs {
+            if distances[from] + weight < distances[to] {
+                distances[to] = distances[from] + weight;
+                predecessors[to] = (from, edge);
+                changed = true;
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    let Some(&(from, to, _, edge)) = arcs
+        .iter()
+        .find(|&&(from, to, weight, _)| distances[from] + weight < distances[to])
+    else {
+        return Ok((distances, predecessors));
+    };
+
+    predecessors[to] = (from, edge);
+
+    // Rounding can make a cycle of weight zero look negative, in which case
+    // the predecessors do not close it.
+    match trace_cycle(&predecessors, to) {
+        Some(cycle) => Err(cycle),
+        None => Ok((distances, predecessors)),
+    }
+}
+
+/// Follows the predecessors back from `node` until they close a cycle.
+/// Returns `None` if they stop before.
+fn trace_cycle(predecessors: &[(usize, usize)], node: usize) -> Option<NegativeCycle> {
+    let n = predecessors.len();
+
+    // Going back n times from a node that can still be improved always
+    // ends on the negative cycle.
+    let mut start = node;
+
+    for _ in 0..n {
+        start = predecessors[start].0;
+
+        if start == NONE {
+            return None;
+        }
+    }
+
+    let mut cycle = NegativeCycle {
+        nodes: vec![start],
+        edges: Vec::new(),
+    };
+    let mut node = start;
+
+    loop {
+        let (previous, edge) = predecessors[node];
+
+        if previous == NONE || cycle.edges.len() == n {
+            return None;
+        }
+
+        cycle.edges.push(edge);
+        node = previous;
+
+        if node == start {
+            break;
+        }
+
+        cycle.nodes.push(node);
+    }
+
+    // The cycle was walked backwards, from every node to the one before it.
+    cycle.nodes[1..].reverse();
+    cycle.edges.reverse();
+
+    Some(cycle)
+}
+
+/// Dijkstra from every node, which needs all weights to be non-negative.
+pub fn repeated_dijkstra(graph: &Graph) -> AllPairs {
+    let mut outgoing = vec![Vec::new(); graph.nodes.len()];
+
+    for (from, to, weight, edge) in arcs(graph) {
+        outgoing[from].push((to, weight, edge));
+    }
+
+    let (distances, predecessors) = (0..graph.nodes.len())
+        .map(|source| dijkstra(&outgoing, source))
+        .unzip();
+
+    AllPairs {
+        distances,
+        predecessors,
+    }
+}
+
+/// Bellman-Ford from every node, stopping at the first negative cycle.
+pub fn repeated_bellman_ford(graph: &Graph) -> Result<AllPairs, NegativeCycle> {
+    let n = graph.nodes.len();
+    let arcs = arcs(graph);
+    let mut all_pairs = AllPairs {
+        distances: Vec::with_capacity(n),
+        predecessors: Vec::with_capacity(n),
+    };
+
+    for source in 0..n {
+        let (distances, predecessors) = bellman_ford(n, &arcs, source)?;
+
+        all_pairs.distances.push(distances);
+        all_pairs.predecessors.push(predecessors);
+    }
+
+    Ok(all_pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::GraphEdge;
+
+    fn graph(n: usize, edges: &[(usize, usize, f32)]) -> Graph {
+        let mut graph = Graph::default();
+
+        for index in 0..n {
+            graph.add_node(index.to_string(), 0.0, 0.0);
+        }
+
+        for &(source, target, weight) in edges {
+            let mut edge = GraphEdge::new(source, target, true);
+
+            edge.weight = Some(weight);
+            graph.add_edge(edge);
+        }
+
+        graph
+    }
+
+    /// The edges of the cycle, starting from the lowest one.
+    fn cycle_edges(cycle: &NegativeCycle) -> Vec<usize> {
+        let mut edges = cycle.edges.clone();
+        let lowest = (0..edges.len()).min_by_key(|&index| edges[index]).unwrap();
+
+        edges.rotate_left(lowest);
+        edges
+    }
+
+    #[test]
+    fn negative_cycle_is_found_by_both() {
+        let graph = graph(4, &[(0, 1, 1.0), (1, 2, -2.0), (2, 0, 0.5), (2, 3, 1.0)]);
+
+        let cycle = floyd_warshall(&graph).unwrap_err();
+
+        assert_eq!(cycle_edges(&cycle), [0, 1, 2]);
+        assert_eq!(cycle.nodes.len(), 3);
+
+        let cycle = repeated_bellman_ford(&graph).unwrap_err();
+
+        assert_eq!(cycle_edges(&cycle), [0, 1, 2]);
+    }
+
+    #[test]
+    fn cycle_of_weight_about_zero_does_not_panic() {
+        // In f32 the four weights do not add up to exactly zero, and the
+        // two algorithms add them in a different order: Floyd-Warshall
+        // sees a negative cycle where Bellman-Ford does not.
+        let graph = graph(4, &[(0, 1, 0.1), (1, 2, 0.1), (2, 3, 0.1), (3, 0, -0.3)]);
+
+        if let Err(cycle) = floyd_warshall(&graph) {
+            assert_eq!(cycle_edges(&cycle), [0, 1, 2, 3]);
+        }
+
+        if let Err(cycle) = repeated_bellman_ford(&graph) {
+            assert_eq!(cycle_edges(&cycle), [0, 1, 2, 3]);
+        }
+    }
+
+    #[test]
+    fn distances_and_paths() {
+        let graph = graph(3, &[(0, 1, 1.0), (1, 2, 2.0), (0, 2, 4.0)]);
+
+        for all_pairs in [
+            floyd_warshall(&graph).unwrap(),
+            repeated_dijkstra(&graph),
+            repeated_bellman_ford(&graph).unwrap(),
+        ] {
+            assert_eq!(all_pairs.distances[0], [0.0, 1.0, 3.0]);
+            assert_eq!(all_pairs.path(0, 2), Some((vec![0, 1, 2], vec![0, 1])));
+            assert_eq!(all_pairs.path(2, 0), None);
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use std::collections::VecDeque;
+
+use crate::graph::Graph;
+
+#[derive(Clone, Debug)]
+pub struct KahnStep {
+    pub node: usize,
+    pub in_degrees: Vec<usize>,
+    pub queue: Vec<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub nodes: Vec<usize>,
+    pub edges: Vec<usize>,
+}
+
+#[derive(Clone, Debug)]
+pub struct KahnRun {
+    pub in_degrees: Vec<usize>,
+    pub queue: Vec<usize>,
+    pub steps: Vec<KahnStep>,
+    pub order: Vec<usize>,
+    pub cycle: Option<Cycle>,
+}
+
+/// Orders the nodes with Kahn's algorithm, following the directed edges
+/// only. When some nodes are left out because of a cycle, one of the
+/// cycles among them is returned as well.
+pub fn kahn(graph: &Graph) -> KahnRun {
+    let n = graph.nodes.len();
+
+    let mut in_degrees = vec![0; n];
+    let mut outgoing = vec![Vec::new(); n];
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if edge.directed {
+            in_degrees[edge.target] += 1;
+            outgoing[edge.source].push((edge.target, index));
+        }
+    }
+
+    let mut queue = (0..n)
+        .filter(|&node| in_degrees[node] == 0)
+        .collect::<VecDeque<_>>();
+
+    let mut run = KahnRun {
+        in_degrees: in_degrees.clone(),
+        queue: queue.iter().copied().collect(),
+        steps: Vec::new(),
+        order: Vec::new(),
+        cycle: None,
+    };
+
+    while let Some(node) = queue.pop_front() {
+        for &(target, _) in outgoing[node].iter() {
+            in_degrees[target] -= 1;
+
+            if in_degrees[target] == 0 {
+                queue.push_back(target);
+            }
+        }
+
+        run.order.push(node);
+        run.steps.push(KahnStep {
+            node,
+            in_degrees: in_degrees.clone(),
+            queue: queue.iter().copied().collect(),
+        });
+    }
+
+    if run.order.len() < n {
+        run.cycle = Some(find_cycle(graph, &in_degrees));
+    }
+
+    run
+}
+
+/// Every node left with a positive in-degree has an incoming edge from
+/// another such node, so walking those edges backwards has to come back to
+/// a node it already went through.
+fn find_cycle(graph: &Graph, in_degrees: &[usize]) -> Cycle {
+    let n = graph.nodes.len();
+    let mut incoming = vec![None; n];
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if edge.directed && in_degrees[edge.source] > 0 && in_degrees[edge.target] > 0 {
+            incoming[edge.target].get_or_insert((edge.source, index));
+        }
+    }
+
+    let start = (0..n)
+        .find(|&node| in_degrees[node] > 0)
+        .expect("Find cycle: every node was ordered");
+
+    let mut position = vec![None; n];
+    let mut walk = Vec::new();
+    let mut node = start;
+
+    while position[node].is_none() {
+        position[node] = Some(walk.len());
+
+        let (source, edge) = incoming[node].expect("Find cycle: node without incoming edge");
+
+        walk.push((node, edge));
+        node = source;
+    }
+
+    let mut cycle = walk.split_off(position[node].unwrap());
+
+    cycle.reverse();
+
+    Cycle {
+        nodes: cycle.iter().map(|&(node, _)| node).collect(),
+        edges: cycle.iter().map(|&(_, edge)| edge).collect(),
+    }
+}
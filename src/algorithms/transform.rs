@@ -0,0 +1,413 @@
+use std::{collections::HashSet, f32::consts::TAU};
+
+use crate::graph::{Graph, GraphEdge, GraphNode};
+
+/// Distance between the copies of the template in a product.
+const PRODUCT_SPREAD: f32 = 40.0;
+
+/// Gap between the two graphs of a disjoint union.
+const UNION_GAP: f32 = 100.0;
+
+/// Small graphs to take products with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Template {
+    Path,
+    Cycle,
+    Complete,
+    Star,
+}
+
+impl Template {
+    pub const ALL: [Template; 4] = [
+        Template::Path,
+        Template::Cycle,
+        Template::Complete,
+        Template::Star,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Path => "Path",
+            Self::Cycle => "Cycle",
+            Self::Complete => "Complete graph",
+            Self::Star => "Star",
+        }
+    }
+
+    /// The template on `size` undirected nodes, placed on the unit circle
+    /// (the center of a star is in the middle).
+    pub fn build(self, size: usize) -> Graph {
+        let mut graph = Graph::default();
+
+        for node in 0..size {
+            let angle = TAU * node as f32 / size as f32;
+
+            graph.add_node((node + 1).to_string(), angle.cos(), angle.sin());
+        }
+
+        let edges = match self {
+            Self::Path => (1..size).map(|node| (node - 1, node)).collect::<Vec<_>>(),
+            Self::Cycle if size > 2 => (0..size).map(|node| (node, (node + 1) % size)).collect(),
+            Self::Cycle => (1..size).map(|node| (node - 1, node)).collect(),
+            Self::Complete => (0..size)
+                .flat_map(|a| (a + 1..size).map(move |b| (a, b)))
+                .collect(),
+            Self::Star => {
+                if let Some(center) = graph.nodes.first_mut() {
+                    center.x = 0.0;
+                    center.y = 0.0;
+                }
+
+                (1..size).map(|node| (0, node)).collect()
+            }
+        };
+
+        for (source, target) in edges {
+            graph.add_edge(GraphEdge::new(source, target, false));
+        }
+
+        graph
+    }
+}
+
+/// The label after the highest numeric label, as new nodes get on the
+/// canvas.
+fn next_label(graph: &Graph) -> String {
+    graph
+        .nodes
+        .iter()
+        .filter_map(|node| node.label.parse::<usize>().ok())
+        .max()
+        .map_or(graph.nodes.len(), |highest| highest + 1)
+        .to_string()
+}
+
+/// Same nodes, with an edge wherever the graph has none. When the graph
+/// has directed edges, every missing ordered pair gets a directed edge, and
+/// an undirected edge counts for both directions. Loops are left out.
+pub fn complement(graph: &Graph) -> Graph {
+    let n = graph.nodes.len();
+    let directed = graph.is_directed();
+    let mut present = HashSet::new();
+
+    for edge in graph.edges.iter() {
+        present.insert((edge.source, edge.target));
+
+        if !edge.directed || !directed {
+            present.insert((edge.target, edge.source));
+        }
+    }
+
+    let mut result = Graph {
+        nodes: graph.nodes.clone(),
+        edges: Vec::new(),
+    };
+
+    for source in 0..n {
+        for target in 0..n {
+            if source == target || (!directed && source > target) {
+                continue;
+            }
+
+            if !present.contains(&(source, target)) {
+                result.add_edge(GraphEdge::new(source, target, directed));
+            }
+        }
+    }
+
+    result
+}
+
+/// Reverses every directed edge.
+pub fn transpose(graph: &Graph) -> Graph {
+    let mut result = graph.clone();
+
+    for edge in result.edges.iter_mut().filter(|edge| edge.directed) {
+        std::mem::swap(&mut edge.source, &mut edge.target);
+        edge.route = edge.route.reversed();
+    }
+
+    result
+}
+
+/// One node for every edge, halfway along it, joined to the nodes of the
+/// edges it shares an end with. When every edge is directed, the node of
+/// an edge into `a` only leads to the nodes of the edges out of `a`.
+pub fn line_graph(graph: &Graph) -> Graph {
+    let directed = !graph.edges.is_empty() && graph.edges.iter().all(|edge| edge.directed);
+    let mut result = Graph::default();
+
+    for edge in graph.edges.iter() {
+        let (source, target) = (&graph.nodes[edge.source], &graph.nodes[edge.target]);
+        let label = edge
+            .label
+            .clone()
+            .unwrap_or_else(|| format!("{}-{}", source.label, target.label));
+
+        result.add_node(
+            label,
+            (source.x + target.x) / 2.0,
+            (source.y + target.y) / 2.0,
+        );
+    }
+
+    let mut incident = vec![Vec::new(); graph.nodes.len()];
+    let mut pairs = HashSet::new();
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        incident[edge.source].push(index);
+
+        if edge.source != edge.target {
+            incident[edge.target].push(index);
+        }
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let ends = if directed {
+            vec![edge.target]
+        } else {
+            vec![edge.source, edge.target]
+        };
+
+        for end in ends {
+            for &other in incident[end].iter() {
+                if other == index || (directed && graph.edges[other].source != end) {
+                    continue;
+                }
+
+                let pair = if directed {
+                    (index, other)
+                } else {
+                    (index.min(other), index.max(other))
+                };
+
+                if pairs.insert(pair) {
+                    result.add_edge(GraphEdge::new(pair.0, pair.1, directed));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+/// The given nodes with every edge between them.
+pub fn induced_subgraph(graph: &Graph, nodes: &[usize]) -> Graph {
+    let mut indices = vec![None; graph.nodes.len()];
+    let mut result = Graph::default();
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        if nodes.contains(&index) {
+            indices[index] = Some(result.nodes.len());
+            result.nodes.push(node.clone());
+        }
+    }
+
+    for edge in graph.edges.iter() {
+        if let (Some(source), Some(target)) = (indices[edge.source], indices[edge.target]) {
+            result.add_edge(GraphEdge {
+                source,
+                target,
+                ..edge.clone()
+            });
+        }
+    }
+
+    result
+}
+
+/// Merges the ends of an edge into a single node halfway between them. The
+/// edges that would join the merged node to itself disappear, and of the
+/// edges that become parallel only the first is kept.
+pub fn contract_edge(graph: &Graph, edge: usize) -> Graph {
+    let (kept, merged) = (graph.edges[edge].source, graph.edges[edge].target);
+    let mut result = Graph::default();
+    let mut indices = vec![0; graph.nodes.len()];
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        if index == merged && merged != kept {
+            continue;
+        }
+
+        indices[index] = result.nodes.len();
+        result.nodes.push(node.clone());
+    }
+
+    indices[merged] = indices[kept];
+
+    if merged != kept {
+        let (a, b) = (&graph.nodes[kept], &graph.nodes[merged]);
+        let node = &mut result.nodes[indices[kept]];
+
+        node.label = format!("{}+{}", a.label, b.label);
+        node.x = (a.x + b.x) / 2.0;
+        node.y = (a.y + b.y) / 2.0;
+    }
+
+    let mut seen = HashSet::new();
+
+    for (index, other) in graph.edges.iter().enumerate() {
+        let (source, target) = (indices[other.source], indices[other.target]);
+
+        if index == edge || (source == target && other.source != other.target) {
+            continue;
+        }
+
+        let key = if other.directed {
+            (source, target, true)
+        } else {
+            (source.min(target), source.max(target), false)
+        };
+
+        if seen.insert(key) {
+            result.add_edge(GraphEdge {
+                source,
+                target,
+                ..other.clone()
+            });
+        }
+    }
+
+    result
+}
+
+/// Splits an edge in two with a new node halfway along it. Both halves
+/// keep the direction of the edge, but not its weight or label.
+pub fn subdivide_edge(graph: &Graph, edge: usize) -> Graph {
+    let mut result = graph.clone();
+    let removed = result.edges.remove(edge);
+    let (source, target) = (&graph.nodes[removed.source], &graph.nodes[removed.target]);
+
+    let middle = result.add_node(
+        next_label(graph),
+        (source.x + target.x) / 2.0,
+        (source.y + target.y) / 2.0,
+    );
+
+    result.add_edge(GraphEdge::new(removed.source, middle, removed.directed));
+    result.add_edge(GraphEdge::new(middle, removed.target, removed.directed));
+
+    result
+}
+
+/// Nodes of a product, one for every pair of nodes, with a small copy of
+/// the second graph around every node of the first.
+fn product_nodes(a: &Graph, b: &Graph) -> Graph {
+    let mut result = Graph::default();
+
+    for u in a.nodes.iter() {
+        for v in b.nodes.iter() {
+            result.add_node(
+                format!("{},{}", u.label, v.label),
+                u.x + v.x * PRODUCT_SPREAD,
+                u.y + v.y * PRODUCT_SPREAD,
+            );
+        }
+    }
+
+    result
+}
+
+/// Cartesian product: `(u, v)` and `(u', v')` are joined when `u = u'` and
+/// `v` is joined to `v'`, or `v = v'` and `u` is joined to `u'`.
+pub fn cartesian_product(a: &Graph, b: &Graph) -> Graph {
+    let m = b.nodes.len();
+    let mut result = product_nodes(a, b);
+
+    for edge in a.edges.iter() {
+        for v in 0..m {
+            result.add_edge(GraphEdge::new(
+                edge.source * m + v,
+                edge.target * m + v,
+                edge.directed,
+            ));
+        }
+    }
+
+    for u in 0..a.nodes.len() {
+        for edge in b.edges.iter() {
+            result.add_edge(GraphEdge::new(
+                u * m + edge.source,
+                u * m + edge.target,
+                edge.directed,
+            ));
+        }
+    }
+
+    result
+}
+
+/// Tensor product: `(u, v)` and `(u', v')` are joined when `u` is joined to
+/// `u'` and `v` to `v'`. The edge is directed when either of them is.
+pub fn tensor_product(a: &Graph, b: &Graph) -> Graph {
+    let m = b.nodes.len();
+    let mut result = product_nodes(a, b);
+
+    for first in a.edges.iter() {
+        for second in b.edges.iter() {
+            let directed = first.directed || second.directed;
+            let (s1, t1, s2, t2) = (first.source, first.target, second.source, second.target);
+
+            result.add_edge(GraphEdge::new(s1 * m + s2, t1 * m + t2, directed));
+
+            if s1 == t1 || s2 == t2 || (first.directed && second.directed) {
+                continue;
+            }
+
+            // An undirected edge can be walked both ways, which pairs the
+            // other edge with its reverse as well.
+            let (source, target) = if second.directed {
+                (t1 * m + s2, s1 * m + t2)
+            } else {
+                (s1 * m + t2, t1 * m + s2)
+            };
+
+            result.add_edge(GraphEdge::new(source, target, directed));
+        }
+    }
+
+    result
+}
+
+/// Both graphs side by side, the second one to the right of the first.
+pub fn disjoint_union(a: &Graph, b: &Graph) -> Graph {
+    let bounds = |graph: &Graph| {
+        graph.nodes.iter().fold(
+            (f32::INFINITY, f32::NEG_INFINITY, 0.0),
+            |(min_x, max_x, sum_y), node| (min_x.min(node.x), max_x.max(node.x), sum_y + node.y),
+        )
+    };
+
+    let (_, a_max_x, a_sum_y) = bounds(a);
+    let (b_min_x, _, b_sum_y) = bounds(b);
+
+    let (dx, dy) = if a.nodes.is_empty() || b.nodes.is_empty() {
+        (0.0, 0.0)
+    } else {
+        (
+            a_max_x + UNION_GAP - b_min_x,
+            a_sum_y / a.nodes.len() as f32 - b_sum_y / b.nodes.len() as f32,
+        )
+    };
+
+    let mut result = a.clone();
+    let offset = a.nodes.len();
+
+    for node in b.nodes.iter() {
+        result.nodes.push(GraphNode {
+            x: node.x + dx,
+            y: node.y + dy,
+            ..node.clone()
+        });
+    }
+
+    for edge in b.edges.iter() {
+        result.add_edge(GraphEdge {
+            source: edge.source + offset,
+            target: edge.target + offset,
+            ..edge.clone()
+        });
+    }
+
+    result
+}
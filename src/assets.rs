@@ -0,0 +1,56 @@
+use std::{f32::consts::SQRT_2, marker::PhantomData};
+
+use bevy::{ecs::system::SystemParam, prelude::*, sprite::Mesh2dHandle, utils::HashMap};
+
+use crate::graph::NodeShape;
+
+#[derive(Resource, Default)]
+pub struct NodeAssetCache {
+    meshes: HashMap<NodeShape, Handle<Mesh>>,
+    materials: HashMap<[u8; 4], Handle<ColorMaterial>>,
+}
+
+#[derive(SystemParam)]
+pub struct NodeAssets<'w, 's> {
+    cache: ResMut<'w, NodeAssetCache>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<ColorMaterial>>,
+    #[system_param(ignore)]
+    marker: PhantomData<&'s ()>,
+}
+
+impl<'w, 's> NodeAssets<'w, 's> {
+    /// A node of the given shape with a radius of 1, with the same corners
+    /// as `NodeShape::corners`. Nodes are scaled to their size by their
+    /// `Transform`, so that every size shares the mesh.
+    pub fn mesh(&mut self, node_shape: NodeShape) -> Mesh2dHandle {
+        let meshes = &mut self.meshes;
+
+        self.cache
+            .meshes
+            .entry(node_shape)
+            .or_insert_with(|| {
+                meshes.add(match node_shape {
+                    NodeShape::Circle => shape::Circle::new(1.0).into(),
+                    NodeShape::Square => shape::Quad::new(Vec2::splat(SQRT_2)).into(),
+                    NodeShape::Diamond => shape::RegularPolygon::new(1.0, 4).into(),
+                    NodeShape::Hexagon => shape::RegularPolygon::new(1.0, 6).into(),
+                })
+            })
+            .clone()
+            .into()
+    }
+
+    pub fn material(&mut self, color: Color) -> Handle<ColorMaterial> {
+        let materials = &mut self.materials;
+        let key = color
+            .as_rgba_f32()
+            .map(|channel| (channel.clamp(0.0, 1.0) * 255.0).round() as u8);
+
+        self.cache
+            .materials
+            .entry(key)
+            .or_insert_with(|| materials.add(ColorMaterial::from(color)))
+            .clone()
+    }
+}
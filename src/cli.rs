@@ -0,0 +1,190 @@
+use std::{
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use bevy::prelude::Color;
+use clap::{Parser, Subcommand};
+
+use crate::{
+    formats::Format,
+    graph::Graph,
+    layout::Layout,
+    render::{self, RenderStyle},
+    resources::{EdgeSettings, GraphCollection, NodeSettings},
+};
+
+#[derive(Parser)]
+#[command(version, about = "Draw, convert and render graphs")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+pub enum Command {
+    /// Open a graph file in the visualizer
+    Open { file: PathBuf },
+    /// Convert a graph file to another format without opening a window
+    Convert {
+        input: PathBuf,
+        output: PathBuf,
+        /// Format of the input file, guessed from its extension by default
+        #[arg(long)]
+        from: Option<Format>,
+        /// Format of the output file, guessed from its extension by default
+        #[arg(long)]
+        to: Option<Format>,
+        /// Only convert the graph at this position (starting at 0) of the input file
+        #[arg(long)]
+        index: Option<usize>,
+    },
+    /// Render a graph file to an SVG or PNG image
+    Render {
+        input: PathBuf,
+        output: PathBuf,
+        /// Format of the input file, guessed from its extension by default
+        #[arg(long)]
+        from: Option<Format>,
+        /// One of circular, force-directed or grid
+        #[arg(long, default_value = "force-directed")]
+        layout: Layout,
+        #[arg(long, default_value_t = 800)]
+        width: u32,
+        #[arg(long, default_value_t = 600)]
+        height: u32,
+        /// Position (starting at 0) of the graph to render in the input file
+        #[arg(long, default_value_t = 0)]
+        index: usize,
+    },
+}
+
+fn format_of(path: &Path, format: Option<Format>) -> Result<Format, Box<dyn Error>> {
+    format
+        .or_else(|| Format::from_path(path))
+        .ok_or_else(|| format!("can not guess the format of {}", path.display()).into())
+}
+
+fn read_graphs(path: &Path, format: Option<Format>) -> Result<Vec<Graph>, Box<dyn Error>> {
+    let format = format_of(path, format)?;
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("can not read {}: {err}", path.display()))?;
+
+    let graphs = format
+        .read(&contents)
+        .map_err(|err| format!("can not parse {}: {err}", path.display()))?;
+
+    if graphs.is_empty() {
+        return Err(format!("{} does not contain any graph", path.display()).into());
+    }
+
+    Ok(graphs)
+}
+
+fn select(mut graphs: Vec<Graph>, index: usize) -> Result<Graph, Box<dyn Error>> {
+    if index >= graphs.len() {
+        return Err(format!(
+            "there is no graph at index {index}, the file has {} graph(s)",
+            graphs.len()
+        )
+        .into());
+    }
+
+    Ok(graphs.swap_remove(index))
+}
+
+pub fn open(file: &Path) -> Result<GraphCollection, Box<dyn Error>> {
+    Ok(GraphCollection {
+        graphs: read_graphs(file, None)?,
+        current: 0,
+    })
+}
+
+pub fn convert(
+    input: &Path,
+    output: &Path,
+    from: Option<Format>,
+    to: Option<Format>,
+    index: Option<usize>,
+) -> Result<(), Box<dyn Error>> {
+    let to = format_of(output, to)?;
+    let mut graphs = read_graphs(input, from)?;
+
+    if let Some(index) = index {
+        graphs = vec![select(graphs, index)?];
+    }
+
+    if graphs.len() > 1 && !to.holds_many_graphs() {
+        return Err(format!(
+            "{} has {} graphs but {to:?} can only hold one, choose it with --index",
+            input.display(),
+            graphs.len()
+        )
+        .into());
+    }
+
+    if !to.holds_data() && graphs.iter().any(Graph::has_attributes) {
+        eprintln!("warning: {to:?} only keeps the structure, the attributes are not written");
+    }
+
+    let contents = graphs
+        .iter()
+        .map(|graph| to.write(graph))
+        .collect::<String>();
+
+    fs::write(output, contents)
+        .map_err(|err| format!("can not write {}: {err}", output.display()))?;
+
+    Ok(())
+}
+
+pub fn render(
+    input: &Path,
+    output: &Path,
+    from: Option<Format>,
+    layout: Layout,
+    width: u32,
+    height: u32,
+    index: usize,
+) -> Result<(), Box<dyn Error>> {
+    let mut graph = select(read_graphs(input, from)?, index)?;
+
+    let node_settings = NodeSettings::default();
+    let edge_settings = EdgeSettings::default();
+    let rgb = |color: Color| {
+        let [r, g, b, _] = color.as_rgba_f32();
+
+        [r, g, b]
+    };
+
+    let style = RenderStyle {
+        background: rgb(Color::rgb(0.1, 0.1, 0.1)),
+        node_color: rgb(node_settings.base_color),
+        edge_color: rgb(edge_settings.color),
+        node_radius: node_settings.radius,
+        edge_width: edge_settings.size,
+    };
+
+    let margin = style.node_radius * 4.0;
+
+    if width as f32 <= margin || height as f32 <= margin {
+        return Err(format!("the image has to be wider and taller than {margin} pixels").into());
+    }
+
+    layout.apply(&mut graph, width as f32 - margin, height as f32 - margin);
+
+    match output.extension().and_then(|extension| extension.to_str()) {
+        Some("svg") => fs::write(output, render::svg(&graph, width, height, &style))?,
+        Some("png") => render::png(&graph, width, height, &style).save(output)?,
+        _ => {
+            return Err(format!(
+                "can not render to {}, use a .svg or .png file",
+                output.display()
+            )
+            .into())
+        }
+    }
+
+    Ok(())
+}
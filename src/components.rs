@@ -1,14 +1,42 @@
 use bevy::prelude::*;
 
+use crate::graph::{self, Dash, EdgeRoute};
+
 #[derive(Component)]
 pub struct Node;
 
 #[derive(Component)]
 pub struct MovingNode;
 
+#[derive(Component)]
+pub struct Radius(pub f32);
+
 #[derive(Component)]
 pub struct SelectedNode;
 
+#[derive(Component)]
+pub struct SelectedEdge;
+
+/// Set on the nodes that are not drawn as `NodeSettings` says.
+#[derive(Component, Clone)]
+pub struct NodeStyle(pub graph::NodeStyle);
+
+impl NodeStyle {
+    pub fn fill(&self) -> Color {
+        let [r, g, b, a] = self.0.fill;
+
+        Color::rgba(r, g, b, a)
+    }
+
+    pub fn outline(&self) -> Option<Color> {
+        self.0.outline.map(|[r, g, b, a]| Color::rgba(r, g, b, a))
+    }
+}
+
+/// Set on the nodes and edges that have attributes.
+#[derive(Component, Clone, Default)]
+pub struct Attributes(pub graph::Attributes);
+
 #[derive(Component)]
 pub struct NeighborNodes {
     pub v: Entity,
@@ -16,3 +44,70 @@ pub struct NeighborNodes {
     pub pos_v: Transform,
     pub pos_u: Transform,
 }
+
+#[derive(Component)]
+pub struct Label(pub String);
+
+#[derive(Component)]
+pub struct Weight(pub f32);
+
+#[derive(Component)]
+pub struct Directed;
+
+/// How far an edge curves away from the straight line between its nodes,
+/// so that parallel edges do not hide each other. On a loop, how much
+/// taller it is than the smallest loop.
+#[derive(Component, Clone, Copy)]
+pub struct Bend(pub f32);
+
+/// Set on the edges that are not drawn as straight lines.
+#[derive(Component, Clone, Copy)]
+pub struct Route(pub EdgeRoute);
+
+/// Corners of an orthogonal edge, from its first node to its second.
+#[derive(Component)]
+pub struct Corners(pub Vec<Vec2>);
+
+/// Set on the edges edited on their own, which are no longer drawn as
+/// `EdgeSettings` says.
+#[derive(Component, Clone, Copy, PartialEq)]
+pub struct EdgeStyle {
+    pub color: Color,
+    pub width: f32,
+    pub dash: Dash,
+}
+
+impl EdgeStyle {
+    pub fn from_graph(style: &graph::EdgeStyle) -> Self {
+        let [r, g, b, a] = style.color;
+
+        Self {
+            color: Color::rgba(r, g, b, a),
+            width: style.width,
+            dash: style.dash,
+        }
+    }
+
+    pub fn to_graph(self) -> graph::EdgeStyle {
+        graph::EdgeStyle {
+            color: self.color.as_rgba_f32(),
+            width: self.width,
+            dash: self.dash,
+        }
+    }
+}
+
+/// Set on the nodes styled by the style rules, with the style the node had
+/// before them and the one the rules gave it.
+#[derive(Component)]
+pub struct NodeRuleStyle {
+    pub before: Option<graph::NodeStyle>,
+    pub applied: graph::NodeStyle,
+}
+
+/// Set on the edges styled by the style rules, like `NodeRuleStyle`.
+#[derive(Component)]
+pub struct EdgeRuleStyle {
+    pub before: Option<EdgeStyle>,
+    pub applied: EdgeStyle,
+}
@@ -0,0 +1,447 @@
+use crate::graph::{Graph, GraphEdge};
+
+use super::ParseError;
+
+const GRAPH6_HEADER: &str = ">>graph6<<";
+const SPARSE6_HEADER: &str = ">>sparse6<<";
+const DIGRAPH6_HEADER: &str = ">>digraph6<<";
+
+/// Larger graphs are refused before their nodes are allocated. Sparse6 can
+/// describe a huge graph in a few bytes.
+const MAX_NODES: usize = 1 << 20;
+
+fn encode_size(n: usize, output: &mut String) {
+    if n <= 62 {
+        output.push((n as u8 + 63) as char);
+    } else if n <= 258047 {
+        output.push('~');
+        encode_bits(&size_bits(n, 18), output);
+    } else {
+        output.push_str("~~");
+        encode_bits(&size_bits(n, 36), output);
+    }
+}
+
+fn size_bits(n: usize, width: usize) -> Vec<bool> {
+    (0..width).rev().map(|bit| (n >> bit) & 1 == 1).collect()
+}
+
+fn encode_bits(bits: &[bool], output: &mut String) {
+    for chunk in bits.chunks(6) {
+        let mut value = 0u8;
+
+        for bit in 0..6 {
+            value <<= 1;
+
+            if chunk.get(bit).copied().unwrap_or(false) {
+                value |= 1;
+            }
+        }
+
+        output.push((value + 63) as char);
+    }
+}
+
+fn decode_size(bytes: &[u8], line: usize) -> Result<(usize, &[u8]), ParseError> {
+    let width = match bytes {
+        [126, 126, ..] => 6,
+        [126, ..] => 3,
+        [_, ..] => return Ok(((bytes[0] - 63) as usize, &bytes[1..])),
+        [] => return Err(ParseError::new(line, "missing number of nodes")),
+    };
+
+    let start = if width == 6 { 2 } else { 1 };
+
+    let Some(digits) = bytes.get(start..start + width) else {
+        return Err(ParseError::new(line, "truncated number of nodes"));
+    };
+
+    let n = digits
+        .iter()
+        .fold(0, |n, &byte| (n << 6) | (byte - 63) as usize);
+
+    if n > MAX_NODES {
+        return Err(ParseError::new(
+            line,
+            format!("{n} nodes is more than the {MAX_NODES} that can be read"),
+        ));
+    }
+
+    Ok((n, &bytes[start + width..]))
+}
+
+/// Fails unless the data holds at least `bits` bits.
+fn check_length(data: &[u8], bits: usize, line: usize) -> Result<(), ParseError> {
+    if data.len() * 6 < bits {
+        return Err(ParseError::new(line, "truncated adjacency matrix"));
+    }
+
+    Ok(())
+}
+
+fn decode_bits(bytes: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    bytes
+        .iter()
+        .flat_map(|&byte| (0..6).rev().map(move |bit| ((byte - 63) >> bit) & 1 == 1))
+}
+
+fn empty_graph(n: usize) -> Graph {
+    let mut graph = Graph::default();
+
+    for index in 0..n {
+        graph.add_node(index.to_string(), 0.0, 0.0);
+    }
+
+    graph
+}
+
+fn read_graph6(bytes: &[u8], line: usize) -> Result<Graph, ParseError> {
+    let (n, data) = decode_size(bytes, line)?;
+
+    check_length(data, n * n.saturating_sub(1) / 2, line)?;
+
+    let mut graph = empty_graph(n);
+    let mut bits = decode_bits(data);
+
+    for target in 1..n {
+        for source in 0..target {
+            let Some(bit) = bits.next() else {
+                return Err(ParseError::new(line, "truncated adjacency matrix"));
+            };
+
+            if bit {
+                graph.add_edge(GraphEdge::new(source, target, false));
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+fn read_digraph6(bytes: &[u8], line: usize) -> Result<Graph, ParseError> {
+    let (n, data) = decode_size(bytes, line)?;
+
+    check_length(data, n * n, line)?;
+
+    let mut graph = empty_graph(n);
+    let mut bits = decode_bits(data);
+
+    for source in 0..n {
+        for target in 0..n {
+            let Some(bit) = bits.next() else {
+                return Err(ParseError::new(line, "truncated adjacency matrix"));
+            };
+
+            if bit {
+                graph.add_edge(GraphEdge::new(source, target, true));
+            }
+        }
+    }
+
+    Ok(graph)
+}
+
+/// Bits needed to write a node number below `n`, none when there is a
+/// single node.
+fn sparse6_width(n: usize) -> usize {
+    let mut k = 0;
+
+    while 1 << k < n {
+        k += 1;
+    }
+
+    k
+}
+
+fn read_sparse6(bytes: &[u8], line: usize) -> Result<Graph, ParseError> {
+    let (n, data) = decode_size(bytes, line)?;
+    let mut graph = empty_graph(n);
+    let k = sparse6_width(n);
+    let mut bits = decode_bits(data);
+    let mut v = 0;
+
+    while let Some(b) = bits.next() {
+        let mut x = 0;
+
+        for _ in 0..k {
+            let Some(bit) = bits.next() else {
+                return Ok(graph);
+            };
+
+            x = (x << 1) | bit as usize;
+        }
+
+        if b {
+            v += 1;
+        }
+
+        if x >= n || v >= n {
+            break;
+        }
+
+        if x > v {
+            v = x;
+        } else {
+            graph.add_edge(GraphEdge::new(x, v, false));
+        }
+    }
+
+    Ok(graph)
+}
+
+pub fn read(input: &str) -> Result<Vec<Graph>, ParseError> {
+    let mut graphs = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let mut line = line.trim();
+
+        for header in [GRAPH6_HEADER, SPARSE6_HEADER, DIGRAPH6_HEADER] {
+            line = line.strip_prefix(header).unwrap_or(line);
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let (reader, data): (fn(&[u8], usize) -> Result<Graph, ParseError>, &str) =
+            if let Some(data) = line.strip_prefix(':') {
+                (read_sparse6, data)
+            } else if let Some(data) = line.strip_prefix('&') {
+                (read_digraph6, data)
+            } else {
+                (read_graph6, line)
+            };
+
+        if let Some(character) = data.chars().find(|c| !('?'..='~').contains(c)) {
+            return Err(ParseError::new(
+                line_number,
+                format!("invalid character {character:?}"),
+            ));
+        }
+
+        graphs.push(reader(data.as_bytes(), line_number)?);
+    }
+
+    Ok(graphs)
+}
+
+pub fn write_graph6(graph: &Graph) -> String {
+    let n = graph.nodes.len();
+    let mut adjacency = vec![false; n * n];
+
+    for edge in graph.edges.iter() {
+        adjacency[edge.source * n + edge.target] = true;
+        adjacency[edge.target * n + edge.source] = true;
+    }
+
+    let mut bits = Vec::new();
+
+    for target in 1..n {
+        for source in 0..target {
+            bits.push(adjacency[source * n + target]);
+        }
+    }
+
+    let mut output = String::new();
+
+    encode_size(n, &mut output);
+    encode_bits(&bits, &mut output);
+    output.push('\n');
+
+    output
+}
+
+pub fn write_digraph6(graph: &Graph) -> String {
+    let n = graph.nodes.len();
+    let mut bits = vec![false; n * n];
+
+    for edge in graph.edges.iter() {
+        bits[edge.source * n + edge.target] = true;
+
+        if !edge.directed {
+            bits[edge.target * n + edge.source] = true;
+        }
+    }
+
+    let mut output = String::from("&");
+
+    encode_size(n, &mut output);
+    encode_bits(&bits, &mut output);
+    output.push('\n');
+
+    output
+}
+
+pub fn write_sparse6(graph: &Graph) -> String {
+    let n = graph.nodes.len();
+    let k = sparse6_width(n);
+
+    let mut edges = graph
+        .edges
+        .iter()
+        .map(|edge| (edge.source.max(edge.target), edge.source.min(edge.target)))
+        .collect::<Vec<_>>();
+
+    edges.sort_unstable();
+
+    let mut bits = Vec::new();
+    let mut current = 0;
+
+    for (v, u) in edges {
+        if v == current {
+            bits.push(false);
+            bits.extend(size_bits(u, k));
+        } else if v == current + 1 {
+            current += 1;
+
+            bits.push(true);
+            bits.extend(size_bits(u, k));
+        } else {
+            current = v;
+
+            bits.push(true);
+            bits.extend(size_bits(v, k));
+            bits.push(false);
+            bits.extend(size_bits(u, k));
+        }
+    }
+
+    let padding = (6 - bits.len() % 6) % 6;
+
+    if k < 6 && n == 1 << k && padding >= k && current + 1 < n {
+        bits.push(false);
+    }
+
+    let padding = (6 - bits.len() % 6) % 6;
+
+    bits.extend(std::iter::repeat(true).take(padding));
+
+    let mut output = String::from(":");
+
+    encode_size(n, &mut output);
+    encode_bits(&bits, &mut output);
+    output.push('\n');
+
+    output
+}
+
+pub fn write(graph: &Graph) -> String {
+    let has_loops_or_parallel_edges = {
+        let mut pairs = graph
+            .edges
+            .iter()
+            .map(|edge| (edge.source.min(edge.target), edge.source.max(edge.target)))
+            .collect::<Vec<_>>();
+
+        pairs.sort_unstable();
+
+        pairs.iter().any(|(source, target)| source == target)
+            || pairs.windows(2).any(|pair| pair[0] == pair[1])
+    };
+
+    if graph.is_directed() {
+        write_digraph6(graph)
+    } else if has_loops_or_parallel_edges {
+        write_sparse6(graph)
+    } else {
+        write_graph6(graph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn edges(graph: &Graph) -> Vec<(usize, usize, bool)> {
+        graph
+            .edges
+            .iter()
+            .map(|edge| (edge.source, edge.target, edge.directed))
+            .collect()
+    }
+
+    fn graph(n: usize, edges: &[(usize, usize)], directed: bool) -> Graph {
+        let mut graph = empty_graph(n);
+
+        for &(source, target) in edges {
+            graph.add_edge(GraphEdge::new(source, target, directed));
+        }
+
+        graph
+    }
+
+    // The examples of nauty's formats.txt.
+
+    #[test]
+    fn graph6_round_trip() {
+        let graph = graph(5, &[(0, 2), (0, 4), (1, 3), (3, 4)], false);
+
+        assert_eq!(write_graph6(&graph), "DQc\n");
+
+        let read = read("DQc").unwrap();
+
+        assert_eq!(read[0].nodes.len(), 5);
+        assert_eq!(
+            edges(&read[0]),
+            [(0, 2, false), (1, 3, false), (0, 4, false), (3, 4, false)]
+        );
+    }
+
+    #[test]
+    fn sparse6_round_trip() {
+        let graph = graph(7, &[(0, 1), (0, 2), (1, 2), (5, 6)], false);
+
+        assert_eq!(write_sparse6(&graph), ":Fa@x^\n");
+
+        let read = read(":Fa@x^").unwrap();
+
+        assert_eq!(read[0].nodes.len(), 7);
+        assert_eq!(
+            edges(&read[0]),
+            [(0, 1, false), (0, 2, false), (1, 2, false), (5, 6, false)]
+        );
+    }
+
+    #[test]
+    fn digraph6_round_trip() {
+        let graph = graph(5, &[(0, 2), (0, 4), (3, 1), (3, 4)], true);
+
+        assert_eq!(write_digraph6(&graph), "&DI?AO?\n");
+
+        let read = read("&DI?AO?").unwrap();
+
+        assert_eq!(read[0].nodes.len(), 5);
+        assert_eq!(
+            edges(&read[0]),
+            [(0, 2, true), (0, 4, true), (3, 1, true), (3, 4, true)]
+        );
+    }
+
+    #[test]
+    fn sparse6_single_node_uses_no_bits() {
+        assert_eq!(sparse6_width(1), 0);
+        assert_eq!(sparse6_width(2), 1);
+        assert_eq!(sparse6_width(7), 3);
+
+        let graph = graph(1, &[(0, 0)], false);
+        let written = write_sparse6(&graph);
+        let read = read(&written).unwrap();
+
+        assert_eq!(edges(&read[0]), [(0, 0, false)]);
+    }
+
+    #[test]
+    fn truncated_data_is_an_error() {
+        assert!(read("DQ").is_err());
+        assert!(read("&DI?").is_err());
+        assert!(read("~?").is_err());
+    }
+
+    #[test]
+    fn oversized_graph_is_an_error() {
+        assert!(read("~~~~~~~~").is_err());
+        assert!(read(":~~~~~~~~").is_err());
+        assert!(read("~~~~~~~~~~~~~~~~~~~~").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! Mermaid flowcharts. Attributes are kept in `%%` comments after the node
+//! or edge they belong to, so that they do not show in the diagram.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::graph::{Graph, GraphEdge};
+
+use super::ParseError;
+
+fn escape(text: &str) -> String {
+    text.replace('"', "#quot;")
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("#quot;", "\"")
+}
+
+pub fn write(graph: &Graph) -> String {
+    let mut output = String::from("graph LR\n");
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        writeln!(output, "    n{index}((\"{}\"))", escape(&node.label)).unwrap();
+
+        if let Some(comment) =
+            super::attributes_comment(&format!("node n{index}"), &node.attributes)
+        {
+            writeln!(output, "    %% {comment}").unwrap();
+        }
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let arrow = if edge.directed { "-->" } else { "---" };
+
+        match super::edge_text(edge) {
+            Some(text) => writeln!(
+                output,
+                "    n{} {arrow}|\"{}\"| n{}",
+                edge.source,
+                escape(&text),
+                edge.target
+            ),
+            None => writeln!(output, "    n{} {arrow} n{}", edge.source, edge.target),
+        }
+        .unwrap();
+
+        if let Some(comment) = super::attributes_comment(&format!("edge {index}"), &edge.attributes)
+        {
+            writeln!(output, "    %% {comment}").unwrap();
+        }
+    }
+
+    output
+}
+
+/// Reads a node such as `n0`, `n0(("label"))` or `n0[label]`, giving its
+/// name and label.
+fn read_node(text: &str) -> Option<(&str, Option<String>)> {
+    let text = text.trim();
+    let end = text
+        .find(|character: char| "([{>".contains(character) || character.is_whitespace())
+        .unwrap_or(text.len());
+    let (name, shape) = text.split_at(end);
+
+    if name.is_empty() {
+        return None;
+    }
+
+    let shape = shape.trim();
+
+    if shape.is_empty() {
+        return Some((name, None));
+    }
+
+    let label = match (shape.find('"'), shape.rfind('"')) {
+        (Some(start), Some(end)) if start < end => &shape[start + 1..end],
+        _ => shape.trim_matches(|character: char| "()[]{}>".contains(character)),
+    };
+
+    Some((name, Some(unescape(label))))
+}
+
+/// Reads an edge such as `n0 --> n1` or `n0 ---|"text"| n1`, giving its two
+/// ends, whether it is directed and its text.
+fn read_edge(line: &str) -> Option<(&str, &str, bool, Option<String>)> {
+    let start = line.find("--")?;
+
+    // Dashes inside a label do not make an edge.
+    if line[..start].contains(|character: char| "\"([{".contains(character)) {
+        return None;
+    }
+
+    let source = line[..start].trim();
+    let rest = line[start..].trim_start_matches('-');
+    let (directed, rest) = match rest.strip_prefix('>') {
+        Some(rest) => (true, rest),
+        None => (false, rest),
+    };
+    let rest = rest.trim_start();
+
+    let (text, target) = match rest.strip_prefix('|') {
+        Some(rest) => {
+            let (text, target) = rest.split_once('|')?;
+            let text = text.trim();
+            let text = text
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"'))
+                .unwrap_or(text);
+
+            (Some(unescape(text)), target)
+        }
+        None => (None, rest),
+    };
+
+    Some((source, target.trim(), directed, text))
+}
+
+/// Reads a flowchart like the ones `write` gives. Nodes are created when
+/// they are first met, and labels in `|"..."|` give edges their label and
+/// weight back.
+pub fn read(input: &str) -> Result<Vec<Graph>, ParseError> {
+    let mut graph = Graph::default();
+    let mut nodes = HashMap::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim().trim_end_matches(';');
+
+        if line.is_empty() || line.starts_with("graph") || line.starts_with("flowchart") {
+            continue;
+        }
+
+        if let Some(comment) = line.strip_prefix("%%") {
+            if let Some(comment) = super::read_attributes_comment(comment, line_number)? {
+                super::apply_attributes_comment(&mut graph, &nodes, comment, line_number)?;
+            }
+
+            continue;
+        }
+
+        if let Some((source, target, directed, text)) = read_edge(line) {
+            let (Some((source, source_label)), Some((target, target_label))) =
+                (read_node(source), read_node(target))
+            else {
+                return Err(ParseError::new(line_number, "invalid edge"));
+            };
+
+            let mut edge = GraphEdge::new(
+                super::named_node(&mut graph, &mut nodes, source),
+                super::named_node(&mut graph, &mut nodes, target),
+                directed,
+            );
+
+            for (node, label) in [(edge.source, source_label), (edge.target, target_label)] {
+                if let Some(label) = label {
+                    graph.nodes[node].label = label;
+                }
+            }
+
+            if let Some(text) = text {
+                (edge.label, edge.weight) = super::read_edge_text(&text);
+            }
+
+            graph.add_edge(edge);
+
+            continue;
+        }
+
+        let Some((name, label)) = read_node(line) else {
+            return Err(ParseError::new(line_number, "invalid node"));
+        };
+
+        let node = super::named_node(&mut graph, &mut nodes, name);
+
+        if let Some(label) = label {
+            graph.nodes[node].label = label;
+        }
+    }
+
+    Ok(vec![graph])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::AttributeValue;
+
+    #[test]
+    fn quotes_in_labels_are_escaped() {
+        let mut graph = Graph::default();
+
+        graph.add_node("say \"hi\"", 0.0, 0.0);
+
+        let written = write(&graph);
+
+        assert!(written.contains("n0((\"say #quot;hi#quot;\"))"));
+        assert_eq!(read(&written).unwrap()[0].nodes[0].label, "say \"hi\"");
+    }
+
+    #[test]
+    fn attributes_are_kept_in_percent_comments() {
+        let mut graph = Graph::default();
+
+        graph.add_node("a", 0.0, 0.0);
+        graph.nodes[0]
+            .attributes
+            .insert("team".into(), AttributeValue::Text("red".into()));
+
+        assert!(write(&graph).contains("    %% node n0 @team:text=\"red\"\n"));
+
+        // Comments that are not about attributes are skipped.
+        let read = read("graph LR\n    %% a note\n    n0 --- n1\n").unwrap();
+
+        assert_eq!(read[0].nodes.len(), 2);
+    }
+
+    #[test]
+    fn arrows_give_the_direction() {
+        let read = read("graph LR\n    a --> b\n    b --- c\n    c -->|\"3\"| a\n").unwrap();
+        let edges = &read[0].edges;
+
+        assert!(edges[0].directed);
+        assert!(!edges[1].directed);
+        assert!(edges[2].directed);
+        assert_eq!(edges[2].weight, Some(3.0));
+    }
+}
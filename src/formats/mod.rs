@@ -0,0 +1,285 @@
+pub mod graph6;
+pub mod mermaid;
+pub mod plantuml;
+pub mod visualizer;
+
+use std::{collections::HashMap, error::Error, fmt, path::Path, str::FromStr};
+
+use crate::graph::{Attributes, Graph, GraphEdge};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Graph6,
+    Sparse6,
+    Digraph6,
+    Mermaid,
+    PlantUml,
+    Visualizer,
+}
+
+impl Format {
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension()?.to_str()?.to_lowercase().parse().ok()
+    }
+
+    pub fn read(self, input: &str) -> Result<Vec<Graph>, ParseError> {
+        match self {
+            Self::Graph6 | Self::Sparse6 | Self::Digraph6 => graph6::read(input),
+            Self::Mermaid => mermaid::read(input),
+            Self::PlantUml => plantuml::read(input),
+            Self::Visualizer => visualizer::read(input),
+        }
+    }
+
+    pub fn write(self, graph: &Graph) -> String {
+        match self {
+            Self::Graph6 => graph6::write_graph6(graph),
+            Self::Sparse6 => graph6::write_sparse6(graph),
+            Self::Digraph6 => graph6::write_digraph6(graph),
+            Self::Mermaid => mermaid::write(graph),
+            Self::PlantUml => plantuml::write(graph),
+            Self::Visualizer => visualizer::write(graph),
+        }
+    }
+
+    pub fn holds_many_graphs(self) -> bool {
+        self != Self::Mermaid
+    }
+
+    /// Whether the format keeps the labels, weights and attributes.
+    pub fn holds_data(self) -> bool {
+        !matches!(self, Self::Graph6 | Self::Sparse6 | Self::Digraph6)
+    }
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "graph6" | "g6" => Ok(Self::Graph6),
+            "sparse6" | "s6" => Ok(Self::Sparse6),
+            "digraph6" | "d6" => Ok(Self::Digraph6),
+            "mermaid" | "mmd" => Ok(Self::Mermaid),
+            "plantuml" | "puml" => Ok(Self::PlantUml),
+            "visualizer" | "gvis" => Ok(Self::Visualizer),
+            _ => Err(format!(
+                "unknown format {s:?}, expected one of graph6, sparse6, digraph6, mermaid, plantuml or visualizer"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl Error for ParseError {}
+
+pub fn edge_text(edge: &GraphEdge) -> Option<String> {
+    crate::graph::edge_text(edge.label.as_deref(), edge.weight)
+}
+
+/// Reads back the text of `edge_text`: a number is a weight, and a number
+/// in parentheses at the end follows a label.
+pub fn read_edge_text(text: &str) -> (Option<String>, Option<f32>) {
+    if let Ok(weight) = text.parse() {
+        return (None, Some(weight));
+    }
+
+    let labelled = text
+        .strip_suffix(')')
+        .and_then(|text| text.rsplit_once(" ("))
+        .and_then(|(label, weight)| Some((label, weight.parse().ok()?)));
+
+    match labelled {
+        Some((label, weight)) => (Some(label.to_string()), Some(weight)),
+        None => (Some(text.to_string()), None),
+    }
+}
+
+/// The node or edge an attribute comment is about: a node by its name in
+/// the file, an edge by its position among the edges.
+pub enum AttributeOwner {
+    Node(String),
+    Edge(usize),
+}
+
+/// The comment that keeps attributes in the formats that only draw the
+/// graph, like `node n0 @team:text="red"` or `edge 3 @cost:number=2`. The
+/// attributes are written as in `.gvis` files.
+pub fn attributes_comment(owner: &str, attributes: &Attributes) -> Option<String> {
+    if attributes.is_empty() {
+        return None;
+    }
+
+    let mut comment = owner.to_string();
+
+    visualizer::write_attributes(&mut comment, attributes);
+
+    Some(comment)
+}
+
+/// Reads a comment written by `attributes_comment`. Other comments give
+/// `None`.
+pub fn read_attributes_comment(
+    comment: &str,
+    line_number: usize,
+) -> Result<Option<(AttributeOwner, Attributes)>, ParseError> {
+    let comment = comment.trim();
+
+    if !comment.starts_with("node ") && !comment.starts_with("edge ") {
+        return Ok(None);
+    }
+
+    let tokens = visualizer::tokenize(comment, line_number)?;
+
+    let [kind, owner, options @ ..] = tokens.as_slice() else {
+        return Ok(None);
+    };
+
+    if options.is_empty() || !options.iter().all(|option| option.starts_with('@')) {
+        return Ok(None);
+    }
+
+    let owner = match kind.as_str() {
+        "node" => AttributeOwner::Node(owner.clone()),
+        _ => match owner.parse() {
+            Ok(index) => AttributeOwner::Edge(index),
+            Err(_) => return Ok(None),
+        },
+    };
+
+    let mut attributes = Attributes::new();
+
+    for option in options {
+        let (name, value) = visualizer::read_attribute(&option[1..], line_number)?;
+
+        attributes.insert(name, value);
+    }
+
+    Ok(Some((owner, attributes)))
+}
+
+/// Gives the attributes of a comment to the node or edge it is about.
+fn apply_attributes_comment(
+    graph: &mut Graph,
+    nodes: &HashMap<String, usize>,
+    (owner, attributes): (AttributeOwner, Attributes),
+    line_number: usize,
+) -> Result<(), ParseError> {
+    let target = match &owner {
+        AttributeOwner::Node(name) => nodes
+            .get(name)
+            .map(|&node| &mut graph.nodes[node].attributes),
+        AttributeOwner::Edge(index) => graph.edges.get_mut(*index).map(|edge| &mut edge.attributes),
+    };
+
+    let Some(target) = target else {
+        return Err(ParseError::new(
+            line_number,
+            match owner {
+                AttributeOwner::Node(name) => format!("attributes of unknown node {name:?}"),
+                AttributeOwner::Edge(index) => format!("attributes of unknown edge {index}"),
+            },
+        ));
+    };
+
+    target.extend(attributes);
+
+    Ok(())
+}
+
+/// The node named `name` in a Mermaid or PlantUML file, added the first
+/// time it is met.
+fn named_node(graph: &mut Graph, nodes: &mut HashMap<String, usize>, name: &str) -> usize {
+    *nodes
+        .entry(name.to_string())
+        .or_insert_with(|| graph.add_node(name.to_string(), 0.0, 0.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::AttributeValue;
+
+    /// A graph with a node label that has a space, a directed edge with a
+    /// label, a weight and attributes, and an undirected edge going back.
+    fn example() -> Graph {
+        let mut graph = Graph::default();
+
+        graph.add_node("a b", 0.0, 0.0);
+        graph.add_node("c", 0.0, 0.0);
+        graph.nodes[0]
+            .attributes
+            .insert("team".into(), AttributeValue::Text("red one".into()));
+
+        let mut edge = GraphEdge::new(0, 1, true);
+
+        edge.label = Some("road".into());
+        edge.weight = Some(2.5);
+        edge.attributes
+            .insert("cost".into(), AttributeValue::Number(-2.0));
+        edge.attributes
+            .insert("open".into(), AttributeValue::Bool(true));
+        graph.add_edge(edge);
+        graph.add_edge(GraphEdge::new(1, 0, false));
+
+        graph
+    }
+
+    #[test]
+    fn diagrams_keep_labels_weights_and_attributes() {
+        let graph = example();
+
+        for format in [Format::Mermaid, Format::PlantUml] {
+            let written = format.write(&graph);
+
+            assert!(!written.contains("team: "), "{format:?}");
+
+            let read = format.read(&written).unwrap();
+
+            assert_eq!(read.len(), 1, "{format:?}");
+
+            let read = &read[0];
+
+            assert_eq!(read.nodes.len(), 2, "{format:?}");
+            assert_eq!(read.nodes[0].label, "a b", "{format:?}");
+            assert_eq!(
+                read.nodes[0].attributes, graph.nodes[0].attributes,
+                "{format:?}"
+            );
+            assert_eq!(read.edges.len(), 2, "{format:?}");
+            assert_eq!(read.edges[0].label.as_deref(), Some("road"), "{format:?}");
+            assert_eq!(read.edges[0].weight, Some(2.5), "{format:?}");
+            assert!(read.edges[0].directed, "{format:?}");
+            assert_eq!(
+                read.edges[0].attributes, graph.edges[0].attributes,
+                "{format:?}"
+            );
+            assert!(!read.edges[1].directed, "{format:?}");
+            assert_eq!(
+                (read.edges[1].source, read.edges[1].target),
+                (1, 0),
+                "{format:?}"
+            );
+        }
+    }
+}
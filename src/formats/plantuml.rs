@@ -0,0 +1,208 @@
+//! PlantUML use case diagrams. Attributes are kept in `'` comments after the
+//! node or edge they belong to, so that they do not show in the diagram.
+
+use std::{collections::HashMap, fmt::Write};
+
+use crate::graph::{Graph, GraphEdge};
+
+use super::ParseError;
+
+fn escape(text: &str) -> String {
+    text.replace('"', "'").replace('\n', " ")
+}
+
+pub fn write(graph: &Graph) -> String {
+    let mut output = String::from("@startuml\nleft to right direction\n");
+
+    for (index, node) in graph.nodes.iter().enumerate() {
+        writeln!(output, "usecase \"{}\" as n{index}", escape(&node.label)).unwrap();
+
+        if let Some(comment) =
+            super::attributes_comment(&format!("node n{index}"), &node.attributes)
+        {
+            writeln!(output, "' {comment}").unwrap();
+        }
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let arrow = if edge.directed { "-->" } else { "--" };
+
+        match super::edge_text(edge) {
+            Some(text) => writeln!(
+                output,
+                "n{} {arrow} n{} : {}",
+                edge.source,
+                edge.target,
+                escape(&text)
+            ),
+            None => writeln!(output, "n{} {arrow} n{}", edge.source, edge.target),
+        }
+        .unwrap();
+
+        if let Some(comment) = super::attributes_comment(&format!("edge {index}"), &edge.attributes)
+        {
+            writeln!(output, "' {comment}").unwrap();
+        }
+    }
+
+    output.push_str("@enduml\n");
+
+    output
+}
+
+/// Reads a `usecase "label" as name` line.
+fn read_usecase(line: &str) -> Option<(String, &str)> {
+    let rest = line.strip_prefix("usecase")?.trim();
+    let (label, name) = rest.rsplit_once(" as ")?;
+    let label = label.trim();
+    let label = label
+        .strip_prefix('"')
+        .and_then(|label| label.strip_suffix('"'))
+        .or_else(|| {
+            label
+                .strip_prefix('(')
+                .and_then(|label| label.strip_suffix(')'))
+        })
+        .unwrap_or(label);
+
+    Some((label.to_string(), name.trim()))
+}
+
+/// Reads a `source --> target : text` or `source -- target` line.
+fn read_edge(line: &str) -> Option<(&str, &str, bool, Option<&str>)> {
+    let (line, text) = match line.split_once(" : ") {
+        Some((line, text)) => (line, Some(text.trim())),
+        None => (line, None),
+    };
+    let start = line.find("--")?;
+    let source = line[..start].trim();
+    let rest = line[start..].trim_start_matches('-');
+    let (directed, target) = match rest.strip_prefix('>') {
+        Some(target) => (true, target),
+        None => (false, rest),
+    };
+    let target = target.trim();
+
+    if source.is_empty() || target.is_empty() {
+        return None;
+    }
+
+    Some((source, target, directed, text))
+}
+
+/// Reads the diagrams `write` gives, one graph per `@startuml` block. Lines
+/// with other PlantUML commands are skipped.
+pub fn read(input: &str) -> Result<Vec<Graph>, ParseError> {
+    let mut graphs = Vec::new();
+    let mut graph = None;
+    let mut nodes = HashMap::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.starts_with("@startuml") {
+            graphs.extend(graph.replace(Graph::default()));
+            nodes.clear();
+
+            continue;
+        }
+
+        if line.starts_with("@enduml") {
+            graphs.extend(graph.take());
+            nodes.clear();
+
+            continue;
+        }
+
+        if line.is_empty() {
+            continue;
+        }
+
+        let graph = graph.get_or_insert_with(Graph::default);
+
+        if let Some(comment) = line.strip_prefix('\'') {
+            if let Some(comment) = super::read_attributes_comment(comment, line_number)? {
+                super::apply_attributes_comment(graph, &nodes, comment, line_number)?;
+            }
+
+            continue;
+        }
+
+        if let Some((label, name)) = read_usecase(line) {
+            let node = super::named_node(graph, &mut nodes, name);
+
+            graph.nodes[node].label = label;
+
+            continue;
+        }
+
+        if line.contains("--") {
+            let Some((source, target, directed, text)) = read_edge(line) else {
+                return Err(ParseError::new(line_number, "invalid edge"));
+            };
+
+            let mut edge = GraphEdge::new(
+                super::named_node(graph, &mut nodes, source),
+                super::named_node(graph, &mut nodes, target),
+                directed,
+            );
+
+            if let Some(text) = text {
+                (edge.label, edge.weight) = super::read_edge_text(text);
+            }
+
+            graph.add_edge(edge);
+        }
+    }
+
+    graphs.extend(graph);
+
+    Ok(graphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::AttributeValue;
+
+    #[test]
+    fn quotes_and_line_breaks_are_replaced() {
+        let mut graph = Graph::default();
+
+        graph.add_node("say \"hi\"\nbye", 0.0, 0.0);
+
+        let written = write(&graph);
+
+        assert!(written.contains("usecase \"say 'hi' bye\" as n0"));
+        assert_eq!(read(&written).unwrap()[0].nodes[0].label, "say 'hi' bye");
+    }
+
+    #[test]
+    fn attributes_are_kept_in_apostrophe_comments() {
+        let mut graph = Graph::default();
+
+        graph.add_node("a", 0.0, 0.0);
+        graph.nodes[0]
+            .attributes
+            .insert("team".into(), AttributeValue::Text("red".into()));
+
+        assert!(write(&graph).contains("\n' node n0 @team:text=\"red\"\n"));
+
+        // Comments that are not about attributes are skipped.
+        let read = read("@startuml\n' a note\nn0 -- n1\n@enduml\n").unwrap();
+
+        assert_eq!(read[0].nodes.len(), 2);
+    }
+
+    #[test]
+    fn arrows_give_the_direction() {
+        let read = read("@startuml\na --> b\nb -- c\nc --> a : 3\n@enduml\n").unwrap();
+        let edges = &read[0].edges;
+
+        assert!(edges[0].directed);
+        assert!(!edges[1].directed);
+        assert!(edges[2].directed);
+        assert_eq!(edges[2].weight, Some(3.0));
+    }
+}
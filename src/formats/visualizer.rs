@@ -0,0 +1,545 @@
+//! The visualizer's own format, which keeps everything shown on the canvas.
+//! Every graph starts with a `graph` line, followed by one line per node and
+//! per edge:
+//!
+//! ```text
+//! graph
+//! node "a" -120 40
+//! node "b" 80 40 shape=hexagon size=18 fill=#ff8000ff outline=#000000ff icon="icons/b.png"
+//! edge 0 1 directed weight=2.5 label="road" route=curved:0.33,0.2,0.67,0.2 color=#ff0000ff width=2 dash=dashed @lanes:number=2
+//! ```
+//!
+//! Nodes are numbered from 0 in the order they are listed, and only the
+//! nodes and edges drawn differently from the default have a style. Attributes are
+//! written as `@name:type=value`, with a type of `text`, `number` or `bool`.
+//! Empty lines and lines starting with `#` are skipped.
+
+use std::fmt::Write;
+
+use crate::graph::{
+    AttributeValue, Attributes, Dash, EdgeRoute, EdgeStyle, Graph, GraphEdge, NodeShape, NodeStyle,
+};
+
+use super::ParseError;
+
+fn quote(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn write_color(color: [f32; 4]) -> String {
+    color.iter().fold(String::from("#"), |mut output, channel| {
+        let byte = (channel.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+        write!(output, "{byte:02x}").unwrap();
+        output
+    })
+}
+
+fn write_style(output: &mut String, style: &NodeStyle) {
+    write!(
+        output,
+        " shape={} size={} fill={}",
+        style.shape.name().to_lowercase(),
+        style.radius,
+        write_color(style.fill)
+    )
+    .unwrap();
+
+    if let Some(outline) = style.outline {
+        write!(output, " outline={}", write_color(outline)).unwrap();
+    }
+
+    if let Some(icon) = &style.icon {
+        write!(output, " icon={}", quote(icon)).unwrap();
+    }
+}
+
+pub(super) fn write_attributes(output: &mut String, attributes: &Attributes) {
+    for (name, value) in attributes.iter() {
+        let name = if name
+            .chars()
+            .all(|character| character.is_alphanumeric() || "_-.".contains(character))
+        {
+            name.clone()
+        } else {
+            quote(name)
+        };
+
+        let text = match value {
+            AttributeValue::Text(text) => quote(text),
+            value => value.to_string(),
+        };
+
+        write!(output, " @{name}:{}={text}", value.type_name()).unwrap();
+    }
+}
+
+fn write_route(route: EdgeRoute) -> String {
+    match route {
+        EdgeRoute::Straight => "straight".to_string(),
+        EdgeRoute::Orthogonal => "orthogonal".to_string(),
+        EdgeRoute::Curved {
+            controls: [(along_1, across_1), (along_2, across_2)],
+        } => format!("curved:{along_1},{across_1},{along_2},{across_2}"),
+    }
+}
+
+pub fn write(graph: &Graph) -> String {
+    let mut output = String::from("graph\n");
+
+    for node in graph.nodes.iter() {
+        write!(output, "node {} {} {}", quote(&node.label), node.x, node.y).unwrap();
+
+        if let Some(style) = &node.style {
+            write_style(&mut output, style);
+        }
+
+        write_attributes(&mut output, &node.attributes);
+
+        output.push('\n');
+    }
+
+    for edge in graph.edges.iter() {
+        write!(output, "edge {} {}", edge.source, edge.target).unwrap();
+
+        if edge.directed {
+            output.push_str(" directed");
+        }
+
+        if let Some(weight) = edge.weight {
+            write!(output, " weight={weight}").unwrap();
+        }
+
+        if let Some(label) = &edge.label {
+            write!(output, " label={}", quote(label)).unwrap();
+        }
+
+        if edge.route != EdgeRoute::Straight {
+            write!(output, " route={}", write_route(edge.route)).unwrap();
+        }
+
+        if let Some(style) = &edge.style {
+            write!(
+                output,
+                " color={} width={} dash={}",
+                write_color(style.color),
+                style.width,
+                style.dash.name().to_lowercase()
+            )
+            .unwrap();
+        }
+
+        write_attributes(&mut output, &edge.attributes);
+
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Splits a line on whitespace, keeping quoted text together and removing
+/// the quotes and escapes.
+pub(super) fn tokenize(line: &str, line_number: usize) -> Result<Vec<String>, ParseError> {
+    let mut tokens = Vec::new();
+    let mut token = None::<String>;
+    let mut chars = line.chars();
+    let mut quoted = false;
+
+    while let Some(character) = chars.next() {
+        match character {
+            '"' => {
+                quoted = !quoted;
+                token.get_or_insert_with(String::new);
+            }
+            '\\' if quoted => match chars.next() {
+                Some(escaped) => token.get_or_insert_with(String::new).push(escaped),
+                None => return Err(ParseError::new(line_number, "unfinished escape")),
+            },
+            character if character.is_whitespace() && !quoted => {
+                tokens.extend(token.take());
+            }
+            character => token.get_or_insert_with(String::new).push(character),
+        }
+    }
+
+    if quoted {
+        return Err(ParseError::new(line_number, "unclosed quote"));
+    }
+
+    tokens.extend(token);
+
+    Ok(tokens)
+}
+
+fn parse_number<T: std::str::FromStr>(
+    text: &str,
+    what: &str,
+    line_number: usize,
+) -> Result<T, ParseError> {
+    text.parse()
+        .map_err(|_| ParseError::new(line_number, format!("invalid {what} {text:?}")))
+}
+
+fn read_color(text: &str, line_number: usize) -> Result<[f32; 4], ParseError> {
+    let invalid = || ParseError::new(line_number, format!("invalid color {text:?}"));
+    let hex = text.strip_prefix('#').ok_or_else(invalid)?;
+
+    if hex.len() != 8 || !hex.is_ascii() {
+        return Err(invalid());
+    }
+
+    let mut color = [0.0; 4];
+
+    for (index, channel) in color.iter_mut().enumerate() {
+        let byte = u8::from_str_radix(&hex[index * 2..index * 2 + 2], 16).map_err(|_| invalid())?;
+
+        *channel = byte as f32 / 255.0;
+    }
+
+    Ok(color)
+}
+
+/// Reads an `@name:type=value` option, given without its `@`.
+pub(super) fn read_attribute(
+    text: &str,
+    line_number: usize,
+) -> Result<(String, AttributeValue), ParseError> {
+    let invalid = || ParseError::new(line_number, format!("invalid attribute {text:?}"));
+    let (name_and_type, value) = text.split_once('=').ok_or_else(invalid)?;
+    let (name, type_name) = name_and_type.rsplit_once(':').ok_or_else(invalid)?;
+
+    if name.is_empty() {
+        return Err(invalid());
+    }
+
+    let value = AttributeValue::parse(type_name, value)
+        .map_err(|err| ParseError::new(line_number, format!("attribute {name:?}: {err}")))?;
+
+    Ok((name.to_string(), value))
+}
+
+fn read_style<'a>(
+    options: impl Iterator<Item = &'a String>,
+    line_number: usize,
+) -> Result<Option<NodeStyle>, ParseError> {
+    let mut options = options.peekable();
+
+    if options.peek().is_none() {
+        return Ok(None);
+    }
+
+    let mut style = NodeStyle::default();
+
+    for option in options {
+        match option.split_once('=') {
+            Some(("shape", value)) => {
+                style.shape = NodeShape::ALL
+                    .into_iter()
+                    .find(|shape| shape.name().eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        ParseError::new(line_number, format!("unknown shape {value:?}"))
+                    })?
+            }
+            Some(("size", value)) => style.radius = parse_number(value, "size", line_number)?,
+            Some(("fill", value)) => style.fill = read_color(value, line_number)?,
+            Some(("outline", value)) => style.outline = Some(read_color(value, line_number)?),
+            Some(("icon", value)) => style.icon = Some(value.to_string()),
+            _ => {
+                return Err(ParseError::new(
+                    line_number,
+                    format!("unknown node option {option:?}"),
+                ))
+            }
+        }
+    }
+
+    Ok(Some(style))
+}
+
+fn read_route(text: &str, line_number: usize) -> Result<EdgeRoute, ParseError> {
+    match text {
+        "straight" => return Ok(EdgeRoute::Straight),
+        "orthogonal" => return Ok(EdgeRoute::Orthogonal),
+        _ => {}
+    }
+
+    let values = text
+        .strip_prefix("curved:")
+        .ok_or_else(|| ParseError::new(line_number, format!("unknown route {text:?}")))?
+        .split(',')
+        .map(|value| parse_number::<f32>(value, "control point", line_number))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let [along_1, across_1, along_2, across_2] = values[..] else {
+        return Err(ParseError::new(
+            line_number,
+            "a curved route needs four numbers",
+        ));
+    };
+
+    Ok(EdgeRoute::Curved {
+        controls: [(along_1, across_1), (along_2, across_2)],
+    })
+}
+
+fn read_edge(
+    graph: &Graph,
+    tokens: &[String],
+    line_number: usize,
+) -> Result<GraphEdge, ParseError> {
+    let [source, target, options @ ..] = tokens else {
+        return Err(ParseError::new(line_number, "an edge needs two nodes"));
+    };
+
+    let mut edge = GraphEdge::new(
+        parse_number(source, "node", line_number)?,
+        parse_number(target, "node", line_number)?,
+        false,
+    );
+
+    for node in [edge.source, edge.target] {
+        if node >= graph.nodes.len() {
+            return Err(ParseError::new(
+                line_number,
+                format!("node {node} is not listed before the edge"),
+            ));
+        }
+    }
+
+    for option in options {
+        if let Some(attribute) = option.strip_prefix('@') {
+            let (name, value) = read_attribute(attribute, line_number)?;
+
+            edge.attributes.insert(name, value);
+
+            continue;
+        }
+
+        match option.split_once('=') {
+            None if option == "directed" => edge.directed = true,
+            Some(("weight", value)) => {
+                edge.weight = Some(parse_number(value, "weight", line_number)?)
+            }
+            Some(("label", value)) => edge.label = Some(value.to_string()),
+            Some(("route", value)) => edge.route = read_route(value, line_number)?,
+            Some(("color", value)) => {
+                edge.style.get_or_insert_with(EdgeStyle::default).color =
+                    read_color(value, line_number)?
+            }
+            Some(("width", value)) => {
+                edge.style.get_or_insert_with(EdgeStyle::default).width =
+                    parse_number(value, "width", line_number)?
+            }
+            Some(("dash", value)) => {
+                edge.style.get_or_insert_with(EdgeStyle::default).dash = Dash::ALL
+                    .into_iter()
+                    .find(|dash| dash.name().eq_ignore_ascii_case(value))
+                    .ok_or_else(|| {
+                        ParseError::new(line_number, format!("unknown dash {value:?}"))
+                    })?
+            }
+            _ => {
+                return Err(ParseError::new(
+                    line_number,
+                    format!("unknown edge option {option:?}"),
+                ))
+            }
+        }
+    }
+
+    Ok(edge)
+}
+
+pub fn read(input: &str) -> Result<Vec<Graph>, ParseError> {
+    let mut graphs = Vec::new();
+
+    for (index, line) in input.lines().enumerate() {
+        let line_number = index + 1;
+        let line = line.trim();
+
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let tokens = tokenize(line, line_number)?;
+        let (keyword, rest) = tokens
+            .split_first()
+            .map(|(keyword, rest)| (keyword.as_str(), rest))
+            .unwrap_or_default();
+
+        if keyword == "graph" {
+            graphs.push(Graph::default());
+
+            continue;
+        }
+
+        let Some(graph) = graphs.last_mut() else {
+            return Err(ParseError::new(
+                line_number,
+                "expected a graph line before the nodes and edges",
+            ));
+        };
+
+        match keyword {
+            "node" => {
+                let [label, x, y, options @ ..] = rest else {
+                    return Err(ParseError::new(
+                        line_number,
+                        "a node needs a label and two coordinates",
+                    ));
+                };
+
+                let node = graph.add_node(
+                    label.clone(),
+                    parse_number(x, "coordinate", line_number)?,
+                    parse_number(y, "coordinate", line_number)?,
+                );
+
+                let (attributes, style_options) = options
+                    .iter()
+                    .partition::<Vec<_>, _>(|option| option.starts_with('@'));
+
+                graph.nodes[node].style = read_style(style_options.into_iter(), line_number)?;
+
+                for attribute in attributes {
+                    let (name, value) = read_attribute(&attribute[1..], line_number)?;
+
+                    graph.nodes[node].attributes.insert(name, value);
+                }
+            }
+            "edge" => {
+                let edge = read_edge(graph, rest, line_number)?;
+
+                graph.add_edge(edge);
+            }
+            _ => {
+                return Err(ParseError::new(
+                    line_number,
+                    format!("unknown line {keyword:?}"),
+                ))
+            }
+        }
+    }
+
+    Ok(graphs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Graph {
+        let mut graph = Graph::default();
+
+        let a = graph.add_node("say \"hi\" \\ bye", -120.0, 40.5);
+        let b = graph.add_node("b", 80.0, 40.0);
+
+        graph.nodes[b].style = Some(NodeStyle {
+            shape: NodeShape::Hexagon,
+            radius: 18.0,
+            fill: [1.0, 128.0 / 255.0, 0.0, 1.0],
+            outline: Some([0.0, 0.0, 0.0, 1.0]),
+            icon: Some("icons/my b.png".to_string()),
+        });
+        graph.nodes[a].attributes.insert(
+            "home town".to_string(),
+            AttributeValue::Text("a \"b\"".to_string()),
+        );
+        graph.nodes[a]
+            .attributes
+            .insert("visited".to_string(), AttributeValue::Bool(true));
+
+        let mut road = GraphEdge::new(a, b, true);
+
+        road.weight = Some(2.5);
+        road.label = Some("main road".to_string());
+        road.route = EdgeRoute::Curved {
+            controls: [(0.33, 0.2), (0.67, -0.2)],
+        };
+        road.attributes
+            .insert("lanes".to_string(), AttributeValue::Number(2.0));
+
+        let mut back = GraphEdge::new(b, a, false);
+
+        back.route = EdgeRoute::Orthogonal;
+        back.style = Some(EdgeStyle {
+            color: [1.0, 0.0, 128.0 / 255.0, 64.0 / 255.0],
+            width: 6.5,
+            dash: Dash::Dotted,
+        });
+
+        graph.add_edge(road);
+        graph.add_edge(back);
+        graph.add_edge(GraphEdge::new(b, b, false));
+
+        graph
+    }
+
+    #[test]
+    fn round_trip() {
+        let graph = example();
+        let written = write(&graph);
+
+        assert_eq!(read(&written).unwrap(), [graph]);
+    }
+
+    #[test]
+    fn several_graphs_round_trip() {
+        let graphs = [example(), Graph::default(), example()];
+        let written = graphs.iter().map(write).collect::<String>();
+
+        assert_eq!(read(&written).unwrap(), graphs);
+    }
+
+    #[test]
+    fn reads_the_documented_example() {
+        let graphs = read(concat!(
+            "# a comment\n",
+            "graph\n",
+            "node \"a\" -120 40\n",
+            "node \"b\" 80 40 shape=hexagon size=18 fill=#ff8000ff outline=#000000ff icon=\"icons/b.png\"\n",
+            "\n",
+            "edge 0 1 directed weight=2.5 label=\"road\" route=curved:0.33,0.2,0.67,0.2 color=#ff0000ff width=2 dash=dashed @lanes:number=2\n",
+        ))
+        .unwrap();
+
+        let [graph] = &graphs[..] else {
+            panic!("expected one graph");
+        };
+
+        assert_eq!(graph.nodes[0].style, None);
+        assert_eq!(
+            graph.nodes[1].style.as_ref().map(|style| style.shape),
+            Some(NodeShape::Hexagon)
+        );
+
+        let edge = &graph.edges[0];
+
+        assert!(edge.directed);
+        assert_eq!(edge.weight, Some(2.5));
+        assert_eq!(edge.label.as_deref(), Some("road"));
+        assert_eq!(
+            edge.style,
+            Some(EdgeStyle {
+                color: [1.0, 0.0, 0.0, 1.0],
+                width: 2.0,
+                dash: Dash::Dashed,
+            })
+        );
+        assert_eq!(
+            edge.attributes.get("lanes"),
+            Some(&AttributeValue::Number(2.0))
+        );
+    }
+
+    #[test]
+    fn invalid_lines_are_errors() {
+        assert!(read("node \"a\" 0 0").is_err());
+        assert!(read("graph\nnode \"a 0 0").is_err());
+        assert!(read("graph\nnode \"a\" 0 0\nedge 0 1").is_err());
+        assert!(read("graph\nnode \"a\" 0 0 shape=star").is_err());
+        assert!(read("graph\nnode \"a\" 0 0 @size:number=big").is_err());
+        assert!(read("graph\nnode \"a\" 0 0\nedge 0 0 route=curved:1,2").is_err());
+        assert!(read("graph\nnode \"a\" 0 0\nedge 0 0 dash=wavy").is_err());
+    }
+}
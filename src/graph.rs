@@ -0,0 +1,349 @@
+use std::{
+    collections::BTreeMap,
+    f32::consts::{FRAC_PI_2, FRAC_PI_4, TAU},
+    fmt,
+};
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Graph {
+    pub nodes: Vec<GraphNode>,
+    pub edges: Vec<GraphEdge>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphNode {
+    pub label: String,
+    pub x: f32,
+    pub y: f32,
+    /// Set on the nodes that are not drawn with the default settings.
+    pub style: Option<NodeStyle>,
+    pub attributes: Attributes,
+}
+
+/// A value attached to a node or an edge under a name of its own.
+#[derive(Clone, Debug, PartialEq)]
+pub enum AttributeValue {
+    Text(String),
+    Number(f64),
+    Bool(bool),
+}
+
+impl AttributeValue {
+    pub const TYPE_NAMES: [&'static str; 3] = ["text", "number", "bool"];
+
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::Text(_) => "text",
+            Self::Number(_) => "number",
+            Self::Bool(_) => "bool",
+        }
+    }
+
+    /// Reads a value of the type called `type_name`.
+    pub fn parse(type_name: &str, text: &str) -> Result<Self, String> {
+        match type_name {
+            "text" => Ok(Self::Text(text.to_string())),
+            "number" => text
+                .trim()
+                .parse()
+                .map(Self::Number)
+                .map_err(|_| format!("{text:?} is not a number")),
+            "bool" => match text.trim() {
+                "true" => Ok(Self::Bool(true)),
+                "false" => Ok(Self::Bool(false)),
+                _ => Err(format!("{text:?} is neither true nor false")),
+            },
+            _ => Err(format!("unknown attribute type {type_name:?}")),
+        }
+    }
+}
+
+impl fmt::Display for AttributeValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Text(text) => write!(f, "{text}"),
+            Self::Number(number) => write!(f, "{number}"),
+            Self::Bool(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+/// Attributes by name, in alphabetical order.
+pub type Attributes = BTreeMap<String, AttributeValue>;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum NodeShape {
+    #[default]
+    Circle,
+    Square,
+    Diamond,
+    Hexagon,
+}
+
+impl NodeShape {
+    pub const ALL: [NodeShape; 4] = [
+        NodeShape::Circle,
+        NodeShape::Square,
+        NodeShape::Diamond,
+        NodeShape::Hexagon,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Circle => "Circle",
+            Self::Square => "Square",
+            Self::Diamond => "Diamond",
+            Self::Hexagon => "Hexagon",
+        }
+    }
+
+    /// Corners of the shape around the origin, fitting in the circle of the
+    /// given radius, clockwise from the top (no corners for a circle).
+    pub fn corners(self, radius: f32) -> Vec<(f32, f32)> {
+        let (sides, first) = match self {
+            Self::Circle => return Vec::new(),
+            Self::Square => (4, FRAC_PI_4),
+            Self::Diamond => (4, FRAC_PI_2),
+            Self::Hexagon => (6, FRAC_PI_2),
+        };
+
+        (0..sides)
+            .map(|side| {
+                let angle = first - TAU * side as f32 / sides as f32;
+
+                (radius * angle.cos(), radius * angle.sin())
+            })
+            .collect()
+    }
+
+    /// Whether the point `(x, y)`, relative to the center, is inside the
+    /// shape.
+    pub fn contains(self, x: f32, y: f32, radius: f32) -> bool {
+        let corners = self.corners(radius);
+
+        if corners.is_empty() {
+            return x * x + y * y <= radius * radius;
+        }
+
+        // The corners go clockwise, so the point is on the right of every
+        // side.
+        (0..corners.len()).all(|index| {
+            let (ax, ay) = corners[index];
+            let (bx, by) = corners[(index + 1) % corners.len()];
+
+            (bx - ax) * (y - ay) - (by - ay) * (x - ax) <= 0.0
+        })
+    }
+}
+
+/// How a node is drawn. Colors are sRGB with alpha, from 0 to 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct NodeStyle {
+    pub shape: NodeShape,
+    pub radius: f32,
+    pub fill: [f32; 4],
+    pub outline: Option<[f32; 4]>,
+    /// Path of an image file drawn over the node.
+    pub icon: Option<String>,
+}
+
+impl Default for NodeStyle {
+    /// Same as the default node settings.
+    fn default() -> Self {
+        Self {
+            shape: NodeShape::Circle,
+            radius: 12.0,
+            fill: [0.94, 0.97, 1.0, 1.0],
+            outline: None,
+            icon: None,
+        }
+    }
+}
+
+/// How an edge is drawn between its nodes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum EdgeRoute {
+    #[default]
+    Straight,
+    /// A cubic curve. Each control point is given as `(along, across)`:
+    /// the fraction of the way from the source to the target, then the
+    /// distance to the left of that line, as a fraction of its length, so
+    /// that the curve keeps its shape when the nodes move.
+    Curved { controls: [(f32, f32); 2] },
+    /// Horizontal and vertical segments, placed around the other nodes.
+    Orthogonal,
+}
+
+impl EdgeRoute {
+    /// Control points of a gentle curve to the left of the edge.
+    pub const DEFAULT_CONTROLS: [(f32, f32); 2] = [(1.0 / 3.0, 0.2), (2.0 / 3.0, 0.2)];
+
+    pub const CURVED: Self = Self::Curved {
+        controls: Self::DEFAULT_CONTROLS,
+    };
+
+    /// The same route, followed from the target to the source.
+    pub fn reversed(self) -> Self {
+        match self {
+            Self::Curved {
+                controls: [(along_1, across_1), (along_2, across_2)],
+            } => Self::Curved {
+                controls: [(1.0 - along_2, -across_2), (1.0 - along_1, -across_1)],
+            },
+            route => route,
+        }
+    }
+}
+
+/// How the line of an edge is broken up.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Dash {
+    #[default]
+    Solid,
+    Dashed,
+    Dotted,
+}
+
+impl Dash {
+    pub const ALL: [Dash; 3] = [Dash::Solid, Dash::Dashed, Dash::Dotted];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Solid => "Solid",
+            Self::Dashed => "Dashed",
+            Self::Dotted => "Dotted",
+        }
+    }
+
+    /// Length of every drawn piece and of the gap after it.
+    pub fn pattern(self) -> Option<(f32, f32)> {
+        match self {
+            Self::Solid => None,
+            Self::Dashed => Some((12.0, 8.0)),
+            Self::Dotted => Some((3.0, 5.0)),
+        }
+    }
+}
+
+/// How an edge is drawn. The color is sRGB with alpha, from 0 to 1.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct EdgeStyle {
+    pub color: [f32; 4],
+    pub width: f32,
+    pub dash: Dash,
+}
+
+impl Default for EdgeStyle {
+    /// Same as the default edge settings.
+    fn default() -> Self {
+        Self {
+            color: [0.94, 0.97, 1.0, 1.0],
+            width: 3.5,
+            dash: Dash::Solid,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct GraphEdge {
+    pub source: usize,
+    pub target: usize,
+    pub directed: bool,
+    pub weight: Option<f32>,
+    pub label: Option<String>,
+    pub route: EdgeRoute,
+    /// Set on the edges that are not drawn with the default settings.
+    pub style: Option<EdgeStyle>,
+    pub attributes: Attributes,
+}
+
+pub fn edge_text(label: Option<&str>, weight: Option<f32>) -> Option<String> {
+    match (label, weight) {
+        (Some(label), Some(weight)) => Some(format!("{label} ({weight})")),
+        (Some(label), None) => Some(label.to_string()),
+        (None, Some(weight)) => Some(weight.to_string()),
+        (None, None) => None,
+    }
+}
+
+impl GraphEdge {
+    pub fn new(source: usize, target: usize, directed: bool) -> Self {
+        Self {
+            source,
+            target,
+            directed,
+            weight: None,
+            label: None,
+            route: EdgeRoute::Straight,
+            style: None,
+            attributes: Attributes::new(),
+        }
+    }
+}
+
+impl Graph {
+    pub fn add_node(&mut self, label: impl Into<String>, x: f32, y: f32) -> usize {
+        self.nodes.push(GraphNode {
+            label: label.into(),
+            x,
+            y,
+            style: None,
+            attributes: Attributes::new(),
+        });
+
+        self.nodes.len() - 1
+    }
+
+    pub fn add_edge(&mut self, edge: GraphEdge) -> usize {
+        self.edges.push(edge);
+
+        self.edges.len() - 1
+    }
+
+    /// Whether the nodes were given positions, rather than all being left at
+    /// the origin by a format that has none.
+    pub fn has_positions(&self) -> bool {
+        self.nodes.iter().any(|node| node.x != 0.0 || node.y != 0.0)
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.edges.iter().any(|edge| edge.directed)
+    }
+
+    pub fn has_attributes(&self) -> bool {
+        self.nodes.iter().any(|node| !node.attributes.is_empty())
+            || self.edges.iter().any(|edge| !edge.attributes.is_empty())
+    }
+
+    pub fn weight(&self, edge: usize) -> f32 {
+        self.edges[edge].weight.unwrap_or(1.0)
+    }
+
+    pub fn undirected_adjacency(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            adjacency[edge.source].push((edge.target, index));
+
+            if edge.source != edge.target {
+                adjacency[edge.target].push((edge.source, index));
+            }
+        }
+
+        adjacency
+    }
+
+    pub fn directed_adjacency(&self) -> Vec<Vec<(usize, usize)>> {
+        let mut adjacency = vec![Vec::new(); self.nodes.len()];
+
+        for (index, edge) in self.edges.iter().enumerate() {
+            adjacency[edge.source].push((edge.target, index));
+
+            if !edge.directed && edge.source != edge.target {
+                adjacency[edge.target].push((edge.source, index));
+            }
+        }
+
+        adjacency
+    }
+}
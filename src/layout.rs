@@ -0,0 +1,235 @@
+use std::{f32::consts::TAU, str::FromStr};
+
+use crate::{algorithms::planarity, graph::Graph};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+    Circular,
+    ForceDirected,
+    Grid,
+}
+
+impl Layout {
+    pub fn apply(self, graph: &mut Graph, width: f32, height: f32) {
+        match self {
+            Self::Circular => circular(graph, width, height),
+            Self::ForceDirected => force_directed(graph, width, height, 300),
+            Self::Grid => grid(graph, width, height),
+        }
+    }
+}
+
+impl FromStr for Layout {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "circular" => Ok(Self::Circular),
+            "force-directed" => Ok(Self::ForceDirected),
+            "grid" => Ok(Self::Grid),
+            _ => Err(format!(
+                "unknown layout {s:?}, expected one of circular, force-directed or grid"
+            )),
+        }
+    }
+}
+
+pub fn circular(graph: &mut Graph, width: f32, height: f32) {
+    let radius = width.min(height) / 2.0;
+    let n = graph.nodes.len() as f32;
+
+    for (index, node) in graph.nodes.iter_mut().enumerate() {
+        let angle = TAU * index as f32 / n;
+
+        node.x = radius * angle.cos();
+        node.y = radius * angle.sin();
+    }
+}
+
+pub fn grid(graph: &mut Graph, width: f32, height: f32) {
+    let n = graph.nodes.len();
+    let columns = (n as f32).sqrt().ceil().max(1.0) as usize;
+    let rows = n.div_ceil(columns);
+
+    let step_x = width / columns.saturating_sub(1).max(1) as f32;
+    let step_y = height / rows.saturating_sub(1).max(1) as f32;
+
+    for (index, node) in graph.nodes.iter_mut().enumerate() {
+        let (column, row) = (index % columns, index / columns);
+
+        node.x = if columns > 1 {
+            column as f32 * step_x - width / 2.0
+        } else {
+            0.0
+        };
+        node.y = if rows > 1 {
+            height / 2.0 - row as f32 * step_y
+        } else {
+            0.0
+        };
+    }
+}
+
+pub fn force_directed(graph: &mut Graph, width: f32, height: f32, iterations: usize) {
+    let n = graph.nodes.len();
+
+    if n == 0 {
+        return;
+    }
+
+    circular(graph, width / 2.0, height / 2.0);
+
+    let k = (width * height / n as f32).sqrt();
+    let initial_temperature = width.min(height) / 10.0;
+    let mut displacements = vec![(0.0, 0.0); n];
+
+    for iteration in 0..iterations {
+        displacements.fill((0.0, 0.0));
+
+        for i in 0..n {
+            for j in i + 1..n {
+                let (mut dx, mut dy) = (
+                    graph.nodes[i].x - graph.nodes[j].x,
+                    graph.nodes[i].y - graph.nodes[j].y,
+                );
+
+                if dx == 0.0 && dy == 0.0 {
+                    dx = (i as f32 - j as f32) * 0.01;
+                    dy = 0.01;
+                }
+
+                let distance = (dx * dx + dy * dy).sqrt();
+                let force = k * k / distance;
+
+                displacements[i].0 += dx / distance * force;
+                displacements[i].1 += dy / distance * force;
+                displacements[j].0 -= dx / distance * force;
+                displacements[j].1 -= dy / distance * force;
+            }
+        }
+
+        for edge in graph.edges.iter() {
+            let (source, target) = (edge.source, edge.target);
+
+            if source == target {
+                continue;
+            }
+
+            let (dx, dy) = (
+                graph.nodes[source].x - graph.nodes[target].x,
+                graph.nodes[source].y - graph.nodes[target].y,
+            );
+
+            let distance = (dx * dx + dy * dy).sqrt().max(0.01);
+            let force = distance * distance / k;
+
+            displacements[source].0 -= dx / distance * force;
+            displacements[source].1 -= dy / distance * force;
+            displacements[target].0 += dx / distance * force;
+            displacements[target].1 += dy / distance * force;
+        }
+
+        let temperature =
+            initial_temperature * (1.0 - iteration as f32 / iterations as f32).max(0.01);
+
+        for (node, (dx, dy)) in graph.nodes.iter_mut().zip(displacements.iter()) {
+            let length = (dx * dx + dy * dy).sqrt();
+
+            if length == 0.0 {
+                continue;
+            }
+
+            let step = length.min(temperature);
+
+            node.x = (node.x + dx / length * step).clamp(-width / 2.0, width / 2.0);
+            node.y = (node.y + dy / length * step).clamp(-height / 2.0, height / 2.0);
+        }
+    }
+}
+
+/// Places the nodes in columns from left to right, every node one column
+/// after the furthest of its predecessors. `order` has to be a topological
+/// order of the directed edges.
+pub fn layered(graph: &mut Graph, order: &[usize], width: f32, height: f32) {
+    let mut outgoing = vec![Vec::new(); graph.nodes.len()];
+    let mut layers = vec![0; graph.nodes.len()];
+
+    for edge in graph.edges.iter().filter(|edge| edge.directed) {
+        outgoing[edge.source].push(edge.target);
+    }
+
+    for &node in order {
+        for &target in outgoing[node].iter() {
+            layers[target] = layers[target].max(layers[node] + 1);
+        }
+    }
+
+    let layer_count = layers.iter().max().map_or(0, |max| max + 1);
+    let mut columns = vec![Vec::new(); layer_count];
+
+    for &node in order {
+        columns[layers[node]].push(node);
+    }
+
+    let step_x = width / layer_count.saturating_sub(1).max(1) as f32;
+
+    for (layer, column) in columns.iter().enumerate() {
+        let step_y = height / column.len().saturating_sub(1).max(1) as f32;
+
+        for (row, &node) in column.iter().enumerate() {
+            graph.nodes[node].x = if layer_count > 1 {
+                layer as f32 * step_x - width / 2.0
+            } else {
+                0.0
+            };
+            graph.nodes[node].y = if column.len() > 1 {
+                height / 2.0 - row as f32 * step_y
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+/// Places the nodes so that no two edges cross, when the graph is planar.
+/// Returns `false` and leaves the graph as it is otherwise.
+pub fn planar(graph: &mut Graph, width: f32, height: f32) -> bool {
+    let Some(positions) = planarity::straight_line_drawing(graph) else {
+        return false;
+    };
+
+    for (node, (x, y)) in graph.nodes.iter_mut().zip(positions) {
+        node.x = (x - 0.5) * width;
+        node.y = (y - 0.5) * height;
+    }
+
+    true
+}
+
+/// Centers the drawing and shrinks it, keeping its proportions, until it
+/// fits in the given size. Drawings that already fit are not enlarged.
+pub fn fit(graph: &mut Graph, width: f32, height: f32) {
+    if graph.nodes.is_empty() {
+        return;
+    }
+
+    let (mut min_x, mut min_y) = (f32::INFINITY, f32::INFINITY);
+    let (mut max_x, mut max_y) = (f32::NEG_INFINITY, f32::NEG_INFINITY);
+
+    for node in graph.nodes.iter() {
+        min_x = min_x.min(node.x);
+        min_y = min_y.min(node.y);
+        max_x = max_x.max(node.x);
+        max_y = max_y.max(node.y);
+    }
+
+    let scale = (width / (max_x - min_x))
+        .min(height / (max_y - min_y))
+        .min(1.0);
+    let (center_x, center_y) = ((min_x + max_x) / 2.0, (min_y + max_y) / 2.0);
+
+    for node in graph.nodes.iter_mut() {
+        node.x = (node.x - center_x) * scale;
+        node.y = (node.y - center_y) * scale;
+    }
+}
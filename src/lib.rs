@@ -1,6 +1,15 @@
+mod algorithms;
+mod assets;
+pub mod cli;
 mod components;
+mod formats;
+mod graph;
+mod layout;
 mod plugins;
+mod render;
 mod resources;
+mod snapshot;
+mod spatial;
 mod systems;
 mod utils;
 
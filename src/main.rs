@@ -1,12 +1,52 @@
+use std::process;
+
 use bevy::{
     prelude::*,
     window::{PresentMode, WindowResizeConstraints},
 };
-use graph_visualizer::GraphPlugins;
+use clap::Parser;
+use graph_visualizer::{
+    cli::{self, Cli, Command},
+    GraphPlugins,
+};
+
+fn exit_on_error<T>(result: Result<T, Box<dyn std::error::Error>>) -> T {
+    result.unwrap_or_else(|err| {
+        eprintln!("error: {err}");
+
+        process::exit(1);
+    })
+}
 
 fn main() {
-    App::new()
-        .insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)))
+    let collection = match Cli::parse().command {
+        None => None,
+        Some(Command::Open { file }) => Some(exit_on_error(cli::open(&file))),
+        Some(Command::Convert {
+            input,
+            output,
+            from,
+            to,
+            index,
+        }) => return exit_on_error(cli::convert(&input, &output, from, to, index)),
+        Some(Command::Render {
+            input,
+            output,
+            from,
+            layout,
+            width,
+            height,
+            index,
+        }) => {
+            return exit_on_error(cli::render(
+                &input, &output, from, layout, width, height, index,
+            ))
+        }
+    };
+
+    let mut app = App::new();
+
+    app.insert_resource(ClearColor(Color::rgb(0.1, 0.1, 0.1)))
         .add_plugins(DefaultPlugins.set(WindowPlugin {
             window: WindowDescriptor {
                 title: "Graph visualizer".to_string(),
@@ -23,6 +63,11 @@ fn main() {
             },
             ..default()
         }))
-        .add_plugins(GraphPlugins)
-        .run();
+        .add_plugins(GraphPlugins);
+
+    if let Some(collection) = collection {
+        app.insert_resource(collection);
+    }
+
+    app.run();
 }
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    centrality::{self, CentralityState},
+    ui,
+};
+
+pub struct CentralityPlugin;
+
+impl Plugin for CentralityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CentralityState>()
+            .add_system(centrality::show_centrality_window.after(ui::show_menu_bar));
+    }
+}
@@ -0,0 +1,21 @@
+use bevy::prelude::*;
+
+use crate::resources::GraphCollection;
+use crate::systems::collection::{self, ShowGraphEvent};
+
+pub struct CollectionPlugin;
+
+impl Plugin for CollectionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GraphCollection>()
+            .add_event::<ShowGraphEvent>()
+            .add_startup_system(collection::show_opened_graphs)
+            .add_system(collection::load_dropped_file)
+            .add_system(collection::page_through_graphs)
+            .add_system(
+                collection::show_graph
+                    .after(collection::load_dropped_file)
+                    .after(collection::page_through_graphs),
+            );
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    coloring::{self, ColoringState},
+    ui,
+};
+
+pub struct ColoringPlugin;
+
+impl Plugin for ColoringPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ColoringState>()
+            .add_system(coloring::show_coloring_window.after(ui::show_menu_bar))
+            .add_system(coloring::play_coloring_game);
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    communities::{self, CommunityState},
+    ui,
+};
+
+pub struct CommunitiesPlugin;
+
+impl Plugin for CommunitiesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CommunityState>()
+            .add_system(communities::show_communities_window.after(ui::show_menu_bar))
+            .add_system(communities::pull_communities);
+    }
+}
@@ -0,0 +1,19 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    connectivity::{self, ConnectivityState},
+    highlight, ui,
+};
+
+pub struct ConnectivityPlugin;
+
+impl Plugin for ConnectivityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ConnectivityState>()
+            .add_system(connectivity::show_connectivity_window.after(ui::show_menu_bar))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                connectivity::analyse_connectivity.before(highlight::apply_highlights),
+            );
+    }
+}
@@ -0,0 +1,18 @@
+use std::time::Duration;
+
+use bevy::{diagnostic::LogDiagnosticsPlugin, prelude::*};
+
+use crate::systems::diagnostics::{self, COLOR_MATERIAL_COUNT, MESH_COUNT};
+
+pub struct AssetDiagnosticsPlugin;
+
+impl Plugin for AssetDiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_startup_system(diagnostics::setup_asset_diagnostics)
+            .add_system(diagnostics::measure_asset_counts)
+            .add_plugin(LogDiagnosticsPlugin {
+                wait_duration: Duration::from_secs(10),
+                ..LogDiagnosticsPlugin::filtered(vec![MESH_COUNT, COLOR_MATERIAL_COUNT])
+            });
+    }
+}
@@ -0,0 +1,23 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    edge_editor::{self, EdgeEditorState},
+    edges, node, routing, ui,
+};
+
+pub struct EdgeEditorPlugin;
+
+impl Plugin for EdgeEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<EdgeEditorState>()
+            .add_system(edge_editor::select_edge.before(node::spawn_node))
+            .add_system(edge_editor::show_edge_editor.after(ui::show_menu_bar))
+            .add_system_to_stage(CoreStage::PostUpdate, edge_editor::stroke_edges)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                edge_editor::dash_edges
+                    .after(edges::spread_parallel_edges)
+                    .after(routing::route_edges),
+            );
+    }
+}
@@ -1,9 +1,12 @@
 use bevy::prelude::*;
 
 use crate::{
-    resources::EdgeSettings,
+    resources::{EdgeIndex, EdgeSettings},
     systems::{
-        edges::{self, CreateOrUnspawnEdgeEvent, RemoveEdgeEvent, UpdateEdgeEvent},
+        edges::{
+            self, CreateOrUnspawnEdgeEvent, DeleteEdgeEvent, GraphChangedEvent, RemoveEdgeEvent,
+            UpdateEdgeEvent,
+        },
         node,
     },
 };
@@ -13,18 +16,28 @@ pub struct EdgesPlugin;
 impl Plugin for EdgesPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<EdgeSettings>()
+            .init_resource::<EdgeIndex>()
             .add_event::<UpdateEdgeEvent>()
             .add_event::<RemoveEdgeEvent>()
             .add_event::<CreateOrUnspawnEdgeEvent>()
+            .add_event::<DeleteEdgeEvent>()
+            .add_event::<GraphChangedEvent>()
             .add_system(
                 edges::emit_create_or_unspawn_edge_event.before(node::mark_node_to_create_edge),
             )
             .add_system(edges::create_or_unspawn_edge)
+            .add_system(edges::delete_edge)
             .add_system(
                 edges::update_edge_after_moving_node
                     .after(node::fix_off_screen_node_positions)
+                    .after(node::move_nodes)
+                    .after(node::change_node_radius)
                     .after(node::emit_update_edge_event_after_node_collision),
             )
-            .add_system(edges::remove_edge_after_remove_node);
+            .add_system(edges::remove_edge_after_remove_node)
+            .add_system(edges::toggle_directed_edges)
+            .add_system(edges::toggle_multigraph)
+            .add_system(edges::change_edge_color)
+            .add_system_to_stage(CoreStage::PostUpdate, edges::spread_parallel_edges);
     }
 }
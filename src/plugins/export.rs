@@ -0,0 +1,11 @@
+use bevy::prelude::*;
+
+use crate::systems::export;
+
+pub struct ExportPlugin;
+
+impl Plugin for ExportPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(export::export_graph);
+    }
+}
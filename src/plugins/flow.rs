@@ -0,0 +1,18 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    flow::{self, FlowState},
+    node, ui,
+};
+
+pub struct FlowPlugin;
+
+impl Plugin for FlowPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FlowState>().add_system(
+            flow::show_flow_window
+                .after(ui::show_menu_bar)
+                .after(node::pick_node),
+        );
+    }
+}
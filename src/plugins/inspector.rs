@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    inspector::{self, InspectorState},
+    ui,
+};
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InspectorState>()
+            .add_system(inspector::show_inspector_window.after(ui::show_menu_bar));
+    }
+}
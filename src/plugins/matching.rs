@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    matching::{self, MatchingState},
+    ui,
+};
+
+pub struct MatchingPlugin;
+
+impl Plugin for MatchingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MatchingState>()
+            .add_system(matching::show_matching_window.after(ui::show_menu_bar));
+    }
+}
@@ -1,8 +1,30 @@
 use bevy::{app::PluginGroupBuilder, prelude::*};
 
+mod centrality;
+mod collection;
+mod coloring;
+mod communities;
+mod connectivity;
+mod diagnostics;
+mod edge_editor;
 mod edges;
+mod export;
+mod flow;
+mod inspector;
+mod matching;
+mod mst;
 mod node;
+mod node_style;
+mod planarity;
+mod routing;
 mod setup;
+mod shortest_paths;
+mod statistics;
+mod style_rules;
+mod topological;
+mod tours;
+mod transformations;
+mod ui;
 mod window;
 
 pub struct GraphPlugins;
@@ -14,5 +36,27 @@ impl PluginGroup for GraphPlugins {
             .add(window::WindowPlugin)
             .add(node::NodePlugin)
             .add(edges::EdgesPlugin)
+            .add(export::ExportPlugin)
+            .add(collection::CollectionPlugin)
+            .add(diagnostics::AssetDiagnosticsPlugin)
+            .add(ui::UiPlugin)
+            .add(mst::MstPlugin)
+            .add(connectivity::ConnectivityPlugin)
+            .add(topological::TopologicalPlugin)
+            .add(coloring::ColoringPlugin)
+            .add(flow::FlowPlugin)
+            .add(matching::MatchingPlugin)
+            .add(tours::ToursPlugin)
+            .add(shortest_paths::ShortestPathsPlugin)
+            .add(centrality::CentralityPlugin)
+            .add(communities::CommunitiesPlugin)
+            .add(planarity::PlanarityPlugin)
+            .add(statistics::StatisticsPlugin)
+            .add(transformations::TransformationsPlugin)
+            .add(routing::RoutingPlugin)
+            .add(edge_editor::EdgeEditorPlugin)
+            .add(node_style::NodeStylePlugin)
+            .add(inspector::InspectorPlugin)
+            .add(style_rules::StyleRulesPlugin)
     }
 }
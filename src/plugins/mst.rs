@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    mst::{self, MstState},
+    ui,
+};
+
+pub struct MstPlugin;
+
+impl Plugin for MstPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<MstState>()
+            .add_system(mst::show_mst_window.after(ui::show_menu_bar));
+    }
+}
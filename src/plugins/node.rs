@@ -1,14 +1,22 @@
 use bevy::prelude::*;
 
-use crate::resources::NodeSettings;
-use crate::systems::node::{self, ChangeNodeColorEvent};
+use crate::assets::NodeAssetCache;
+use crate::resources::{NodeIndex, NodeSettings};
+use crate::systems::node::{
+    self, ChangeNodeColorEvent, ChangeNodeRadiusEvent, MoveNodesEvent, NodePickedEvent,
+};
 
 pub struct NodePlugin;
 
 impl Plugin for NodePlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<NodeSettings>()
+            .init_resource::<NodeIndex>()
+            .init_resource::<NodeAssetCache>()
             .add_event::<ChangeNodeColorEvent>()
+            .add_event::<ChangeNodeRadiusEvent>()
+            .add_event::<MoveNodesEvent>()
+            .add_event::<NodePickedEvent>()
             .add_system(node::spawn_node)
             .add_system(node::remove_node)
             .add_system(node::mark_node_to_move)
@@ -16,11 +24,20 @@ impl Plugin for NodePlugin {
             .add_system(node::unmark_node_that_was_moving)
             .add_system(node::mark_node_to_create_edge)
             .add_system(node::change_node_color)
+            .add_system(node::change_node_radius)
+            .add_system(node::move_nodes)
+            .add_system(node::pick_node.after(node::spawn_node))
             .add_system(
                 node::fix_off_screen_node_positions
                     .after(node::spawn_node)
-                    .after(node::move_node),
+                    .after(node::move_node)
+                    .after(node::move_nodes),
             )
-            .add_system(node::emit_update_edge_event_after_node_collision);
+            .add_system(node::emit_update_edge_event_after_node_collision)
+            .add_system_to_stage(CoreStage::PostUpdate, node::restyle_nodes)
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                node::update_node_index.after(node::restyle_nodes),
+            );
     }
 }
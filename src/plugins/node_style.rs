@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    node_style::{self, NodeStyleState},
+    ui,
+};
+
+pub struct NodeStylePlugin;
+
+impl Plugin for NodeStylePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NodeStyleState>()
+            .add_system(node_style::show_node_style_window.after(ui::show_menu_bar));
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    planarity::{self, PlanarityState},
+    ui,
+};
+
+pub struct PlanarityPlugin;
+
+impl Plugin for PlanarityPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<PlanarityState>()
+            .add_system(planarity::show_planarity_window.after(ui::show_menu_bar));
+    }
+}
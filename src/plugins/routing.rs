@@ -0,0 +1,22 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    edges, node,
+    routing::{self, RoutingState},
+    ui,
+};
+
+pub struct RoutingPlugin;
+
+impl Plugin for RoutingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RoutingState>()
+            .add_system(routing::show_routing_window.after(ui::show_menu_bar))
+            .add_system_to_stage(
+                CoreStage::PostUpdate,
+                routing::route_edges
+                    .after(edges::spread_parallel_edges)
+                    .after(node::update_node_index),
+            );
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    shortest_paths::{self, ShortestPathsState},
+    ui,
+};
+
+pub struct ShortestPathsPlugin;
+
+impl Plugin for ShortestPathsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ShortestPathsState>()
+            .add_system(shortest_paths::show_shortest_paths_window.after(ui::show_menu_bar));
+    }
+}
@@ -0,0 +1,16 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    statistics::{self, GraphStatistics},
+    ui,
+};
+
+pub struct StatisticsPlugin;
+
+impl Plugin for StatisticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GraphStatistics>()
+            .add_system(statistics::show_statistics_panel.after(ui::show_menu_bar))
+            .add_system_to_stage(CoreStage::PostUpdate, statistics::update_statistics);
+    }
+}
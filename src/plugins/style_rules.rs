@@ -0,0 +1,17 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    style_rules::{self, StyleRules, StyleRulesState},
+    ui,
+};
+
+pub struct StyleRulesPlugin;
+
+impl Plugin for StyleRulesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<StyleRules>()
+            .init_resource::<StyleRulesState>()
+            .add_system(style_rules::show_style_rules_window.after(ui::show_menu_bar))
+            .add_system(style_rules::apply_style_rules);
+    }
+}
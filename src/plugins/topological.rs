@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    topological::{self, TopologicalState},
+    ui,
+};
+
+pub struct TopologicalPlugin;
+
+impl Plugin for TopologicalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TopologicalState>()
+            .add_system(topological::show_topological_window.after(ui::show_menu_bar));
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    tours::{self, ToursState},
+    ui,
+};
+
+pub struct ToursPlugin;
+
+impl Plugin for ToursPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ToursState>()
+            .add_system(tours::show_tours_window.after(ui::show_menu_bar));
+    }
+}
@@ -0,0 +1,15 @@
+use bevy::prelude::*;
+
+use crate::systems::{
+    transformations::{self, TransformationsState},
+    ui,
+};
+
+pub struct TransformationsPlugin;
+
+impl Plugin for TransformationsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TransformationsState>()
+            .add_system(transformations::show_transformations_window.after(ui::show_menu_bar));
+    }
+}
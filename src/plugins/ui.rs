@@ -0,0 +1,26 @@
+use bevy::prelude::*;
+use bevy_egui::{EguiPlugin, EguiSystem};
+
+use crate::{
+    resources::{HighlightSettings, Highlights, UiState},
+    systems::{edges::ChangeEdgeColorEvent, highlight, ui},
+};
+
+pub struct UiPlugin;
+
+impl Plugin for UiPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugin(EguiPlugin)
+            .init_resource::<UiState>()
+            .init_resource::<Highlights>()
+            .init_resource::<HighlightSettings>()
+            .add_event::<ChangeEdgeColorEvent>()
+            .add_system_to_stage(
+                CoreStage::PreUpdate,
+                ui::update_pointer_state.after(EguiSystem::BeginFrame),
+            )
+            .add_system(ui::show_menu_bar)
+            .add_system(ui::draw_labels)
+            .add_system_to_stage(CoreStage::PostUpdate, highlight::apply_highlights);
+    }
+}
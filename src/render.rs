@@ -0,0 +1,266 @@
+use std::fmt::Write;
+
+use image::{Rgb, RgbImage};
+
+use crate::graph::Graph;
+
+pub struct RenderStyle {
+    pub background: [f32; 3],
+    pub node_color: [f32; 3],
+    pub edge_color: [f32; 3],
+    pub node_radius: f32,
+    pub edge_width: f32,
+}
+
+enum Shape {
+    Segment([f32; 2], [f32; 2]),
+    Loop([f32; 2]),
+}
+
+fn edge_shapes(graph: &Graph, width: u32, height: u32, radius: f32) -> Vec<Shape> {
+    let to_image = |x: f32, y: f32| [x + width as f32 / 2.0, height as f32 / 2.0 - y];
+
+    let mut shapes = Vec::new();
+
+    for edge in graph.edges.iter() {
+        let (source, target) = (&graph.nodes[edge.source], &graph.nodes[edge.target]);
+
+        if edge.source == edge.target {
+            shapes.push(Shape::Loop(to_image(source.x, source.y + radius)));
+
+            continue;
+        }
+
+        shapes.push(Shape::Segment(
+            to_image(source.x, source.y),
+            to_image(target.x, target.y),
+        ));
+
+        let (dx, dy) = (target.x - source.x, target.y - source.y);
+        let length = (dx * dx + dy * dy).sqrt();
+
+        if !edge.directed || length == 0.0 {
+            continue;
+        }
+
+        let (dx, dy) = (dx / length, dy / length);
+        let tip = (target.x - dx * radius, target.y - dy * radius);
+        let back = (tip.0 - dx * radius, tip.1 - dy * radius);
+        let side = (-dy * radius / 2.0, dx * radius / 2.0);
+
+        for sign in [1.0, -1.0] {
+            shapes.push(Shape::Segment(
+                to_image(back.0 + side.0 * sign, back.1 + side.1 * sign),
+                to_image(tip.0, tip.1),
+            ));
+        }
+    }
+
+    shapes
+}
+
+fn hex(color: [f32; 3]) -> String {
+    color.iter().fold(String::from("#"), |mut output, channel| {
+        write!(
+            output,
+            "{:02x}",
+            (channel.clamp(0.0, 1.0) * 255.0).round() as u8
+        )
+        .unwrap();
+
+        output
+    })
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+pub fn svg(graph: &Graph, width: u32, height: u32, style: &RenderStyle) -> String {
+    let radius = style.node_radius;
+    let to_image = |x: f32, y: f32| (x + width as f32 / 2.0, height as f32 / 2.0 - y);
+
+    let mut output = String::new();
+
+    writeln!(
+        output,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{width}" height="{height}" viewBox="0 0 {width} {height}">"#
+    )
+    .unwrap();
+    writeln!(
+        output,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        hex(style.background)
+    )
+    .unwrap();
+    writeln!(
+        output,
+        r#"<g stroke="{}" stroke-width="{}" stroke-linecap="round" fill="none">"#,
+        hex(style.edge_color),
+        style.edge_width
+    )
+    .unwrap();
+
+    for shape in edge_shapes(graph, width, height, radius) {
+        match shape {
+            Shape::Segment([x1, y1], [x2, y2]) => {
+                writeln!(output, r#"<line x1="{x1}" y1="{y1}" x2="{x2}" y2="{y2}"/>"#)
+            }
+            Shape::Loop([cx, cy]) => {
+                writeln!(output, r#"<circle cx="{cx}" cy="{cy}" r="{radius}"/>"#)
+            }
+        }
+        .unwrap();
+    }
+
+    writeln!(output, "</g>").unwrap();
+    writeln!(output, r#"<g fill="{}">"#, hex(style.node_color)).unwrap();
+
+    for node in graph.nodes.iter() {
+        let (cx, cy) = to_image(node.x, node.y);
+
+        writeln!(output, r#"<circle cx="{cx}" cy="{cy}" r="{radius}"/>"#).unwrap();
+    }
+
+    writeln!(output, "</g>").unwrap();
+    writeln!(
+        output,
+        r#"<g font-family="sans-serif" font-size="{}" text-anchor="middle" dominant-baseline="central">"#,
+        radius
+    )
+    .unwrap();
+
+    for node in graph.nodes.iter() {
+        let (x, y) = to_image(node.x, node.y);
+
+        writeln!(
+            output,
+            r#"<text x="{x}" y="{y}" fill="{}">{}</text>"#,
+            hex(style.background),
+            escape(&node.label)
+        )
+        .unwrap();
+    }
+
+    for edge in graph.edges.iter() {
+        let Some(text) = crate::formats::edge_text(edge) else {
+            continue;
+        };
+
+        let (source, target) = (&graph.nodes[edge.source], &graph.nodes[edge.target]);
+        let (x, y) = to_image(
+            (source.x + target.x) / 2.0,
+            (source.y + target.y) / 2.0 + radius,
+        );
+
+        writeln!(
+            output,
+            r#"<text x="{x}" y="{y}" fill="{}">{}</text>"#,
+            hex(style.edge_color),
+            escape(&text)
+        )
+        .unwrap();
+    }
+
+    writeln!(output, "</g>").unwrap();
+    writeln!(output, "</svg>").unwrap();
+
+    output
+}
+
+fn blend(image: &mut RgbImage, x: i64, y: i64, color: [f32; 3], coverage: f32) {
+    if coverage <= 0.0 || x < 0 || y < 0 || x >= image.width() as i64 || y >= image.height() as i64
+    {
+        return;
+    }
+
+    let pixel = image.get_pixel_mut(x as u32, y as u32);
+
+    for (channel, value) in pixel.0.iter_mut().zip(color) {
+        let blended = value * 255.0 * coverage + *channel as f32 * (1.0 - coverage);
+
+        *channel = blended.round().clamp(0.0, 255.0) as u8;
+    }
+}
+
+fn draw(
+    image: &mut RgbImage,
+    (min, max): ([f32; 2], [f32; 2]),
+    color: [f32; 3],
+    distance: impl Fn(f32, f32) -> f32,
+) {
+    for y in min[1].floor() as i64..=max[1].ceil() as i64 {
+        for x in min[0].floor() as i64..=max[0].ceil() as i64 {
+            let coverage = (0.5 - distance(x as f32 + 0.5, y as f32 + 0.5)).clamp(0.0, 1.0);
+
+            blend(image, x, y, color, coverage);
+        }
+    }
+}
+
+fn segment_distance([x1, y1]: [f32; 2], [x2, y2]: [f32; 2], x: f32, y: f32) -> f32 {
+    let (dx, dy) = (x2 - x1, y2 - y1);
+    let length_squared = dx * dx + dy * dy;
+
+    let t = if length_squared == 0.0 {
+        0.0
+    } else {
+        (((x - x1) * dx + (y - y1) * dy) / length_squared).clamp(0.0, 1.0)
+    };
+
+    ((x - x1 - t * dx).powi(2) + (y - y1 - t * dy).powi(2)).sqrt()
+}
+
+pub fn png(graph: &Graph, width: u32, height: u32, style: &RenderStyle) -> RgbImage {
+    let background = style
+        .background
+        .map(|channel| (channel * 255.0).round() as u8);
+    let mut image = RgbImage::from_pixel(width, height, Rgb(background));
+
+    let radius = style.node_radius;
+    let half_width = style.edge_width / 2.0;
+
+    for shape in edge_shapes(graph, width, height, radius) {
+        match shape {
+            Shape::Segment(start, end) => {
+                let bounds = (
+                    [
+                        start[0].min(end[0]) - half_width - 1.0,
+                        start[1].min(end[1]) - half_width - 1.0,
+                    ],
+                    [
+                        start[0].max(end[0]) + half_width + 1.0,
+                        start[1].max(end[1]) + half_width + 1.0,
+                    ],
+                );
+
+                draw(&mut image, bounds, style.edge_color, |x, y| {
+                    segment_distance(start, end, x, y) - half_width
+                });
+            }
+            Shape::Loop([cx, cy]) => {
+                let extent = radius + half_width + 1.0;
+                let bounds = ([cx - extent, cy - extent], [cx + extent, cy + extent]);
+
+                draw(&mut image, bounds, style.edge_color, |x, y| {
+                    (((x - cx).powi(2) + (y - cy).powi(2)).sqrt() - radius).abs() - half_width
+                });
+            }
+        }
+    }
+
+    for node in graph.nodes.iter() {
+        let (cx, cy) = (node.x + width as f32 / 2.0, height as f32 / 2.0 - node.y);
+        let extent = radius + 1.0;
+        let bounds = ([cx - extent, cy - extent], [cx + extent, cy + extent]);
+
+        draw(&mut image, bounds, style.node_color, |x, y| {
+            ((x - cx).powi(2) + (y - cy).powi(2)).sqrt() - radius
+        });
+    }
+
+    image
+}
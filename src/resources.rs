@@ -1,8 +1,36 @@
-use bevy::prelude::{Color, Resource};
+use bevy::{
+    prelude::{Color, Deref, DerefMut, Entity, Resource},
+    utils::{HashMap, HashSet},
+};
+
+use crate::{
+    graph::{EdgeRoute, Graph},
+    spatial::SpatialIndex,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum InteractionMode {
+    #[default]
+    Edit,
+    ColoringGame,
+    PickNode,
+}
 
 #[derive(Resource, Default)]
 pub struct VisualizerState {
     pub is_moving_node: bool,
+    pub is_pointer_over_ui: bool,
+    pub is_typing: bool,
+    pub interaction_mode: InteractionMode,
+}
+
+impl VisualizerState {
+    /// Whether a click on the canvas should edit the graph.
+    pub fn can_edit(&self) -> bool {
+        !self.is_moving_node
+            && !self.is_pointer_over_ui
+            && self.interaction_mode == InteractionMode::Edit
+    }
 }
 
 #[derive(Resource)]
@@ -27,14 +55,300 @@ impl Default for NodeSettings {
 #[derive(Resource)]
 pub struct EdgeSettings {
     pub color: Color,
+    pub selected_color: Color,
     pub size: f32,
+    pub directed: bool,
+    /// Connecting two nodes again adds a parallel edge instead of removing
+    /// the edge between them, and a node can be connected to itself.
+    pub multigraph: bool,
+    /// How new edges are drawn.
+    pub route: EdgeRoute,
 }
 
 impl Default for EdgeSettings {
     fn default() -> Self {
         Self {
             color: Color::ALICE_BLUE,
+            selected_color: Color::CYAN,
             size: 3.5,
+            directed: false,
+            multigraph: false,
+            route: EdgeRoute::Straight,
+        }
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct GraphCollection {
+    pub graphs: Vec<Graph>,
+    pub current: usize,
+}
+
+#[derive(Resource, Deref, DerefMut)]
+pub struct NodeIndex(pub SpatialIndex<Entity>);
+
+impl Default for NodeIndex {
+    fn default() -> Self {
+        Self(SpatialIndex::new(64.0))
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct EdgeIndex {
+    endpoints: HashMap<Entity, (Entity, Entity)>,
+    incident: HashMap<Entity, HashSet<Entity>>,
+}
+
+impl EdgeIndex {
+    pub fn insert(&mut self, edge: Entity, v: Entity, u: Entity) {
+        self.endpoints.insert(edge, (v, u));
+        self.incident.entry(v).or_default().insert(edge);
+        self.incident.entry(u).or_default().insert(edge);
+    }
+
+    pub fn remove(&mut self, edge: Entity) {
+        let Some((v, u)) = self.endpoints.remove(&edge) else {
+            return;
+        };
+
+        for node in [v, u] {
+            if let Some(edges) = self.incident.get_mut(&node) {
+                edges.remove(&edge);
+
+                if edges.is_empty() {
+                    self.incident.remove(&node);
+                }
+            }
+        }
+    }
+
+    pub fn remove_node(&mut self, node: Entity) -> Vec<Entity> {
+        let edges = self
+            .incident
+            .remove(&node)
+            .map(|edges| edges.into_iter().collect::<Vec<_>>())
+            .unwrap_or_default();
+
+        for edge in edges.iter() {
+            self.remove(*edge);
+        }
+
+        edges
+    }
+
+    pub fn incident_edges(&self, node: Entity) -> impl Iterator<Item = Entity> + '_ {
+        self.incident.get(&node).into_iter().flatten().copied()
+    }
+
+    /// An edge between the two nodes, in either direction.
+    pub fn edge_between(&self, v: Entity, u: Entity) -> Option<Entity> {
+        self.incident_edges(v).find(|edge| {
+            matches!(self.endpoints.get(edge), Some(&ends) if ends == (v, u) || ends == (u, v))
+        })
+    }
+
+    pub fn clear(&mut self) {
+        self.endpoints.clear();
+        self.incident.clear();
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Tool {
+    MinimumSpanningTree,
+    Connectivity,
+    TopologicalSort,
+    Coloring,
+    MaximumFlow,
+    Matching,
+    Tours,
+    ShortestPaths,
+    Centrality,
+    Communities,
+    Planarity,
+    Transformations,
+    Routing,
+    NodeStyle,
+    Inspector,
+    StyleRules,
+}
+
+impl Tool {
+    pub const ALL: [Tool; 16] = [
+        Tool::MinimumSpanningTree,
+        Tool::Connectivity,
+        Tool::TopologicalSort,
+        Tool::Coloring,
+        Tool::MaximumFlow,
+        Tool::Matching,
+        Tool::Tours,
+        Tool::ShortestPaths,
+        Tool::Centrality,
+        Tool::Communities,
+        Tool::Planarity,
+        Tool::Transformations,
+        Tool::Routing,
+        Tool::NodeStyle,
+        Tool::Inspector,
+        Tool::StyleRules,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::MinimumSpanningTree => "Minimum spanning tree",
+            Self::Connectivity => "Connectivity",
+            Self::TopologicalSort => "Topological sort",
+            Self::Coloring => "Coloring",
+            Self::MaximumFlow => "Maximum flow",
+            Self::Matching => "Matching",
+            Self::Tours => "Eulerian and Hamiltonian",
+            Self::ShortestPaths => "Shortest paths",
+            Self::Centrality => "Centrality",
+            Self::Communities => "Communities",
+            Self::Planarity => "Planarity",
+            Self::Transformations => "Transformations",
+            Self::Routing => "Edge routing",
+            Self::NodeStyle => "Node style",
+            Self::Inspector => "Inspector",
+            Self::StyleRules => "Style rules",
         }
     }
 }
+
+#[derive(Resource)]
+pub struct UiState {
+    pub open_tools: HashSet<Tool>,
+    pub show_labels: bool,
+    pub show_statistics: bool,
+}
+
+impl Default for UiState {
+    fn default() -> Self {
+        Self {
+            open_tools: HashSet::new(),
+            show_labels: true,
+            show_statistics: true,
+        }
+    }
+}
+
+impl UiState {
+    pub fn is_open(&self, tool: Tool) -> bool {
+        self.open_tools.contains(&tool)
+    }
+
+    pub fn set_open(&mut self, tool: Tool, open: bool) {
+        if open {
+            self.open_tools.insert(tool);
+        } else {
+            self.open_tools.remove(&tool);
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct HighlightSettings {
+    pub accepted_color: Color,
+    pub rejected_color: Color,
+    pub current_color: Color,
+    pub critical_color: Color,
+}
+
+impl Default for HighlightSettings {
+    fn default() -> Self {
+        Self {
+            accepted_color: Color::LIME_GREEN,
+            rejected_color: Color::CRIMSON,
+            current_color: Color::GOLD,
+            critical_color: Color::ORANGE_RED,
+        }
+    }
+}
+
+/// The highlights set by one tool.
+#[derive(Clone, Default)]
+pub struct HighlightLayer {
+    pub nodes: HashMap<Entity, Color>,
+    pub edges: HashMap<Entity, Color>,
+    pub edge_labels: HashMap<Entity, String>,
+    pub node_radii: HashMap<Entity, f32>,
+}
+
+impl HighlightLayer {
+    pub fn clear(&mut self) {
+        self.nodes.clear();
+        self.edges.clear();
+        self.edge_labels.clear();
+        self.node_radii.clear();
+    }
+}
+
+/// The highlights of every tool, kept apart so that a tool only replaces or
+/// clears its own. Where two tools highlight the same node or edge, the one
+/// that set its highlights last wins.
+#[derive(Resource, Default)]
+pub struct Highlights {
+    /// Ordered from the oldest to the latest.
+    layers: Vec<(Tool, HighlightLayer)>,
+}
+
+impl Highlights {
+    /// The highlights of a tool, which are moved on top of the others.
+    pub fn layer(&mut self, tool: Tool) -> &mut HighlightLayer {
+        let layer = match self.layers.iter().position(|&(other, _)| other == tool) {
+            Some(index) => self.layers.remove(index).1,
+            None => HighlightLayer::default(),
+        };
+
+        self.layers.push((tool, layer));
+
+        &mut self.layers.last_mut().unwrap().1
+    }
+
+    pub fn clear(&mut self, tool: Tool) {
+        self.layers.retain(|&(other, _)| other != tool);
+    }
+
+    pub fn node_color(&self, entity: Entity) -> Option<Color> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(_, layer)| layer.nodes.get(&entity).copied())
+    }
+
+    pub fn edge_color(&self, entity: Entity) -> Option<Color> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(_, layer)| layer.edges.get(&entity).copied())
+    }
+
+    pub fn edge_label(&self, entity: Entity) -> Option<&String> {
+        self.layers
+            .iter()
+            .rev()
+            .find_map(|(_, layer)| layer.edge_labels.get(&entity))
+    }
+
+    /// The highlights of all tools in one layer.
+    pub fn merged(&self) -> HighlightLayer {
+        let mut merged = HighlightLayer::default();
+
+        for (_, layer) in self.layers.iter() {
+            merged
+                .nodes
+                .extend(layer.nodes.iter().map(|(&k, &v)| (k, v)));
+            merged
+                .edges
+                .extend(layer.edges.iter().map(|(&k, &v)| (k, v)));
+            merged
+                .edge_labels
+                .extend(layer.edge_labels.iter().map(|(&k, v)| (k, v.clone())));
+            merged
+                .node_radii
+                .extend(layer.node_radii.iter().map(|(&k, &v)| (k, v)));
+        }
+
+        merged
+    }
+}
@@ -0,0 +1,132 @@
+use bevy::{ecs::system::SystemParam, prelude::*, utils::HashMap};
+
+use crate::{
+    components::{
+        Attributes, Directed, EdgeRuleStyle, EdgeStyle, Label, NeighborNodes, Node, NodeRuleStyle,
+        NodeStyle, Route, Weight,
+    },
+    graph::{EdgeRoute, Graph, GraphEdge},
+};
+
+pub struct GraphSnapshot {
+    pub graph: Graph,
+    pub nodes: Vec<Entity>,
+    pub edges: Vec<Entity>,
+}
+
+#[derive(SystemParam)]
+pub struct GraphQuery<'w, 's> {
+    nodes: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static Transform,
+            &'static Label,
+            Option<&'static NodeStyle>,
+            Option<&'static NodeRuleStyle>,
+            Option<&'static Attributes>,
+        ),
+        With<Node>,
+    >,
+    edges: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static NeighborNodes,
+            Option<&'static Label>,
+            Option<&'static Weight>,
+            Option<&'static Directed>,
+            Option<&'static Route>,
+            Option<&'static EdgeStyle>,
+            Option<&'static EdgeRuleStyle>,
+            Option<&'static Attributes>,
+        ),
+    >,
+}
+
+impl<'w, 's> GraphQuery<'w, 's> {
+    /// The graph as it is on the canvas. Nodes and edges styled by the
+    /// style rules keep the style they had before the rules, which are not
+    /// part of the graph.
+    pub fn snapshot(&self) -> GraphSnapshot {
+        let mut graph = Graph::default();
+        let mut nodes = Vec::new();
+        let mut edges = Vec::new();
+        let mut indices = HashMap::new();
+
+        for (entity, transform, label, style, rule_style, attributes) in self.nodes.iter() {
+            let index = graph.add_node(
+                label.0.clone(),
+                transform.translation.x,
+                transform.translation.y,
+            );
+
+            let style = style.map(|style| &style.0);
+
+            graph.nodes[index].style = match rule_style {
+                Some(rule_style) if style == Some(&rule_style.applied) => rule_style.before.clone(),
+                _ => style.cloned(),
+            };
+
+            if let Some(attributes) = attributes {
+                graph.nodes[index].attributes = attributes.0.clone();
+            }
+
+            indices.insert(entity, index);
+            nodes.push(entity);
+        }
+
+        for (
+            entity,
+            neighbor_nodes,
+            label,
+            weight,
+            directed,
+            route,
+            style,
+            rule_style,
+            attributes,
+        ) in self.edges.iter()
+        {
+            let (Some(&source), Some(&target)) = (
+                indices.get(&neighbor_nodes.v),
+                indices.get(&neighbor_nodes.u),
+            ) else {
+                continue;
+            };
+
+            graph.add_edge(GraphEdge {
+                source,
+                target,
+                directed: directed.is_some(),
+                weight: weight.map(|weight| weight.0),
+                label: label.map(|label| label.0.clone()),
+                route: route.map_or(EdgeRoute::Straight, |route| route.0),
+                style: match rule_style {
+                    Some(rule_style) if style == Some(&rule_style.applied) => rule_style.before,
+                    _ => style.copied(),
+                }
+                .map(EdgeStyle::to_graph),
+                attributes: attributes
+                    .map_or_else(Default::default, |attributes| attributes.0.clone()),
+            });
+
+            edges.push(entity);
+        }
+
+        GraphSnapshot {
+            graph,
+            nodes,
+            edges,
+        }
+    }
+
+    /// Whether some edge is directed, without taking a snapshot.
+    pub fn is_directed(&self) -> bool {
+        self.edges
+            .iter()
+            .any(|(_, _, _, _, directed, ..)| directed.is_some())
+    }
+}
@@ -0,0 +1,114 @@
+use std::{collections::HashMap, hash::Hash};
+
+use crate::graph::NodeShape;
+
+pub struct SpatialIndex<T> {
+    cell_size: f32,
+    cells: HashMap<(i32, i32), Vec<T>>,
+    positions: HashMap<T, (f32, f32, f32, NodeShape)>,
+    max_radius: f32,
+}
+
+impl<T: Copy + Eq + Hash> SpatialIndex<T> {
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            positions: HashMap::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    fn cell(&self, x: f32, y: f32) -> (i32, i32) {
+        (
+            (x / self.cell_size).floor() as i32,
+            (y / self.cell_size).floor() as i32,
+        )
+    }
+
+    /// Adds or moves a shape fitting in the circle of the given radius.
+    pub fn insert(&mut self, item: T, x: f32, y: f32, radius: f32, shape: NodeShape) {
+        let cell = self.cell(x, y);
+
+        // The largest radius ever seen bounds the cells to look at.
+        self.max_radius = self.max_radius.max(radius);
+
+        if let Some((old_x, old_y, ..)) = self.positions.insert(item, (x, y, radius, shape)) {
+            let old_cell = self.cell(old_x, old_y);
+
+            if old_cell == cell {
+                return;
+            }
+
+            self.remove_from_cell(item, old_cell);
+        }
+
+        self.cells.entry(cell).or_default().push(item);
+    }
+
+    pub fn remove(&mut self, item: T) {
+        if let Some((x, y, ..)) = self.positions.remove(&item) {
+            self.remove_from_cell(item, self.cell(x, y));
+        }
+    }
+
+    fn remove_from_cell(&mut self, item: T, cell: (i32, i32)) {
+        let Some(items) = self.cells.get_mut(&cell) else {
+            return;
+        };
+
+        items.retain(|other| *other != item);
+
+        if items.is_empty() {
+            self.cells.remove(&cell);
+        }
+    }
+
+    /// The shapes that may reach into the rectangle from `min` to `max`,
+    /// with their center, radius and shape.
+    pub fn items_in(
+        &self,
+        min: (f32, f32),
+        max: (f32, f32),
+    ) -> impl Iterator<Item = (T, (f32, f32, f32, NodeShape))> + '_ {
+        let (min_column, min_row) = self.cell(min.0 - self.max_radius, min.1 - self.max_radius);
+        let (max_column, max_row) = self.cell(max.0 + self.max_radius, max.1 + self.max_radius);
+
+        (min_column..=max_column)
+            .flat_map(move |column| (min_row..=max_row).map(move |row| (column, row)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|item| (*item, self.positions[item]))
+    }
+
+    /// The shape containing the point whose center is the closest to it.
+    pub fn item_at(&self, x: f32, y: f32) -> Option<T> {
+        let (min_column, min_row) = self.cell(x - self.max_radius, y - self.max_radius);
+        let (max_column, max_row) = self.cell(x + self.max_radius, y + self.max_radius);
+
+        let mut nearest = None;
+        let mut nearest_distance = f32::INFINITY;
+
+        for column in min_column..=max_column {
+            for row in min_row..=max_row {
+                let Some(items) = self.cells.get(&(column, row)) else {
+                    continue;
+                };
+
+                for item in items {
+                    let (item_x, item_y, radius, shape) = self.positions[item];
+                    let distance = ((x - item_x).powi(2) + (y - item_y).powi(2)).sqrt();
+
+                    if shape.contains(x - item_x, y - item_y, radius)
+                        && distance <= nearest_distance
+                    {
+                        nearest = Some(*item);
+                        nearest_distance = distance;
+                    }
+                }
+            }
+        }
+
+        nearest
+    }
+}
@@ -0,0 +1,75 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc,
+    },
+    thread::{self, JoinHandle},
+    time::{Duration, Instant},
+};
+
+/// Work running on its own thread, shared by the windows whose algorithms
+/// can take longer than a frame. The work counts its progress and checks
+/// whether it was cancelled. Dropping the task cancels it: the thread stops
+/// soon after and its result is dropped.
+pub struct BackgroundTask<T> {
+    started: Instant,
+    progress: Arc<AtomicU64>,
+    cancel: Arc<AtomicBool>,
+    /// Taken once the result is returned.
+    handle: Option<JoinHandle<T>>,
+}
+
+impl<T: Send + 'static> BackgroundTask<T> {
+    pub fn start(work: impl FnOnce(&AtomicU64, &AtomicBool) -> T + Send + 'static) -> Self {
+        let progress = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+
+        let handle = {
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+
+            thread::spawn(move || work(&progress, &cancel))
+        };
+
+        Self {
+            started: Instant::now(),
+            progress,
+            cancel,
+            handle: Some(handle),
+        }
+    }
+}
+
+impl<T> BackgroundTask<T> {
+    /// The result, once the thread is done. It is only returned once, and
+    /// is an error when the work panicked.
+    pub fn poll(&mut self) -> Option<thread::Result<T>> {
+        if !self.handle.as_ref()?.is_finished() {
+            return None;
+        }
+
+        self.handle.take().map(JoinHandle::join)
+    }
+
+    pub fn progress(&self) -> u64 {
+        self.progress.load(Ordering::Relaxed)
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn cancel(&self) {
+        self.cancel.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+}
+
+impl<T> Drop for BackgroundTask<T> {
+    fn drop(&mut self) {
+        self.cancel();
+    }
+}
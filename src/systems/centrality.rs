@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::centrality,
+    graph::Graph,
+    resources::{HighlightLayer, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CentralityMetric {
+    Degree,
+    Closeness,
+    Betweenness,
+    Eigenvector,
+    PageRank,
+}
+
+impl CentralityMetric {
+    const ALL: [CentralityMetric; 5] = [
+        CentralityMetric::Degree,
+        CentralityMetric::Closeness,
+        CentralityMetric::Betweenness,
+        CentralityMetric::Eigenvector,
+        CentralityMetric::PageRank,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Degree => "Degree",
+            Self::Closeness => "Closeness",
+            Self::Betweenness => "Betweenness",
+            Self::Eigenvector => "Eigenvector",
+            Self::PageRank => "PageRank",
+        }
+    }
+
+    fn compute(self, graph: &Graph) -> Vec<f32> {
+        match self {
+            Self::Degree => centrality::degree(graph),
+            Self::Closeness => centrality::closeness(graph),
+            Self::Betweenness => centrality::betweenness(graph),
+            Self::Eigenvector => centrality::eigenvector(graph),
+            Self::PageRank => centrality::page_rank(graph),
+        }
+    }
+}
+
+pub struct CentralityScores {
+    metric: CentralityMetric,
+    snapshot: GraphSnapshot,
+    values: Vec<f32>,
+}
+
+#[derive(Resource)]
+pub struct CentralityState {
+    pub metric: CentralityMetric,
+    pub scale_radius: bool,
+    pub color_gradient: bool,
+    pub min_radius: f32,
+    pub max_radius: f32,
+    pub scores: Option<CentralityScores>,
+}
+
+impl Default for CentralityState {
+    fn default() -> Self {
+        Self {
+            metric: CentralityMetric::Degree,
+            scale_radius: true,
+            color_gradient: true,
+            min_radius: 8.0,
+            max_radius: 28.0,
+            scores: None,
+        }
+    }
+}
+
+/// Maps the scores onto the node radius and color, the lowest score getting
+/// the smallest radius and blue and the highest the largest radius and red.
+fn encode(state: &CentralityState, highlights: &mut HighlightLayer) {
+    highlights.clear();
+
+    let Some(scores) = state.scores.as_ref() else {
+        return;
+    };
+
+    let lowest = scores.values.iter().copied().fold(f32::INFINITY, f32::min);
+    let highest = scores
+        .values
+        .iter()
+        .copied()
+        .fold(f32::NEG_INFINITY, f32::max);
+
+    for (&entity, &value) in scores.snapshot.nodes.iter().zip(scores.values.iter()) {
+        let t = if highest > lowest {
+            (value - lowest) / (highest - lowest)
+        } else {
+            0.5
+        };
+
+        if state.scale_radius {
+            highlights.node_radii.insert(
+                entity,
+                state.min_radius + t * (state.max_radius - state.min_radius),
+            );
+        }
+
+        if state.color_gradient {
+            highlights.nodes.insert(entity, utils::gradient_color(t));
+        }
+    }
+}
+
+pub fn show_centrality_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut centrality_state: ResMut<CentralityState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+) {
+    let mut open = ui_state.is_open(Tool::Centrality);
+    let state = &mut *centrality_state;
+
+    if !open {
+        if state.scores.take().is_some() {
+            highlights.clear(Tool::Centrality);
+        }
+
+        return;
+    }
+
+    let mut changed = false;
+
+    egui::Window::new(Tool::Centrality.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                let metric = state.metric;
+
+                egui::ComboBox::from_label("metric")
+                    .selected_text(state.metric.name())
+                    .show_ui(ui, |ui| {
+                        for metric in CentralityMetric::ALL {
+                            ui.selectable_value(&mut state.metric, metric, metric.name());
+                        }
+                    });
+
+                let recompute = state.scores.is_some() && state.metric != metric;
+
+                if ui.button("Compute").clicked() || recompute {
+                    let snapshot = graph_query.snapshot();
+
+                    state.scores = Some(CentralityScores {
+                        metric: state.metric,
+                        values: state.metric.compute(&snapshot.graph),
+                        snapshot,
+                    });
+                    changed = true;
+                }
+            });
+
+            changed |= ui
+                .checkbox(&mut state.scale_radius, "Scale the node radius")
+                .changed();
+            changed |= ui
+                .checkbox(&mut state.color_gradient, "Color from blue (low) to red (high)")
+                .changed();
+
+            ui.add_enabled_ui(state.scale_radius, |ui| {
+                changed |= ui
+                    .add(egui::Slider::new(&mut state.min_radius, 4.0..=30.0).text("smallest radius"))
+                    .changed();
+                changed |= ui
+                    .add(egui::Slider::new(&mut state.max_radius, 4.0..=60.0).text("largest radius"))
+                    .changed();
+            });
+
+            ui.label("Closeness, betweenness, eigenvector and PageRank follow the edge directions and give every edge length 1.");
+
+            let Some(scores) = state.scores.as_ref() else {
+                return;
+            };
+
+            let graph = &scores.snapshot.graph;
+            let mut ranking = (0..graph.nodes.len()).collect::<Vec<_>>();
+
+            ranking.sort_by(|&a, &b| scores.values[b].total_cmp(&scores.values[a]));
+
+            ui.separator();
+            ui.label(format!("{} centrality", scores.metric.name()));
+
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("centrality_scores")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for node in ranking {
+                                ui.label(graph.nodes[node].label.as_str());
+                                ui.label(format!("{:.3}", scores.values[node]));
+                                ui.end_row();
+                            }
+                        });
+                });
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Centrality, false);
+    }
+
+    if changed {
+        encode(state, highlights.layer(Tool::Centrality));
+    }
+}
@@ -0,0 +1,225 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    assets::NodeAssets,
+    components::{
+        Attributes, Directed, EdgeStyle, Label, NeighborNodes, Node, NodeStyle, Route, Weight,
+    },
+    formats::Format,
+    graph::EdgeRoute,
+    layout,
+    resources::{EdgeIndex, EdgeSettings, GraphCollection, NodeSettings, VisualizerState},
+};
+
+use super::{edges, node};
+
+/// Shows the current graph of the collection in place of the canvas. The
+/// nodes are laid out again unless `layout` is off or the graph has
+/// positions of its own, in which case they keep them.
+pub struct ShowGraphEvent {
+    pub layout: bool,
+}
+
+pub fn show_opened_graphs(
+    mut event_writer: EventWriter<ShowGraphEvent>,
+    collection: Res<GraphCollection>,
+) {
+    if !collection.graphs.is_empty() {
+        event_writer.send(ShowGraphEvent { layout: true });
+    }
+}
+
+pub fn load_dropped_file(
+    mut event_reader: EventReader<FileDragAndDrop>,
+    mut event_writer: EventWriter<ShowGraphEvent>,
+    mut collection: ResMut<GraphCollection>,
+) {
+    for ev in event_reader.iter() {
+        let FileDragAndDrop::DroppedFile { path_buf, .. } = ev else {
+            continue;
+        };
+
+        let contents = match fs::read_to_string(path_buf) {
+            Ok(contents) => contents,
+            Err(err) => {
+                error!("Can not read {}: {err}", path_buf.display());
+
+                continue;
+            }
+        };
+
+        let format = Format::from_path(path_buf).unwrap_or(Format::Graph6);
+
+        match format.read(&contents) {
+            Ok(graphs) if graphs.is_empty() => {
+                warn!("{} does not contain any graph", path_buf.display());
+            }
+            Ok(graphs) => {
+                info!(
+                    "Loaded {} graph(s) from {}",
+                    graphs.len(),
+                    path_buf.display()
+                );
+
+                collection.graphs = graphs;
+                collection.current = 0;
+
+                event_writer.send(ShowGraphEvent { layout: true });
+            }
+            Err(err) => error!("Can not load {}: {err}", path_buf.display()),
+        }
+    }
+}
+
+pub fn page_through_graphs(
+    input: Res<Input<KeyCode>>,
+    mut event_writer: EventWriter<ShowGraphEvent>,
+    mut collection: ResMut<GraphCollection>,
+    visualizer_state: Res<VisualizerState>,
+) {
+    if visualizer_state.is_moving_node || visualizer_state.is_typing || collection.graphs.is_empty()
+    {
+        return;
+    }
+
+    let last = collection.graphs.len() - 1;
+
+    let current = if input.just_released(KeyCode::PageDown) {
+        (collection.current + 1).min(last)
+    } else if input.just_released(KeyCode::PageUp) {
+        collection.current.saturating_sub(1)
+    } else {
+        return;
+    };
+
+    if current != collection.current {
+        collection.current = current;
+
+        event_writer.send(ShowGraphEvent { layout: true });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_graph(
+    mut commands: Commands,
+    node_query: Query<Entity, With<Node>>,
+    edge_query: Query<Entity, With<NeighborNodes>>,
+    mut event_reader: EventReader<ShowGraphEvent>,
+    mut event_writer: EventWriter<edges::GraphChangedEvent>,
+    mut windows: ResMut<Windows>,
+    mut node_assets: NodeAssets,
+    mut edge_index: ResMut<EdgeIndex>,
+    collection: Res<GraphCollection>,
+    node_settings: Res<NodeSettings>,
+    edge_settings: Res<EdgeSettings>,
+) {
+    let Some(event) = event_reader.iter().last() else {
+        return;
+    };
+
+    let Some(graph) = collection.graphs.get(collection.current) else {
+        return;
+    };
+
+    let window = windows
+        .get_primary_mut()
+        .expect("Can not get the primary window");
+
+    for entity in node_query.iter().chain(edge_query.iter()) {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    edge_index.clear();
+
+    let mut graph = graph.clone();
+    let margin = node_settings.radius * 4.0;
+
+    if event.layout && !graph.has_positions() {
+        layout::force_directed(
+            &mut graph,
+            window.width() - margin,
+            window.height() - margin,
+            300,
+        );
+    }
+
+    let positions = graph
+        .nodes
+        .iter()
+        .map(|node| Transform::from_xyz(node.x, node.y, 1.0))
+        .collect::<Vec<_>>();
+
+    let entities = graph
+        .nodes
+        .iter()
+        .map(|node| {
+            let mut entity = commands.spawn((
+                node::node_bundle(node.x, node.y, &node_settings, &mut node_assets),
+                Label(node.label.clone()),
+            ));
+
+            if let Some(style) = &node.style {
+                entity.insert(NodeStyle(style.clone()));
+            }
+
+            if !node.attributes.is_empty() {
+                entity.insert(Attributes(node.attributes.clone()));
+            }
+
+            entity.id()
+        })
+        .collect::<Vec<_>>();
+
+    for edge in graph.edges.iter() {
+        let (pos_v, pos_u) = (positions[edge.source], positions[edge.target]);
+
+        let path = edges::edge_path(&pos_v, &pos_u, edge.directed, node_settings.radius, 0.0);
+
+        let mut entity = commands.spawn(edges::edge_bundle(
+            NeighborNodes {
+                v: entities[edge.source],
+                u: entities[edge.target],
+                pos_v,
+                pos_u,
+            },
+            &path,
+            &edge_settings,
+        ));
+
+        if edge.directed {
+            entity.insert(Directed);
+        }
+
+        if let Some(weight) = edge.weight {
+            entity.insert(Weight(weight));
+        }
+
+        if let Some(label) = &edge.label {
+            entity.insert(Label(label.clone()));
+        }
+
+        if edge.route != EdgeRoute::Straight {
+            entity.insert(Route(edge.route));
+        }
+
+        if let Some(style) = &edge.style {
+            entity.insert(EdgeStyle::from_graph(style));
+        }
+
+        if !edge.attributes.is_empty() {
+            entity.insert(Attributes(edge.attributes.clone()));
+        }
+
+        edge_index.insert(entity.id(), entities[edge.source], entities[edge.target]);
+    }
+
+    window.set_title(format!(
+        "Graph visualizer - graph {} of {}",
+        collection.current + 1,
+        collection.graphs.len()
+    ));
+
+    event_writer.send(edges::GraphChangedEvent);
+}
@@ -0,0 +1,377 @@
+use std::time::{Duration, Instant};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::coloring::{self, ExactColoring},
+    components::Node,
+    graph::Graph,
+    resources::{
+        HighlightLayer, HighlightSettings, Highlights, InteractionMode, NodeIndex, Tool, UiState,
+        VisualizerState,
+    },
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::background::BackgroundTask;
+
+/// Exact searches are only offered up to this number of nodes.
+const MAX_EXACT_NODES: usize = 64;
+
+pub enum ColoringResult {
+    Heuristic {
+        name: &'static str,
+        colors: usize,
+    },
+    Exact {
+        colors: usize,
+        optimal: bool,
+        cancelled: bool,
+        tried: u64,
+        elapsed: Duration,
+    },
+}
+
+pub struct ExactSearch {
+    snapshot: GraphSnapshot,
+    task: BackgroundTask<ExactColoring>,
+}
+
+#[derive(Resource)]
+pub struct ColoringState {
+    pub result: Option<ColoringResult>,
+    pub search: Option<ExactSearch>,
+    pub timeout_seconds: f32,
+    pub game_colors: usize,
+    pub game_assignment: HashMap<Entity, usize>,
+    pub game_conflicts: usize,
+}
+
+impl Default for ColoringState {
+    fn default() -> Self {
+        Self {
+            result: None,
+            search: None,
+            timeout_seconds: 2.0,
+            game_colors: 3,
+            game_assignment: HashMap::new(),
+            game_conflicts: 0,
+        }
+    }
+}
+
+fn apply_coloring(snapshot: &GraphSnapshot, colors: &[usize], highlights: &mut HighlightLayer) {
+    highlights.clear();
+
+    for (&entity, &color) in snapshot.nodes.iter().zip(colors.iter()) {
+        highlights.nodes.insert(entity, utils::palette_color(color));
+    }
+}
+
+fn start_exact_search(snapshot: GraphSnapshot, timeout_seconds: f32) -> ExactSearch {
+    let graph = snapshot.graph.clone();
+    let deadline = Instant::now() + Duration::from_secs_f32(timeout_seconds);
+
+    ExactSearch {
+        snapshot,
+        task: BackgroundTask::start(move |progress, cancel| {
+            coloring::exact(&graph, deadline, progress, cancel)
+        }),
+    }
+}
+
+/// Colors the nodes of the game and marks the edges whose endpoints have
+/// the same color. Returns the number of such edges.
+fn show_game(
+    snapshot: &GraphSnapshot,
+    assignment: &HashMap<Entity, usize>,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) -> usize {
+    highlights.clear();
+
+    let colors = snapshot
+        .nodes
+        .iter()
+        .map(|entity| assignment.get(entity).copied())
+        .collect::<Vec<_>>();
+
+    for (&entity, color) in snapshot.nodes.iter().zip(colors.iter()) {
+        if let Some(color) = color {
+            highlights
+                .nodes
+                .insert(entity, utils::palette_color(*color));
+        }
+    }
+
+    let conflicts = coloring::conflicts(&snapshot.graph, &colors);
+
+    for &edge in conflicts.iter() {
+        highlights
+            .edges
+            .insert(snapshot.edges[edge], highlight_settings.rejected_color);
+    }
+
+    conflicts.len()
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_coloring_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut coloring_state: ResMut<ColoringState>,
+    mut visualizer_state: ResMut<VisualizerState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    nodes: Query<Entity, With<Node>>,
+) {
+    let mut open = ui_state.is_open(Tool::Coloring);
+    let playing = visualizer_state.interaction_mode == InteractionMode::ColoringGame;
+
+    if !open {
+        coloring_state.search = None;
+
+        if coloring_state.result.take().is_some() || playing {
+            visualizer_state.interaction_mode = InteractionMode::Edit;
+            coloring_state.game_assignment.clear();
+            coloring_state.game_conflicts = 0;
+            highlights.clear(Tool::Coloring);
+        }
+
+        return;
+    }
+
+    let state = &mut *coloring_state;
+
+    if let Some(result) = state.search.as_mut().and_then(|search| search.task.poll()) {
+        let search = state.search.take().unwrap();
+
+        match result {
+            Ok(exact) => {
+                apply_coloring(
+                    &search.snapshot,
+                    &exact.colors,
+                    highlights.layer(Tool::Coloring),
+                );
+
+                state.result = Some(ColoringResult::Exact {
+                    colors: coloring::color_count(&exact.colors),
+                    optimal: exact.optimal,
+                    cancelled: search.task.is_cancelled(),
+                    tried: search.task.progress(),
+                    elapsed: search.task.elapsed(),
+                });
+            }
+            Err(_) => error!("The exact coloring search stopped unexpectedly"),
+        }
+    }
+
+    let idle = state.search.is_none();
+
+    egui::Window::new(Tool::Coloring.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.add_enabled_ui(!playing, |ui| {
+                ui.horizontal(|ui| {
+                    let heuristics: [(&'static str, fn(&Graph) -> Vec<usize>); 3] = [
+                        ("Greedy", coloring::greedy),
+                        ("DSatur", coloring::dsatur),
+                        ("Welsh-Powell", coloring::welsh_powell),
+                    ];
+
+                    for (name, heuristic) in heuristics {
+                        if ui.add_enabled(idle, egui::Button::new(name)).clicked() {
+                            let snapshot = graph_query.snapshot();
+                            let colors = heuristic(&snapshot.graph);
+
+                            apply_coloring(&snapshot, &colors, highlights.layer(Tool::Coloring));
+
+                            state.result = Some(ColoringResult::Heuristic {
+                                name,
+                                colors: coloring::color_count(&colors),
+                            });
+                        }
+                    }
+                });
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(idle, egui::Button::new("Chromatic number"))
+                        .clicked()
+                    {
+                        let snapshot = graph_query.snapshot();
+
+                        if snapshot.graph.nodes.len() > MAX_EXACT_NODES {
+                            warn!(
+                                "The exact search is limited to graphs with at most {MAX_EXACT_NODES} nodes"
+                            );
+                        } else {
+                            state.search =
+                                Some(start_exact_search(snapshot, state.timeout_seconds));
+                            state.result = None;
+                            highlights.clear(Tool::Coloring);
+                        }
+                    }
+
+                    if ui
+                        .add_enabled(!idle, egui::Button::new("Cancel"))
+                        .clicked()
+                    {
+                        if let Some(search) = state.search.as_ref() {
+                            search.task.cancel();
+                        }
+                    }
+
+                    ui.add(
+                        egui::Slider::new(&mut state.timeout_seconds, 0.1..=10.0)
+                            .text("seconds at most"),
+                    );
+                });
+            });
+
+            ui.label(format!(
+                "Loops are ignored. The exact search works on graphs with up to {MAX_EXACT_NODES} nodes."
+            ));
+
+            if let Some(search) = state.search.as_ref() {
+                ui.label(format!(
+                    "Searching: {} partial colorings tried in {:.1} s",
+                    search.task.progress(),
+                    search.task.elapsed().as_secs_f32()
+                ));
+            }
+
+            match &state.result {
+                Some(ColoringResult::Heuristic { name, colors }) => {
+                    ui.label(format!("{name} used {colors} colors"));
+                }
+                Some(ColoringResult::Exact {
+                    colors,
+                    optimal,
+                    cancelled,
+                    tried,
+                    elapsed,
+                }) => {
+                    if *optimal {
+                        ui.label(format!("Chromatic number: {colors}"));
+                    } else {
+                        let reason = if *cancelled { "Cancelled" } else { "Timed out" };
+
+                        ui.label(format!(
+                            "{reason}: the chromatic number is at most {colors}"
+                        ));
+                    }
+
+                    ui.label(format!(
+                        "{tried} partial colorings tried in {:.1} s",
+                        elapsed.as_secs_f32()
+                    ));
+                }
+                None => {}
+            }
+
+            ui.separator();
+
+            let mut game = playing;
+
+            ui.checkbox(&mut game, "Coloring game");
+
+            if game != playing {
+                state.search = None;
+                state.result = None;
+                state.game_assignment.clear();
+                state.game_conflicts = 0;
+                highlights.clear(Tool::Coloring);
+
+                visualizer_state.interaction_mode = if game {
+                    InteractionMode::ColoringGame
+                } else {
+                    InteractionMode::Edit
+                };
+            }
+
+            if !game {
+                return;
+            }
+
+            ui.add(egui::Slider::new(&mut state.game_colors, 2..=8).text("colors"));
+            ui.label("Click a node to change its color. Same-colored neighbors are marked in red.");
+
+            let node_count = nodes.iter().count();
+            let colored = nodes
+                .iter()
+                .filter(|entity| state.game_assignment.contains_key(entity))
+                .count();
+
+            ui.label(format!(
+                "Colored nodes: {colored} of {node_count}, conflicts: {}",
+                state.game_conflicts
+            ));
+
+            if colored == node_count && state.game_conflicts == 0 {
+                ui.label("Solved!");
+            }
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Coloring, false);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn play_coloring_game(
+    mut coloring_state: ResMut<ColoringState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    node_index: Res<NodeIndex>,
+    visualizer_state: Res<VisualizerState>,
+    highlight_settings: Res<HighlightSettings>,
+) {
+    if visualizer_state.interaction_mode != InteractionMode::ColoringGame
+        || visualizer_state.is_pointer_over_ui
+    {
+        return;
+    }
+
+    if !buttons.just_released(MouseButton::Left) {
+        return;
+    }
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+
+    let Some((x, y)) = utils::get_mouse_coordinates(window) else {
+        return;
+    };
+
+    let Some(entity) = node_index.item_at(x, y) else {
+        return;
+    };
+
+    let state = &mut *coloring_state;
+
+    // Clicking goes through every color and then back to no color.
+    match state.game_assignment.get(&entity).copied() {
+        Some(color) if color + 1 >= state.game_colors => {
+            state.game_assignment.remove(&entity);
+        }
+        Some(color) => {
+            state.game_assignment.insert(entity, color + 1);
+        }
+        None => {
+            state.game_assignment.insert(entity, 0);
+        }
+    }
+
+    state.game_conflicts = show_game(
+        &graph_query.snapshot(),
+        &state.game_assignment,
+        highlights.layer(Tool::Coloring),
+        &highlight_settings,
+    );
+}
@@ -0,0 +1,293 @@
+use std::{
+    f32::consts::PI,
+    sync::atomic::{AtomicBool, AtomicU64},
+};
+
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContext};
+use bevy_rapier2d::prelude::*;
+
+use crate::{
+    algorithms::community,
+    components::{MovingNode, Node, Radius},
+    graph::Graph,
+    resources::{HighlightLayer, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::background::BackgroundTask;
+
+/// How strongly communities push each other away when pulled together.
+const SEPARATION: f32 = 20000.0;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum CommunityAlgorithm {
+    Louvain,
+    LabelPropagation,
+    GirvanNewman,
+}
+
+impl CommunityAlgorithm {
+    const ALL: [CommunityAlgorithm; 3] = [
+        CommunityAlgorithm::Louvain,
+        CommunityAlgorithm::LabelPropagation,
+        CommunityAlgorithm::GirvanNewman,
+    ];
+
+    fn name(self) -> &'static str {
+        match self {
+            Self::Louvain => "Louvain",
+            Self::LabelPropagation => "Label propagation",
+            Self::GirvanNewman => "Girvan-Newman",
+        }
+    }
+
+    /// Returns `None` when the run was cancelled.
+    fn run(self, graph: &Graph, progress: &AtomicU64, cancel: &AtomicBool) -> Option<Vec<usize>> {
+        match self {
+            Self::Louvain => Some(community::louvain(graph)),
+            Self::LabelPropagation => Some(community::label_propagation(graph)),
+            Self::GirvanNewman => community::girvan_newman(graph, progress, cancel),
+        }
+    }
+}
+
+pub struct CommunitySearch {
+    algorithm: CommunityAlgorithm,
+    snapshot: GraphSnapshot,
+    task: BackgroundTask<Option<Vec<usize>>>,
+}
+
+pub struct CommunityResult {
+    algorithm: CommunityAlgorithm,
+    count: usize,
+    modularity: f64,
+}
+
+#[derive(Resource)]
+pub struct CommunityState {
+    pub result: Option<CommunityResult>,
+    pub search: Option<CommunitySearch>,
+    pub communities: HashMap<Entity, usize>,
+    pub pull_together: bool,
+    pub strength: f32,
+}
+
+impl Default for CommunityState {
+    fn default() -> Self {
+        Self {
+            result: None,
+            search: None,
+            communities: HashMap::new(),
+            pull_together: false,
+            strength: 2.0,
+        }
+    }
+}
+
+fn start_search(snapshot: GraphSnapshot, algorithm: CommunityAlgorithm) -> CommunitySearch {
+    let graph = snapshot.graph.clone();
+
+    CommunitySearch {
+        algorithm,
+        snapshot,
+        task: BackgroundTask::start(move |progress, cancel| {
+            algorithm.run(&graph, progress, cancel)
+        }),
+    }
+}
+
+fn show_communities(
+    state: &mut CommunityState,
+    algorithm: CommunityAlgorithm,
+    snapshot: &GraphSnapshot,
+    communities: &[usize],
+    highlights: &mut HighlightLayer,
+) {
+    highlights.clear();
+
+    for (&entity, &community) in snapshot.nodes.iter().zip(communities.iter()) {
+        highlights
+            .nodes
+            .insert(entity, utils::palette_color(community));
+    }
+
+    state.communities = snapshot
+        .nodes
+        .iter()
+        .copied()
+        .zip(communities.iter().copied())
+        .collect();
+    state.result = Some(CommunityResult {
+        algorithm,
+        count: community::community_count(communities),
+        modularity: community::modularity(&snapshot.graph, communities),
+    });
+}
+
+pub fn show_communities_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut community_state: ResMut<CommunityState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+) {
+    let mut open = ui_state.is_open(Tool::Communities);
+    let state = &mut *community_state;
+
+    if !open {
+        state.search = None;
+
+        if state.result.take().is_some() {
+            state.communities.clear();
+            highlights.clear(Tool::Communities);
+        }
+
+        return;
+    }
+
+    if let Some(result) = state.search.as_mut().and_then(|search| search.task.poll()) {
+        let search = state.search.take().unwrap();
+
+        match result {
+            Ok(Some(communities)) => show_communities(
+                state,
+                search.algorithm,
+                &search.snapshot,
+                &communities,
+                highlights.layer(Tool::Communities),
+            ),
+            Ok(None) => {}
+            Err(_) => error!("The community detection stopped unexpectedly"),
+        }
+    }
+
+    let idle = state.search.is_none();
+
+    egui::Window::new(Tool::Communities.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for algorithm in CommunityAlgorithm::ALL {
+                    if ui
+                        .add_enabled(idle, egui::Button::new(algorithm.name()))
+                        .clicked()
+                    {
+                        state.search = Some(start_search(graph_query.snapshot(), algorithm));
+                    }
+                }
+
+                if ui
+                    .add_enabled(!idle, egui::Button::new("Cancel"))
+                    .clicked()
+                {
+                    if let Some(search) = state.search.as_ref() {
+                        search.task.cancel();
+                    }
+                }
+            });
+
+            ui.label("Edge directions and weights are ignored. Girvan-Newman gets slow past a few hundred edges.");
+
+            if let Some(search) = state.search.as_ref() {
+                let removed = if search.algorithm == CommunityAlgorithm::GirvanNewman {
+                    format!(
+                        ", {} of {} edges removed",
+                        search.task.progress(),
+                        search.snapshot.graph.edges.len()
+                    )
+                } else {
+                    String::new()
+                };
+
+                ui.label(format!(
+                    "Running {} for {:.1} s{removed}",
+                    search.algorithm.name(),
+                    search.task.elapsed().as_secs_f32()
+                ));
+            }
+
+            if let Some(result) = state.result.as_ref() {
+                ui.label(format!(
+                    "{} found {} communities, modularity {:.3}",
+                    result.algorithm.name(),
+                    result.count,
+                    result.modularity
+                ));
+            }
+
+            ui.separator();
+            ui.checkbox(&mut state.pull_together, "Pull communities together");
+            ui.add_enabled(
+                state.pull_together,
+                egui::Slider::new(&mut state.strength, 0.5..=5.0).text("strength"),
+            );
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Communities, false);
+    }
+}
+
+/// Pushes every node towards the center of its community and the centers
+/// of different communities away from each other. The push is an impulse,
+/// so the velocity given by other forces is kept and damping slows the
+/// nodes down to the pull speed.
+pub fn pull_communities(
+    mut query: Query<
+        (Entity, &Transform, &Radius, &Damping, &mut ExternalImpulse),
+        (With<Node>, Without<MovingNode>),
+    >,
+    community_state: Res<CommunityState>,
+    ui_state: Res<UiState>,
+    time: Res<Time>,
+) {
+    let state = &*community_state;
+
+    if !state.pull_together || state.communities.is_empty() || !ui_state.is_open(Tool::Communities)
+    {
+        return;
+    }
+
+    let mut sums = HashMap::<usize, (Vec2, f32)>::new();
+
+    for (entity, transform, ..) in query.iter() {
+        if let Some(&community) = state.communities.get(&entity) {
+            let sum = sums.entry(community).or_default();
+
+            sum.0 += transform.translation.truncate();
+            sum.1 += 1.0;
+        }
+    }
+
+    let centers = sums
+        .into_iter()
+        .map(|(community, (sum, count))| (community, sum / count))
+        .collect::<HashMap<_, _>>();
+
+    for (entity, transform, radius, damping, mut impulse) in query.iter_mut() {
+        let Some(community) = state.communities.get(&entity) else {
+            continue;
+        };
+
+        let center = centers[community];
+        let mut push = Vec2::ZERO;
+
+        for (other, &other_center) in centers.iter() {
+            let away = center - other_center;
+
+            if other != community && away != Vec2::ZERO {
+                push += away / away.length_squared();
+            }
+        }
+
+        let pull_velocity = (center - transform.translation.truncate()) * state.strength
+            + push * SEPARATION * state.strength;
+
+        // The colliders have the default density of 1.
+        let mass = PI * radius.0 * radius.0;
+
+        impulse.impulse += pull_velocity * damping.linear_damping * mass * time.delta_seconds();
+    }
+}
@@ -0,0 +1,193 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::connectivity,
+    resources::{HighlightSettings, Highlights, Tool, UiState},
+    snapshot::GraphQuery,
+    utils,
+};
+
+use super::edges::GraphChangedEvent;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ComponentView {
+    Connected,
+    StronglyConnected,
+}
+
+pub struct ConnectivitySummary {
+    components: usize,
+    strongly_connected_components: Option<usize>,
+    bridges: usize,
+    articulation_points: usize,
+}
+
+#[derive(Resource)]
+pub struct ConnectivityState {
+    pub view: ComponentView,
+    pub show_cut_elements: bool,
+    pub summary: Option<ConnectivitySummary>,
+    needs_update: bool,
+}
+
+impl Default for ConnectivityState {
+    fn default() -> Self {
+        Self {
+            view: ComponentView::Connected,
+            show_cut_elements: true,
+            summary: None,
+            needs_update: true,
+        }
+    }
+}
+
+pub fn show_connectivity_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut connectivity_state: ResMut<ConnectivityState>,
+    mut highlights: ResMut<Highlights>,
+) {
+    let mut open = ui_state.is_open(Tool::Connectivity);
+
+    if !open {
+        if connectivity_state.summary.take().is_some() {
+            connectivity_state.needs_update = true;
+            highlights.clear(Tool::Connectivity);
+        }
+
+        return;
+    }
+
+    let state = &mut *connectivity_state;
+
+    egui::Window::new(Tool::Connectivity.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let Some(summary) = state.summary.as_ref() else {
+                return;
+            };
+
+            let mut view = state.view;
+
+            ui.radio_value(&mut view, ComponentView::Connected, "Connected components");
+            ui.add_enabled_ui(summary.strongly_connected_components.is_some(), |ui| {
+                ui.radio_value(
+                    &mut view,
+                    ComponentView::StronglyConnected,
+                    "Strongly connected components",
+                );
+            });
+
+            let mut show_cut_elements = state.show_cut_elements;
+
+            ui.checkbox(&mut show_cut_elements, "Bridges and articulation points");
+
+            if view != state.view || show_cut_elements != state.show_cut_elements {
+                state.view = view;
+                state.show_cut_elements = show_cut_elements;
+                state.needs_update = true;
+            }
+
+            ui.separator();
+
+            ui.label(format!("Connected components: {}", summary.components));
+
+            if let Some(count) = summary.strongly_connected_components {
+                ui.label(format!("Strongly connected components: {count}"));
+            }
+
+            ui.label(format!("Bridges: {}", summary.bridges));
+            ui.label(format!(
+                "Articulation points: {}",
+                summary.articulation_points
+            ));
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Connectivity, false);
+    }
+}
+
+/// Runs after the commands of the frame are applied, so that the edges
+/// created or removed during the frame are part of the analysis.
+pub fn analyse_connectivity(
+    mut event_reader: EventReader<GraphChangedEvent>,
+    mut connectivity_state: ResMut<ConnectivityState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    ui_state: Res<UiState>,
+    highlight_settings: Res<HighlightSettings>,
+) {
+    let graph_changed = event_reader.iter().last().is_some();
+
+    if !ui_state.is_open(Tool::Connectivity) {
+        return;
+    }
+
+    if !graph_changed && !connectivity_state.needs_update {
+        return;
+    }
+
+    let snapshot = graph_query.snapshot();
+    let graph = &snapshot.graph;
+
+    let components = connectivity::connected_components(graph);
+    let strongly_connected_components = graph
+        .is_directed()
+        .then(|| connectivity::strongly_connected_components(graph));
+    let cut_elements = connectivity::cut_elements(graph);
+
+    if strongly_connected_components.is_none() {
+        connectivity_state.view = ComponentView::Connected;
+    }
+
+    let highlights = highlights.layer(Tool::Connectivity);
+
+    highlights.clear();
+
+    let node_components = match (connectivity_state.view, &strongly_connected_components) {
+        (ComponentView::StronglyConnected, Some(strongly_connected)) => strongly_connected,
+        _ => &components,
+    };
+
+    for (node, &component) in node_components.iter().enumerate() {
+        highlights
+            .nodes
+            .insert(snapshot.nodes[node], utils::palette_color(component));
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        let component = node_components[edge.source];
+
+        if node_components[edge.target] == component {
+            highlights
+                .edges
+                .insert(snapshot.edges[index], utils::palette_color(component));
+        }
+    }
+
+    if connectivity_state.show_cut_elements {
+        for &edge in cut_elements.bridges.iter() {
+            highlights
+                .edges
+                .insert(snapshot.edges[edge], highlight_settings.critical_color);
+        }
+
+        for &node in cut_elements.articulation_points.iter() {
+            highlights
+                .nodes
+                .insert(snapshot.nodes[node], highlight_settings.critical_color);
+        }
+    }
+
+    connectivity_state.summary = Some(ConnectivitySummary {
+        components: connectivity::component_count(&components),
+        strongly_connected_components: strongly_connected_components
+            .as_deref()
+            .map(connectivity::component_count),
+        bridges: cut_elements.bridges.len(),
+        articulation_points: cut_elements.articulation_points.len(),
+    });
+    connectivity_state.needs_update = false;
+}
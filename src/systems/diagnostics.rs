@@ -0,0 +1,27 @@
+use bevy::{
+    diagnostic::{Diagnostic, DiagnosticId, Diagnostics},
+    prelude::*,
+};
+
+pub const MESH_COUNT: DiagnosticId =
+    DiagnosticId::from_u128(180_432_695_915_206_187_436_862_904_412_815_342_337);
+pub const COLOR_MATERIAL_COUNT: DiagnosticId =
+    DiagnosticId::from_u128(257_318_809_563_127_401_981_225_004_718_096_775_111);
+
+pub fn setup_asset_diagnostics(mut diagnostics: ResMut<Diagnostics>) {
+    diagnostics.add(Diagnostic::new(MESH_COUNT, "mesh_count", 1));
+    diagnostics.add(Diagnostic::new(
+        COLOR_MATERIAL_COUNT,
+        "color_material_count",
+        1,
+    ));
+}
+
+pub fn measure_asset_counts(
+    mut diagnostics: ResMut<Diagnostics>,
+    meshes: Res<Assets<Mesh>>,
+    materials: Res<Assets<ColorMaterial>>,
+) {
+    diagnostics.add_measurement(MESH_COUNT, || meshes.len() as f64);
+    diagnostics.add_measurement(COLOR_MATERIAL_COUNT, || materials.len() as f64);
+}
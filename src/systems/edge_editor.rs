@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+use bevy_prototype_lyon::prelude::*;
+
+use crate::{
+    components::{
+        Bend, Corners, Directed, EdgeStyle, Label, NeighborNodes, Radius, Route, SelectedEdge,
+        Weight,
+    },
+    graph::{Dash, EdgeRoute},
+    resources::{EdgeIndex, EdgeSettings, Highlights, NodeIndex, NodeSettings, VisualizerState},
+    utils,
+};
+
+use super::edges::{self, distance_to_path, ChangeEdgeColorEvent, GraphChangedEvent};
+
+/// How far from its line, beyond half its width, an edge can be clicked.
+const EDGE_HIT_DISTANCE: f32 = 6.0;
+
+#[derive(Resource, Default)]
+pub struct EdgeEditorState {
+    /// The edge whose label is being typed, so that the text is only reset
+    /// when another edge is selected.
+    editing: Option<Entity>,
+    label: String,
+}
+
+/// The style that draws an edge as `EdgeSettings` says.
+pub fn default_edge_style(edge_settings: &EdgeSettings) -> EdgeStyle {
+    EdgeStyle {
+        color: edge_settings.color,
+        width: edge_settings.size,
+        dash: Dash::Solid,
+    }
+}
+
+/// The color an edge is drawn with when no tool highlights it.
+fn edge_color(style: Option<&EdgeStyle>, selected: bool, edge_settings: &EdgeSettings) -> Color {
+    if selected {
+        edge_settings.selected_color
+    } else {
+        style.map_or(edge_settings.color, |style| style.color)
+    }
+}
+
+/// Selects the edge under the cursor on a left click, or unselects it when
+/// it is already selected. Clicks on an edge do not create a node.
+#[allow(clippy::too_many_arguments)]
+pub fn select_edge(
+    mut commands: Commands,
+    edges: Query<(Entity, &Path, Option<&EdgeStyle>), With<NeighborNodes>>,
+    selected_edges: Query<(Entity, Option<&EdgeStyle>), With<SelectedEdge>>,
+    mut event_writer: EventWriter<ChangeEdgeColorEvent>,
+    mut buttons: ResMut<Input<MouseButton>>,
+    windows: Res<Windows>,
+    node_index: Res<NodeIndex>,
+    edge_settings: Res<EdgeSettings>,
+    visualizer_state: Res<VisualizerState>,
+) {
+    if !visualizer_state.can_edit() {
+        return;
+    }
+
+    if !buttons.just_released(MouseButton::Left) {
+        return;
+    }
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+
+    let Some((x, y)) = utils::get_mouse_coordinates(window) else {
+        return;
+    };
+
+    if node_index.item_at(x, y).is_some() {
+        return;
+    }
+
+    let Some((entity, _)) = edges
+        .iter()
+        .filter_map(|(entity, path, style)| {
+            let width = style.map_or(edge_settings.size, |style| style.width);
+            let distance = distance_to_path(path, Vec2::new(x, y));
+
+            (distance <= width / 2.0 + EDGE_HIT_DISTANCE).then_some((entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+    else {
+        return;
+    };
+
+    buttons.clear_just_released(MouseButton::Left);
+
+    for (selected, style) in selected_edges.iter() {
+        commands.entity(selected).remove::<SelectedEdge>();
+
+        event_writer.send(ChangeEdgeColorEvent {
+            entity: selected,
+            color: edge_color(style, false, &edge_settings),
+        });
+    }
+
+    if selected_edges.contains(entity) {
+        return;
+    }
+
+    commands.entity(entity).insert(SelectedEdge);
+
+    event_writer.send(ChangeEdgeColorEvent {
+        entity,
+        color: edge_settings.selected_color,
+    });
+}
+
+/// Strokes the edges whose style was edited, keeping the color of the
+/// selection or of a tool's highlight.
+pub fn stroke_edges(
+    mut edges: Query<
+        (Entity, &EdgeStyle, &mut DrawMode, Option<&SelectedEdge>),
+        Changed<EdgeStyle>,
+    >,
+    highlights: Res<Highlights>,
+    edge_settings: Res<EdgeSettings>,
+) {
+    for (entity, style, mut draw_mode, selected_edge) in edges.iter_mut() {
+        let color = highlights
+            .edge_color(entity)
+            .unwrap_or_else(|| edge_color(Some(style), selected_edge.is_some(), &edge_settings));
+
+        *draw_mode = DrawMode::Stroke(StrokeMode::new(color, style.width));
+    }
+}
+
+/// Breaks the line of dashed edges into dashes whenever it is drawn again.
+/// When the dash itself changed, the whole line is drawn again first.
+#[allow(clippy::type_complexity)]
+pub fn dash_edges(
+    mut edges: Query<
+        (
+            &NeighborNodes,
+            &EdgeStyle,
+            ChangeTrackers<EdgeStyle>,
+            &mut Path,
+            Option<&Directed>,
+            Option<&Bend>,
+            Option<&Route>,
+            Option<&Corners>,
+        ),
+        Or<(Changed<Path>, Changed<EdgeStyle>)>,
+    >,
+    radii: Query<&Radius>,
+    node_settings: Res<NodeSettings>,
+) {
+    for (neighbor_nodes, style, style_tracker, mut path, directed, bend, route, corners) in
+        edges.iter_mut()
+    {
+        if style_tracker.is_changed() {
+            let radius = radii
+                .get(neighbor_nodes.u)
+                .map_or(node_settings.radius, |radius| radius.0);
+            let route = route.map_or(EdgeRoute::Straight, |route| route.0);
+
+            *path = match (route, corners) {
+                (EdgeRoute::Orthogonal, Some(corners)) if neighbor_nodes.v != neighbor_nodes.u => {
+                    edges::polyline_edge_path(&corners.0, directed.is_some(), radius)
+                }
+                _ => edges::neighbor_nodes_path(
+                    neighbor_nodes,
+                    directed.is_some(),
+                    radius,
+                    bend.map_or(0.0, |bend| bend.0),
+                    route,
+                ),
+            };
+        }
+
+        edges::dash_path(&mut path, style.dash);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_edge_editor(
+    mut commands: Commands,
+    mut egui_context: ResMut<EguiContext>,
+    mut editor_state: ResMut<EdgeEditorState>,
+    mut edge_index: ResMut<EdgeIndex>,
+    mut color_event_writer: EventWriter<ChangeEdgeColorEvent>,
+    mut graph_changed_event_writer: EventWriter<GraphChangedEvent>,
+    selected_edges: Query<
+        (
+            Entity,
+            &NeighborNodes,
+            Option<&EdgeStyle>,
+            Option<&Weight>,
+            Option<&Label>,
+        ),
+        With<SelectedEdge>,
+    >,
+    labels: Query<&Label, Without<NeighborNodes>>,
+    edge_settings: Res<EdgeSettings>,
+) {
+    let Some((entity, neighbor_nodes, style, weight, label)) = selected_edges.iter().next() else {
+        editor_state.editing = None;
+
+        return;
+    };
+
+    if editor_state.editing != Some(entity) {
+        editor_state.editing = Some(entity);
+        editor_state.label = label.map_or_else(String::new, |label| label.0.clone());
+    }
+
+    let node_label = |node| labels.get(node).map_or("?", |label| label.0.as_str());
+    let title = format!(
+        "Edge {} - {}",
+        node_label(neighbor_nodes.v),
+        node_label(neighbor_nodes.u)
+    );
+
+    let mut open = true;
+    let mut new_style = style
+        .copied()
+        .unwrap_or_else(|| default_edge_style(&edge_settings));
+    let mut has_weight = weight.is_some();
+    let mut new_weight = weight.map_or(1.0, |weight| weight.0);
+    let mut delete = false;
+
+    egui::Window::new("Edge")
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label(title);
+
+            let mut color = new_style.color.as_rgba_f32();
+
+            ui.horizontal(|ui| {
+                ui.label("Color");
+
+                if ui.color_edit_button_rgba_unmultiplied(&mut color).changed() {
+                    new_style.color = Color::rgba(color[0], color[1], color[2], color[3]);
+                }
+            });
+
+            ui.add(egui::Slider::new(&mut new_style.width, 1.0..=12.0).text("width"));
+
+            ui.horizontal(|ui| {
+                for dash in Dash::ALL {
+                    ui.radio_value(&mut new_style.dash, dash, dash.name());
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.checkbox(&mut has_weight, "Weight");
+                ui.add_enabled(has_weight, egui::DragValue::new(&mut new_weight).speed(0.1));
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Label");
+                ui.text_edit_singleline(&mut editor_state.label);
+            });
+
+            ui.separator();
+            delete = ui.button("Delete edge").clicked();
+        });
+
+    if delete {
+        commands.entity(entity).despawn();
+
+        edge_index.remove(entity);
+
+        graph_changed_event_writer.send(GraphChangedEvent);
+
+        return;
+    }
+
+    if !open {
+        commands.entity(entity).remove::<SelectedEdge>();
+
+        color_event_writer.send(ChangeEdgeColorEvent {
+            entity,
+            color: edge_color(Some(&new_style), false, &edge_settings),
+        });
+
+        return;
+    }
+
+    let mut edge = commands.entity(entity);
+
+    let style_changed = style != Some(&new_style);
+    let default_style = new_style == default_edge_style(&edge_settings);
+
+    if style_changed && !(style.is_none() && default_style) {
+        edge.insert(new_style);
+    }
+
+    match (has_weight, weight) {
+        (true, Some(weight)) if weight.0 == new_weight => {}
+        (true, _) => {
+            edge.insert(Weight(new_weight));
+        }
+        (false, Some(_)) => {
+            edge.remove::<Weight>();
+        }
+        (false, None) => {}
+    }
+
+    let new_label = editor_state.label.trim();
+
+    if label.map_or("", |label| label.0.as_str()) != new_label {
+        if new_label.is_empty() {
+            edge.remove::<Label>();
+        } else {
+            edge.insert(Label(new_label.to_string()));
+        }
+    }
+}
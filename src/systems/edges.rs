@@ -1,12 +1,21 @@
-use bevy::prelude::*;
-use bevy_prototype_lyon::prelude::*;
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_prototype_lyon::prelude::{
+    tess::path::{iterator::PathIterator, PathEvent},
+    *,
+};
 
 use crate::{
-    components::{NeighborNodes, SelectedNode},
-    resources::{EdgeSettings, NodeSettings},
+    components::{
+        Bend, Corners, Directed, EdgeStyle, NeighborNodes, NodeStyle, Radius, Route, SelectedNode,
+    },
+    graph::{Dash, EdgeRoute},
+    resources::{EdgeIndex, EdgeSettings, NodeSettings, VisualizerState},
 };
 
-use super::node::ChangeNodeColorEvent;
+use super::node::{self, ChangeNodeColorEvent};
 
 pub struct UpdateEdgeEvent {
     pub changed_node: Entity,
@@ -17,142 +26,760 @@ pub struct RemoveEdgeEvent {
     pub removed_node: Entity,
 }
 
+/// Removes one of the edges between two nodes, or one of the loops on a
+/// node when both are the same.
+pub struct DeleteEdgeEvent {
+    pub v: Entity,
+    pub u: Entity,
+}
+
+pub struct ChangeEdgeColorEvent {
+    pub entity: Entity,
+    pub color: Color,
+}
+
+/// Sent whenever nodes or edges are added or removed, so that analyses can
+/// be run again on the new graph.
+pub struct GraphChangedEvent;
+
 pub struct CreateOrUnspawnEdgeEvent {
     pub neighbor_nodes: NeighborNodes,
     pub path: Path,
+    pub directed: bool,
+}
+
+/// Distance between neighboring parallel edges, and between nested loops.
+pub const PARALLEL_SPACING: f32 = 24.0;
+
+/// How far a curve may stray from the segments it is split into, to
+/// measure or dash it.
+const FLATTEN_TOLERANCE: f32 = 0.5;
+
+fn arrow_head(path_builder: &mut PathBuilder, end: Vec2, direction: Vec2, radius: f32) {
+    if direction == Vec2::ZERO {
+        return;
+    }
+
+    let tip = end - direction * radius;
+    let back = tip - direction * radius;
+    let side = direction.perp() * radius / 2.0;
+
+    path_builder.move_to(back + side);
+    path_builder.line_to(tip);
+    path_builder.line_to(back - side);
+}
+
+/// A straight line between the nodes, or a curve passing `bend` away from
+/// its middle, to the left of the direction from start to end.
+pub fn edge_path(
+    start_pos: &Transform,
+    end_pos: &Transform,
+    directed: bool,
+    radius: f32,
+    bend: f32,
+) -> Path {
+    let start = start_pos.translation.truncate();
+    let end = end_pos.translation.truncate();
+
+    let mut path_builder = PathBuilder::new();
+
+    path_builder.move_to(start);
+
+    // A quadratic curve passes halfway between the middle of the line and
+    // its control point.
+    let control = (start + end) / 2.0 + (end - start).normalize_or_zero().perp() * bend * 2.0;
+
+    if bend == 0.0 {
+        path_builder.line_to(end);
+    } else {
+        path_builder.quadratic_bezier_to(control, end);
+    }
+
+    if directed {
+        arrow_head(
+            &mut path_builder,
+            end,
+            (end - control).normalize_or_zero(),
+            radius,
+        );
+    }
+
+    path_builder.build()
+}
+
+/// Where a control point given relative to an edge is, as described for
+/// `EdgeRoute::Curved`.
+pub fn control_point(start: Vec2, end: Vec2, (along, across): (f32, f32)) -> Vec2 {
+    let direction = end - start;
+
+    start + direction * along + direction.perp() * across
+}
+
+/// The control point at `point`, relative to the edge from `start` to
+/// `end`.
+pub fn relative_control_point(start: Vec2, end: Vec2, point: Vec2) -> (f32, f32) {
+    let direction = end - start;
+    let length_squared = direction.length_squared();
+
+    if length_squared == 0.0 {
+        return (0.5, 0.0);
+    }
+
+    let offset = point - start;
+
+    (
+        offset.dot(direction) / length_squared,
+        offset.dot(direction.perp()) / length_squared,
+    )
+}
+
+pub fn curved_edge_path(
+    start_pos: &Transform,
+    end_pos: &Transform,
+    directed: bool,
+    radius: f32,
+    controls: [(f32, f32); 2],
+) -> Path {
+    let start = start_pos.translation.truncate();
+    let end = end_pos.translation.truncate();
+    let [first, second] = controls.map(|control| control_point(start, end, control));
+
+    let mut path_builder = PathBuilder::new();
+
+    path_builder.move_to(start);
+    path_builder.cubic_bezier_to(first, second, end);
+
+    if directed {
+        arrow_head(
+            &mut path_builder,
+            end,
+            (end - second).normalize_or_zero(),
+            radius,
+        );
+    }
+
+    path_builder.build()
+}
+
+/// Straight segments through the given corners.
+pub fn polyline_edge_path(corners: &[Vec2], directed: bool, radius: f32) -> Path {
+    let mut path_builder = PathBuilder::new();
+
+    let (Some(&start), Some(&end)) = (corners.first(), corners.last()) else {
+        return path_builder.build();
+    };
+
+    path_builder.move_to(start);
+
+    for &corner in corners[1..].iter() {
+        path_builder.line_to(corner);
+    }
+
+    if directed && corners.len() > 1 {
+        let before = corners[corners.len() - 2];
+
+        arrow_head(
+            &mut path_builder,
+            end,
+            (end - before).normalize_or_zero(),
+            radius,
+        );
+    }
+
+    path_builder.build()
+}
+
+pub fn distance_to_segment(a: Vec2, b: Vec2, point: Vec2) -> f32 {
+    let ab = b - a;
+    let t = ((point - a).dot(ab) / ab.length_squared().max(f32::EPSILON)).clamp(0.0, 1.0);
+
+    (a + ab * t).distance(point)
+}
+
+/// The points of every sub-path of a path, with its curves split into short
+/// segments. The line of an edge comes first, then its arrow head.
+fn path_polylines(path: &Path) -> Vec<Vec<Vec2>> {
+    let mut polylines = Vec::<Vec<Vec2>>::new();
+
+    for event in path.0.iter().flattened(FLATTEN_TOLERANCE) {
+        match event {
+            PathEvent::Begin { at } => polylines.push(vec![Vec2::new(at.x, at.y)]),
+            PathEvent::Line { to, .. } => {
+                if let Some(polyline) = polylines.last_mut() {
+                    polyline.push(Vec2::new(to.x, to.y));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    polylines
+}
+
+/// How far `point` is from the closest part of a path.
+pub fn distance_to_path(path: &Path, point: Vec2) -> f32 {
+    path_polylines(path)
+        .iter()
+        .flat_map(|polyline| polyline.windows(2))
+        .map(|pair| distance_to_segment(pair[0], pair[1], point))
+        .fold(f32::INFINITY, f32::min)
+}
+
+/// Breaks the line of an edge into dashes. The arrow head is kept whole.
+pub fn dash_path(path: &mut Path, dash: Dash) {
+    let Some((on, off)) = dash.pattern() else {
+        return;
+    };
+
+    let mut path_builder = PathBuilder::new();
+
+    for (index, polyline) in path_polylines(path).into_iter().enumerate() {
+        if index > 0 {
+            path_builder.move_to(polyline[0]);
+
+            for &point in polyline[1..].iter() {
+                path_builder.line_to(point);
+            }
+
+            continue;
+        }
+
+        let mut travelled = 0.0;
+        let mut drawing = false;
+
+        for pair in polyline.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            let length = a.distance(b);
+            let mut start = 0.0;
+
+            while start < length {
+                let phase = travelled % (on + off);
+                let (dash_on, left) = if phase < on {
+                    (true, on - phase)
+                } else {
+                    (false, on + off - phase)
+                };
+                let end = (start + left).min(length);
+
+                if dash_on {
+                    if !drawing {
+                        path_builder.move_to(a.lerp(b, start / length));
+                    }
+
+                    path_builder.line_to(a.lerp(b, end / length));
+                }
+
+                drawing = dash_on;
+                travelled += end - start;
+                start = end;
+            }
+        }
+    }
+
+    *path = path_builder.build();
+}
+
+/// Control points of a loop above a node, `bend` taller than the smallest
+/// loop.
+fn loop_controls(position: Vec2, radius: f32, bend: f32) -> (Vec2, Vec2) {
+    let height = radius * 2.5 + bend;
+
+    // A cubic curve with both control points at the same height only rises
+    // to three quarters of it.
+    let control = Vec2::new(height * 0.6, height / 0.75);
+
+    (
+        position + Vec2::new(-control.x, control.y),
+        position + control,
+    )
+}
+
+pub fn loop_path(pos: &Transform, directed: bool, radius: f32, bend: f32) -> Path {
+    let position = pos.translation.truncate();
+    let (first, second) = loop_controls(position, radius, bend);
+
+    let mut path_builder = PathBuilder::new();
+
+    path_builder.move_to(position);
+    path_builder.cubic_bezier_to(first, second, position);
+
+    if directed {
+        arrow_head(
+            &mut path_builder,
+            position,
+            (position - second).normalize_or_zero(),
+            radius,
+        );
+    }
+
+    path_builder.build()
+}
+
+/// The path of an edge, drawn as a loop when both of its nodes are the
+/// same. Orthogonal edges need the positions of the other nodes, so they
+/// are drawn straight here and routed by `routing::route_edges`.
+pub fn neighbor_nodes_path(
+    neighbor_nodes: &NeighborNodes,
+    directed: bool,
+    radius: f32,
+    bend: f32,
+    route: EdgeRoute,
+) -> Path {
+    if neighbor_nodes.v == neighbor_nodes.u {
+        return loop_path(&neighbor_nodes.pos_v, directed, radius, bend);
+    }
+
+    match route {
+        EdgeRoute::Curved { controls } => curved_edge_path(
+            &neighbor_nodes.pos_v,
+            &neighbor_nodes.pos_u,
+            directed,
+            radius,
+            controls,
+        ),
+        EdgeRoute::Straight | EdgeRoute::Orthogonal => edge_path(
+            &neighbor_nodes.pos_v,
+            &neighbor_nodes.pos_u,
+            directed,
+            radius,
+            bend,
+        ),
+    }
+}
+
+/// Where the middle of an edge is drawn, to put its label there. The
+/// corners are those of an orthogonal edge.
+pub fn edge_middle(
+    neighbor_nodes: &NeighborNodes,
+    radius: f32,
+    bend: f32,
+    route: EdgeRoute,
+    corners: Option<&Corners>,
+) -> Vec2 {
+    let start = neighbor_nodes.pos_v.translation.truncate();
+    let end = neighbor_nodes.pos_u.translation.truncate();
+
+    if neighbor_nodes.v == neighbor_nodes.u {
+        let (first, second) = loop_controls(start, radius, bend);
+
+        return (start + first * 3.0 + second * 3.0 + end) / 8.0;
+    }
+
+    match (route, corners) {
+        (EdgeRoute::Curved { controls }, _) => {
+            let [first, second] = controls.map(|control| control_point(start, end, control));
+
+            (start + first * 3.0 + second * 3.0 + end) / 8.0
+        }
+        (EdgeRoute::Orthogonal, Some(corners)) => {
+            let segments = corners.0.windows(2).map(|pair| (pair[0], pair[1]));
+            let mut remaining = segments.clone().map(|(a, b)| a.distance(b)).sum::<f32>() / 2.0;
+
+            for (a, b) in segments {
+                let length = a.distance(b);
+
+                if remaining <= length {
+                    return a.lerp(b, remaining / length.max(f32::EPSILON));
+                }
+
+                remaining -= length;
+            }
+
+            end
+        }
+        _ => (start + end) / 2.0 + (end - start).normalize_or_zero().perp() * bend,
+    }
+}
+
+pub fn edge_bundle(
+    neighbor_nodes: NeighborNodes,
+    path: &Path,
+    edge_settings: &EdgeSettings,
+) -> impl Bundle {
+    (
+        neighbor_nodes,
+        GeometryBuilder::build_as(
+            path,
+            DrawMode::Stroke(StrokeMode::new(edge_settings.color, edge_settings.size)),
+            Transform::default(),
+        ),
+    )
 }
 
 pub fn emit_create_or_unspawn_edge_event(
     mut commands: Commands,
-    query: Query<(Entity, &Transform), With<SelectedNode>>,
+    query: Query<
+        (
+            Entity,
+            &Transform,
+            &Radius,
+            ChangeTrackers<SelectedNode>,
+            Option<&NodeStyle>,
+        ),
+        With<SelectedNode>,
+    >,
     mut change_color_event_writer: EventWriter<ChangeNodeColorEvent>,
     mut create_or_unspawn_edge_event_writer: EventWriter<CreateOrUnspawnEdgeEvent>,
     node_settings: Res<NodeSettings>,
+    edge_settings: Res<EdgeSettings>,
 ) {
     let mut iter = query.iter();
 
-    let Some((first_entity, start_pos)) = iter.next() else {
+    let Some(first) = iter.next() else {
         return;
     };
 
-    let Some((second_entity, end_pos)) = iter.next() else {
+    let Some(second) = iter.next() else {
         return;
     };
 
-    let mut path_builder = PathBuilder::new();
-
-    path_builder.move_to(Vec2::new(start_pos.translation.x, start_pos.translation.y));
-    path_builder.line_to(Vec2::new(end_pos.translation.x, end_pos.translation.y));
-
-    let line = path_builder.build();
+    let (
+        (first_entity, start_pos, _, _, first_style),
+        (second_entity, end_pos, end_radius, _, second_style),
+    ) = if first.3.is_added() {
+        (second, first)
+    } else {
+        (first, second)
+    };
 
     commands.entity(first_entity).remove::<SelectedNode>();
     commands.entity(second_entity).remove::<SelectedNode>();
 
     change_color_event_writer.send(ChangeNodeColorEvent {
         entity: first_entity,
-        color: node_settings.base_color,
+        color: node::node_color(first_style, &node_settings),
     });
 
     change_color_event_writer.send(ChangeNodeColorEvent {
         entity: second_entity,
-        color: node_settings.base_color,
+        color: node::node_color(second_style, &node_settings),
     });
 
+    let neighbor_nodes = NeighborNodes {
+        v: first_entity,
+        u: second_entity,
+        pos_v: *start_pos,
+        pos_u: *end_pos,
+    };
+
     create_or_unspawn_edge_event_writer.send(CreateOrUnspawnEdgeEvent {
-        neighbor_nodes: NeighborNodes {
-            v: first_entity,
-            u: second_entity,
-            pos_v: *start_pos,
-            pos_u: *end_pos,
-        },
-        path: line,
+        path: neighbor_nodes_path(
+            &neighbor_nodes,
+            edge_settings.directed,
+            end_radius.0,
+            0.0,
+            EdgeRoute::Straight,
+        ),
+        neighbor_nodes,
+        directed: edge_settings.directed,
     });
 }
 
 pub fn create_or_unspawn_edge(
     mut commands: Commands,
-    query: Query<(Entity, &NeighborNodes)>,
+    query: Query<(&NeighborNodes, Option<&Directed>)>,
     mut event_reader: EventReader<CreateOrUnspawnEdgeEvent>,
+    mut graph_changed_event_writer: EventWriter<GraphChangedEvent>,
+    mut edge_index: ResMut<EdgeIndex>,
     edge_settings: Res<EdgeSettings>,
 ) {
     'first_loop: for ev in event_reader.iter() {
-        for (entity, neighbor_nodes) in query.iter() {
-            if neighbor_nodes.u == ev.neighbor_nodes.u && neighbor_nodes.v == ev.neighbor_nodes.v
-                || neighbor_nodes.u == ev.neighbor_nodes.v
-                    && neighbor_nodes.v == ev.neighbor_nodes.u
-            {
+        graph_changed_event_writer.send(GraphChangedEvent);
+
+        let incident_edges = if edge_settings.multigraph {
+            Vec::new()
+        } else {
+            edge_index
+                .incident_edges(ev.neighbor_nodes.v)
+                .collect::<Vec<_>>()
+        };
+
+        for entity in incident_edges {
+            let Ok((neighbor_nodes, directed)) = query.get(entity) else {
+                continue;
+            };
+
+            let same_direction =
+                neighbor_nodes.u == ev.neighbor_nodes.u && neighbor_nodes.v == ev.neighbor_nodes.v;
+            let opposite_direction =
+                neighbor_nodes.u == ev.neighbor_nodes.v && neighbor_nodes.v == ev.neighbor_nodes.u;
+
+            if same_direction || (opposite_direction && !(ev.directed && directed.is_some())) {
                 commands.entity(entity).despawn();
 
+                edge_index.remove(entity);
+
                 continue 'first_loop;
             }
         }
 
-        commands.spawn((
+        let mut edge = commands.spawn(edge_bundle(
             NeighborNodes {
                 v: ev.neighbor_nodes.v,
                 u: ev.neighbor_nodes.u,
                 pos_v: ev.neighbor_nodes.pos_v,
                 pos_u: ev.neighbor_nodes.pos_u,
             },
-            GeometryBuilder::build_as(
-                &ev.path,
-                DrawMode::Stroke(StrokeMode::new(edge_settings.color, edge_settings.size)),
-                Transform::default(),
-            ),
+            &ev.path,
+            &edge_settings,
         ));
+
+        if ev.directed {
+            edge.insert(Directed);
+        }
+
+        if edge_settings.route != EdgeRoute::Straight {
+            edge.insert(Route(edge_settings.route));
+        }
+
+        edge_index.insert(edge.id(), ev.neighbor_nodes.v, ev.neighbor_nodes.u);
     }
 }
 
 pub fn update_edge_after_moving_node(
-    mut query: Query<(&mut Path, &mut NeighborNodes)>,
+    mut query: Query<(
+        &mut Path,
+        &mut NeighborNodes,
+        Option<&Directed>,
+        Option<&Bend>,
+        Option<&Route>,
+    )>,
+    radii: Query<&Radius>,
     mut event_reader: EventReader<UpdateEdgeEvent>,
+    edge_index: Res<EdgeIndex>,
+    node_settings: Res<NodeSettings>,
 ) {
-    for ev in event_reader.iter() {
-        let (changed_node, translation) = (ev.changed_node, ev.transform.translation);
-
-        let mut path_builder = PathBuilder::new();
+    let changed_nodes = event_reader
+        .iter()
+        .map(|ev| (ev.changed_node, ev.transform))
+        .collect::<HashMap<_, _>>();
 
-        path_builder.move_to(Vec2::new(translation.x, translation.y));
+    let mut changed_edges = HashSet::new();
 
-        for (mut path, mut neighbor_nodes) in query.iter_mut() {
-            let mut end_pos = None;
+    for (&changed_node, transform) in changed_nodes.iter() {
+        for edge in edge_index.incident_edges(changed_node) {
+            let Ok((_, mut neighbor_nodes, ..)) = query.get_mut(edge) else {
+                continue;
+            };
 
             if neighbor_nodes.v == changed_node {
-                end_pos = Some(neighbor_nodes.pos_u);
-
-                neighbor_nodes.pos_v = ev.transform;
-            } else if neighbor_nodes.u == changed_node {
-                end_pos = Some(neighbor_nodes.pos_v);
+                neighbor_nodes.pos_v = *transform;
+            }
 
-                neighbor_nodes.pos_u = ev.transform;
+            if neighbor_nodes.u == changed_node {
+                neighbor_nodes.pos_u = *transform;
             }
 
-            if let Some(end_pos) = end_pos {
-                path_builder.line_to(Vec2::new(end_pos.translation.x, end_pos.translation.y));
+            changed_edges.insert(edge);
+        }
+    }
 
-                *path = path_builder.build();
+    for edge in changed_edges {
+        let Ok((mut path, neighbor_nodes, directed, bend, route)) = query.get_mut(edge) else {
+            continue;
+        };
 
-                path_builder = PathBuilder::new();
+        let route = route.map_or(EdgeRoute::Straight, |route| route.0);
 
-                path_builder.move_to(Vec2::new(translation.x, translation.y));
+        // Orthogonal edges go around every node, so they are routed again
+        // separately.
+        if route == EdgeRoute::Orthogonal {
+            continue;
+        }
+
+        let radius = radii
+            .get(neighbor_nodes.u)
+            .map_or(node_settings.radius, |radius| radius.0);
+
+        *path = neighbor_nodes_path(
+            &neighbor_nodes,
+            directed.is_some(),
+            radius,
+            bend.map_or(0.0, |bend| bend.0),
+            route,
+        );
+    }
+}
+
+/// Gives every edge between the same two nodes its own curve, and every
+/// loop on a node its own size. Runs after the commands of the frame are
+/// applied, so that the edges spawned or despawned during the frame are
+/// counted.
+pub fn spread_parallel_edges(
+    mut commands: Commands,
+    mut query: Query<(
+        Entity,
+        &NeighborNodes,
+        &mut Path,
+        Option<&Directed>,
+        Option<&Bend>,
+        Option<&Route>,
+    )>,
+    added_edges: Query<(), Added<NeighborNodes>>,
+    removed_edges: RemovedComponents<NeighborNodes>,
+    radii: Query<&Radius>,
+    node_settings: Res<NodeSettings>,
+) {
+    if added_edges.is_empty() && removed_edges.iter().next().is_none() {
+        return;
+    }
+
+    let mut bundles = HashMap::<(Entity, Entity), Vec<Entity>>::new();
+
+    for (entity, neighbor_nodes, ..) in query.iter() {
+        let (v, u) = (neighbor_nodes.v, neighbor_nodes.u);
+
+        bundles
+            .entry((v.min(u), v.max(u)))
+            .or_default()
+            .push(entity);
+    }
+
+    for ((first, second), mut edges) in bundles {
+        edges.sort_unstable();
+
+        let count = edges.len();
+
+        for (index, edge) in edges.into_iter().enumerate() {
+            let Ok((_, neighbor_nodes, mut path, directed, old_bend, route)) = query.get_mut(edge)
+            else {
+                continue;
+            };
+
+            // The curves are spread on both sides of the straight line, and
+            // turned around for the edges going from the second node to the
+            // first, so that all of them are counted on the same side.
+            let bend = if first == second {
+                index as f32 * PARALLEL_SPACING
+            } else if neighbor_nodes.v == first {
+                (index as f32 - (count - 1) as f32 / 2.0) * PARALLEL_SPACING
+            } else {
+                ((count - 1) as f32 / 2.0 - index as f32) * PARALLEL_SPACING
+            };
+
+            if old_bend.map_or(first != second && bend == 0.0, |old_bend| {
+                old_bend.0 == bend
+            }) {
+                continue;
+            }
+
+            commands.entity(edge).insert(Bend(bend));
+
+            let route = route.map_or(EdgeRoute::Straight, |route| route.0);
+
+            if route == EdgeRoute::Orthogonal {
+                continue;
             }
+
+            let radius = radii
+                .get(neighbor_nodes.u)
+                .map_or(node_settings.radius, |radius| radius.0);
+
+            *path = neighbor_nodes_path(neighbor_nodes, directed.is_some(), radius, bend, route);
         }
     }
 }
 
+pub fn delete_edge(
+    mut commands: Commands,
+    query: Query<&NeighborNodes>,
+    mut event_reader: EventReader<DeleteEdgeEvent>,
+    mut graph_changed_event_writer: EventWriter<GraphChangedEvent>,
+    mut edge_index: ResMut<EdgeIndex>,
+) {
+    for ev in event_reader.iter() {
+        let Some(edge) = edge_index
+            .incident_edges(ev.v)
+            .filter(|&edge| {
+                query.get(edge).map_or(false, |neighbor_nodes| {
+                    (neighbor_nodes.v, neighbor_nodes.u) == (ev.v, ev.u)
+                        || (neighbor_nodes.v, neighbor_nodes.u) == (ev.u, ev.v)
+                })
+            })
+            .max()
+        else {
+            continue;
+        };
+
+        commands.entity(edge).despawn();
+
+        edge_index.remove(edge);
+
+        graph_changed_event_writer.send(GraphChangedEvent);
+    }
+}
+
+pub fn toggle_directed_edges(
+    input: Res<Input<KeyCode>>,
+    mut edge_settings: ResMut<EdgeSettings>,
+    visualizer_state: Res<VisualizerState>,
+) {
+    if visualizer_state.is_typing || !input.just_released(KeyCode::D) {
+        return;
+    }
+
+    edge_settings.directed = !edge_settings.directed;
+
+    info!(
+        "New edges are now {}",
+        if edge_settings.directed {
+            "directed"
+        } else {
+            "undirected"
+        }
+    );
+}
+
+pub fn toggle_multigraph(
+    input: Res<Input<KeyCode>>,
+    mut edge_settings: ResMut<EdgeSettings>,
+    visualizer_state: Res<VisualizerState>,
+) {
+    // Ctrl + M exports the graph instead.
+    if visualizer_state.is_typing
+        || input.any_pressed([KeyCode::LControl, KeyCode::RControl])
+        || !input.just_released(KeyCode::M)
+    {
+        return;
+    }
+
+    edge_settings.multigraph = !edge_settings.multigraph;
+
+    info!(
+        "Multigraph mode is now {}",
+        if edge_settings.multigraph {
+            "on"
+        } else {
+            "off"
+        }
+    );
+}
+
 pub fn remove_edge_after_remove_node(
     mut commands: Commands,
-    query: Query<(Entity, &NeighborNodes)>,
     mut event_reader: EventReader<RemoveEdgeEvent>,
+    mut graph_changed_event_writer: EventWriter<GraphChangedEvent>,
+    mut edge_index: ResMut<EdgeIndex>,
 ) {
     for ev in event_reader.iter() {
-        let removed_node = ev.removed_node;
+        graph_changed_event_writer.send(GraphChangedEvent);
 
-        for (entity, neighbor_nodes) in query.iter() {
-            if neighbor_nodes.v == removed_node || neighbor_nodes.u == removed_node {
-                commands.entity(entity).despawn();
-            }
+        for entity in edge_index.remove_node(ev.removed_node) {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+pub fn change_edge_color(
+    mut query: Query<(&mut DrawMode, Option<&EdgeStyle>), With<NeighborNodes>>,
+    mut event_reader: EventReader<ChangeEdgeColorEvent>,
+    edge_settings: Res<EdgeSettings>,
+) {
+    for ev in event_reader.iter() {
+        if let Ok((mut draw_mode, style)) = query.get_mut(ev.entity) {
+            let width = style.map_or(edge_settings.size, |style| style.width);
+
+            *draw_mode = DrawMode::Stroke(StrokeMode::new(ev.color, width));
         }
     }
 }
@@ -0,0 +1,59 @@
+use std::fs;
+
+use bevy::prelude::*;
+
+use crate::{
+    formats::{graph6, mermaid, plantuml, visualizer},
+    resources::VisualizerState,
+    snapshot::GraphQuery,
+};
+
+fn write_export(path: &str, contents: String) {
+    match fs::write(path, contents) {
+        Ok(()) => info!("Graph exported to {path}"),
+        Err(err) => error!("Can not export the graph to {path}: {err}"),
+    }
+}
+
+pub fn export_graph(
+    input: Res<Input<KeyCode>>,
+    graph_query: GraphQuery,
+    visualizer_state: Res<VisualizerState>,
+) {
+    if visualizer_state.is_typing || !input.any_pressed([KeyCode::LControl, KeyCode::RControl]) {
+        return;
+    }
+
+    if input.just_released(KeyCode::M) {
+        write_export("graph.mmd", mermaid::write(&graph_query.snapshot().graph));
+    }
+
+    if input.just_released(KeyCode::U) {
+        write_export("graph.puml", plantuml::write(&graph_query.snapshot().graph));
+    }
+
+    if input.just_released(KeyCode::S) {
+        write_export(
+            "graph.gvis",
+            visualizer::write(&graph_query.snapshot().graph),
+        );
+    }
+
+    if input.just_released(KeyCode::G) {
+        let graph = graph_query.snapshot().graph;
+
+        if graph.has_attributes() {
+            warn!("graph6, sparse6 and digraph6 only keep the structure, the attributes are not exported");
+        }
+
+        let contents = graph6::write(&graph);
+
+        let path = match contents.chars().next() {
+            Some(':') => "graph.s6",
+            Some('&') => "graph.d6",
+            _ => "graph.g6",
+        };
+
+        write_export(path, contents);
+    }
+}
@@ -0,0 +1,348 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::flow::{self, FlowRun},
+    components::{Label, Node},
+    resources::{
+        HighlightLayer, HighlightSettings, Highlights, InteractionMode, Tool, UiState,
+        VisualizerState,
+    },
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::{node::NodePickedEvent, playback::Playback, weights::WeightTable};
+
+const EPSILON: f32 = 1e-6;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FlowAlgorithm {
+    EdmondsKarp,
+    Dinic,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Terminal {
+    Source,
+    Sink,
+}
+
+pub struct FlowAnimation {
+    algorithm: FlowAlgorithm,
+    snapshot: GraphSnapshot,
+    run: FlowRun,
+    step: usize,
+}
+
+impl FlowAnimation {
+    fn is_finished(&self) -> bool {
+        self.step == self.run.steps.len()
+    }
+
+    fn value_so_far(&self) -> f32 {
+        self.run.steps[..self.step]
+            .iter()
+            .map(|step| step.amount)
+            .sum()
+    }
+
+    fn cut_edges(&self) -> Vec<usize> {
+        let graph = &self.snapshot.graph;
+        let source_side = &self.run.source_side;
+
+        (0..graph.edges.len())
+            .filter(|&index| {
+                let edge = &graph.edges[index];
+
+                (source_side[edge.source] && !source_side[edge.target])
+                    || (!edge.directed && !source_side[edge.source] && source_side[edge.target])
+            })
+            .collect()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct FlowState {
+    pub source: Option<Entity>,
+    pub sink: Option<Entity>,
+    pub picking: Option<Terminal>,
+    pub animation: Option<FlowAnimation>,
+    pub playback: Playback,
+}
+
+fn highlight(
+    state: &FlowState,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    for (terminal, color) in [(state.source, 0), (state.sink, 1)] {
+        if let Some(entity) = terminal {
+            highlights.nodes.insert(entity, utils::palette_color(color));
+        }
+    }
+
+    let Some(animation) = state.animation.as_ref() else {
+        return;
+    };
+
+    let GraphSnapshot {
+        graph,
+        nodes,
+        edges,
+    } = &animation.snapshot;
+
+    let flows = match animation.step.checked_sub(1) {
+        Some(step) => animation.run.steps[step].flows.clone(),
+        None => vec![0.0; graph.edges.len()],
+    };
+
+    for (index, &flow) in flows.iter().enumerate() {
+        let capacity = graph.weight(index).max(0.0);
+
+        highlights
+            .edge_labels
+            .insert(edges[index], format!("{}/{capacity}", flow.abs()));
+
+        if flow.abs() >= capacity - EPSILON && capacity > 0.0 {
+            highlights
+                .edges
+                .insert(edges[index], highlight_settings.critical_color);
+        } else if flow.abs() > EPSILON {
+            highlights
+                .edges
+                .insert(edges[index], highlight_settings.accepted_color);
+        }
+    }
+
+    if let Some(step) = animation.step.checked_sub(1) {
+        for &edge in animation.run.steps[step].path.iter() {
+            highlights
+                .edges
+                .insert(edges[edge], highlight_settings.current_color);
+        }
+    }
+
+    if !animation.is_finished() {
+        return;
+    }
+
+    for (node, &source_side) in animation.run.source_side.iter().enumerate() {
+        let color = if source_side { 0 } else { 1 };
+
+        highlights
+            .nodes
+            .insert(nodes[node], utils::palette_color(color));
+    }
+
+    for edge in animation.cut_edges() {
+        highlights
+            .edges
+            .insert(edges[edge], highlight_settings.rejected_color);
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_flow_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut flow_state: ResMut<FlowState>,
+    mut visualizer_state: ResMut<VisualizerState>,
+    mut highlights: ResMut<Highlights>,
+    mut event_reader: EventReader<NodePickedEvent>,
+    graph_query: GraphQuery,
+    labels: Query<&Label, With<Node>>,
+    highlight_settings: Res<HighlightSettings>,
+    time: Res<Time>,
+    mut capacities: WeightTable,
+) {
+    let mut open = ui_state.is_open(Tool::MaximumFlow);
+    let state = &mut *flow_state;
+
+    if !open {
+        if state.source.is_some() || state.sink.is_some() || state.animation.is_some() {
+            *state = FlowState::default();
+            highlights.clear(Tool::MaximumFlow);
+        }
+
+        if visualizer_state.interaction_mode == InteractionMode::PickNode {
+            visualizer_state.interaction_mode = InteractionMode::Edit;
+        }
+
+        return;
+    }
+
+    let mut changed = false;
+
+    for ev in event_reader.iter() {
+        match state.picking.take() {
+            Some(Terminal::Source) => state.source = Some(ev.entity),
+            Some(Terminal::Sink) => state.sink = Some(ev.entity),
+            None => continue,
+        }
+
+        state.animation = None;
+        state.playback.playing = false;
+        changed = true;
+    }
+
+    if visualizer_state.interaction_mode != InteractionMode::PickNode {
+        state.picking = None;
+    }
+
+    if let Some(animation) = state.animation.as_mut() {
+        changed |=
+            state
+                .playback
+                .tick(time.delta(), &mut animation.step, animation.run.steps.len());
+    }
+
+    let name = |entity: Option<Entity>| {
+        entity
+            .and_then(|entity| labels.get(entity).ok())
+            .map_or_else(|| "none".to_string(), |label| label.0.clone())
+    };
+
+    egui::Window::new(Tool::MaximumFlow.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            for (terminal, title, entity) in [
+                (Terminal::Source, "Source", state.source),
+                (Terminal::Sink, "Sink", state.sink),
+            ] {
+                ui.horizontal(|ui| {
+                    ui.label(format!("{title}: {}", name(entity)));
+
+                    let picking = state.picking == Some(terminal);
+                    let text = if picking { "Click a node" } else { "Pick" };
+
+                    if ui.add_enabled(!picking, egui::Button::new(text)).clicked() {
+                        state.picking = Some(terminal);
+                        visualizer_state.interaction_mode = InteractionMode::PickNode;
+                    }
+                });
+            }
+
+            ui.label("Edge weights are the capacities (1 when missing).");
+
+            if capacities.show(ui, "Edge capacities", true) && state.animation.take().is_some() {
+                state.playback.playing = false;
+                changed = true;
+            }
+
+            ui.horizontal(|ui| {
+                let ready = match (state.source, state.sink) {
+                    (Some(source), Some(sink)) => {
+                        source != sink && labels.contains(source) && labels.contains(sink)
+                    }
+                    _ => false,
+                };
+
+                for (algorithm, text) in [
+                    (FlowAlgorithm::EdmondsKarp, "Run Edmonds-Karp"),
+                    (FlowAlgorithm::Dinic, "Run Dinic"),
+                ] {
+                    if !ui.add_enabled(ready, egui::Button::new(text)).clicked() {
+                        continue;
+                    }
+
+                    let snapshot = graph_query.snapshot();
+                    let position = |entity: Option<Entity>| {
+                        snapshot.nodes.iter().position(|node| Some(*node) == entity)
+                    };
+
+                    let (Some(source), Some(sink)) = (position(state.source), position(state.sink))
+                    else {
+                        continue;
+                    };
+
+                    let run = match algorithm {
+                        FlowAlgorithm::EdmondsKarp => {
+                            flow::edmonds_karp(&snapshot.graph, source, sink)
+                        }
+                        FlowAlgorithm::Dinic => flow::dinic(&snapshot.graph, source, sink),
+                    };
+
+                    state.animation = Some(FlowAnimation {
+                        algorithm,
+                        snapshot,
+                        run,
+                        step: 0,
+                    });
+                    state.playback.playing = false;
+                    changed = true;
+                }
+            });
+
+            let Some(animation) = state.animation.as_mut() else {
+                return;
+            };
+
+            ui.separator();
+
+            changed |= state
+                .playback
+                .controls(ui, &mut animation.step, animation.run.steps.len());
+
+            if let Some(step) = animation.step.checked_sub(1) {
+                let step = &animation.run.steps[step];
+
+                if animation.algorithm == FlowAlgorithm::Dinic {
+                    ui.label(format!("Phase {}", step.phase + 1));
+                }
+
+                ui.label(format!(
+                    "Augmenting path of {} edges: +{}",
+                    step.path.len(),
+                    step.amount
+                ));
+            }
+
+            if !animation.is_finished() {
+                ui.label(format!("Flow so far: {}", animation.value_so_far()));
+
+                return;
+            }
+
+            let graph = &animation.snapshot.graph;
+            let cut_edges = animation.cut_edges();
+
+            ui.label(format!("Maximum flow: {}", animation.run.value));
+            ui.label(format!(
+                "Minimum cut: {} edges, capacity {}",
+                cut_edges.len(),
+                cut_edges
+                    .iter()
+                    .map(|&edge| graph.weight(edge).max(0.0))
+                    .sum::<f32>()
+            ));
+
+            egui::ScrollArea::vertical()
+                .max_height(150.0)
+                .show(ui, |ui| {
+                    for &edge in cut_edges.iter() {
+                        let graph_edge = &graph.edges[edge];
+
+                        ui.label(format!(
+                            "{} - {}",
+                            graph.nodes[graph_edge.source].label,
+                            graph.nodes[graph_edge.target].label
+                        ));
+                    }
+                });
+        });
+
+    if !open {
+        ui_state.set_open(Tool::MaximumFlow, false);
+    }
+
+    if changed {
+        highlight(
+            state,
+            highlights.layer(Tool::MaximumFlow),
+            &highlight_settings,
+        );
+    }
+}
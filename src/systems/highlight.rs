@@ -0,0 +1,115 @@
+use bevy::prelude::*;
+
+use crate::{
+    components::{
+        EdgeStyle, MovingNode, NeighborNodes, Node, NodeStyle, SelectedEdge, SelectedNode,
+    },
+    resources::{EdgeSettings, HighlightLayer, Highlights, NodeSettings},
+};
+
+use super::{
+    edges::ChangeEdgeColorEvent,
+    node::{self, ChangeNodeColorEvent, ChangeNodeRadiusEvent},
+};
+
+/// Sends color and radius events for the nodes and edges whose highlight
+/// changed since the last time, restoring the regular colors and radius of
+/// the ones that lost it.
+#[allow(clippy::too_many_arguments)]
+pub fn apply_highlights(
+    nodes: Query<
+        (
+            Option<&SelectedNode>,
+            Option<&MovingNode>,
+            Option<&NodeStyle>,
+        ),
+        With<Node>,
+    >,
+    edges: Query<(Option<&EdgeStyle>, Option<&SelectedEdge>), With<NeighborNodes>>,
+    mut node_event_writer: EventWriter<ChangeNodeColorEvent>,
+    mut radius_event_writer: EventWriter<ChangeNodeRadiusEvent>,
+    mut edge_event_writer: EventWriter<ChangeEdgeColorEvent>,
+    highlights: Res<Highlights>,
+    node_settings: Res<NodeSettings>,
+    edge_settings: Res<EdgeSettings>,
+    mut applied: Local<HighlightLayer>,
+) {
+    if !highlights.is_changed() {
+        return;
+    }
+
+    let highlights = highlights.merged();
+
+    for (&entity, _) in applied.nodes.iter() {
+        if highlights.nodes.contains_key(&entity) {
+            continue;
+        }
+
+        let Ok((selected_node, moving_node, style)) = nodes.get(entity) else {
+            continue;
+        };
+
+        node_event_writer.send(ChangeNodeColorEvent {
+            entity,
+            color: if moving_node.is_some() {
+                node_settings.moving_color
+            } else if selected_node.is_some() {
+                node_settings.selected_color
+            } else {
+                node::node_color(style, &node_settings)
+            },
+        });
+    }
+
+    for (&entity, &color) in highlights.nodes.iter() {
+        if applied.nodes.get(&entity) != Some(&color) && nodes.contains(entity) {
+            node_event_writer.send(ChangeNodeColorEvent { entity, color });
+        }
+    }
+
+    for (&entity, _) in applied.edges.iter() {
+        if highlights.edges.contains_key(&entity) {
+            continue;
+        }
+
+        let Ok((style, selected_edge)) = edges.get(entity) else {
+            continue;
+        };
+
+        edge_event_writer.send(ChangeEdgeColorEvent {
+            entity,
+            color: if selected_edge.is_some() {
+                edge_settings.selected_color
+            } else {
+                style.map_or(edge_settings.color, |style| style.color)
+            },
+        });
+    }
+
+    for (&entity, &color) in highlights.edges.iter() {
+        if applied.edges.get(&entity) != Some(&color) && edges.contains(entity) {
+            edge_event_writer.send(ChangeEdgeColorEvent { entity, color });
+        }
+    }
+
+    for (&entity, _) in applied.node_radii.iter() {
+        if highlights.node_radii.contains_key(&entity) {
+            continue;
+        }
+
+        if let Ok((.., style)) = nodes.get(entity) {
+            radius_event_writer.send(ChangeNodeRadiusEvent {
+                entity,
+                radius: node::node_radius(style, &node_settings),
+            });
+        }
+    }
+
+    for (&entity, &radius) in highlights.node_radii.iter() {
+        if applied.node_radii.get(&entity) != Some(&radius) && nodes.contains(entity) {
+            radius_event_writer.send(ChangeNodeRadiusEvent { entity, radius });
+        }
+    }
+
+    *applied = highlights;
+}
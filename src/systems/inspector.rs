@@ -0,0 +1,184 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    components::{Attributes, Label, NeighborNodes, SelectedEdge, SelectedNode},
+    graph::AttributeValue,
+    resources::{Tool, UiState},
+};
+
+#[derive(Resource)]
+pub struct InspectorState {
+    /// The node or edge inspected last, so that the new attribute being typed
+    /// is only reset when another one is selected.
+    inspecting: Option<Entity>,
+    name: String,
+    type_name: &'static str,
+    value: String,
+    error: Option<String>,
+}
+
+impl Default for InspectorState {
+    fn default() -> Self {
+        Self {
+            inspecting: None,
+            name: String::new(),
+            type_name: AttributeValue::TYPE_NAMES[0],
+            value: String::new(),
+            error: None,
+        }
+    }
+}
+
+/// Shows and edits the attributes of the selected node or, when no node is
+/// selected, of the selected edge.
+pub fn show_inspector_window(
+    mut commands: Commands,
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut inspector_state: ResMut<InspectorState>,
+    selected_nodes: Query<(Entity, &Label, Option<&Attributes>), With<SelectedNode>>,
+    selected_edges: Query<(Entity, &NeighborNodes, Option<&Attributes>), With<SelectedEdge>>,
+    labels: Query<&Label, Without<NeighborNodes>>,
+) {
+    let mut open = ui_state.is_open(Tool::Inspector);
+    let state = &mut *inspector_state;
+
+    if !open {
+        state.inspecting = None;
+
+        return;
+    }
+
+    let node_label = |node| labels.get(node).map_or("?", |label| label.0.as_str());
+    let selected = selected_nodes
+        .iter()
+        .next()
+        .map(|(entity, label, attributes)| (entity, format!("Node {}", label.0), attributes))
+        .or_else(|| {
+            selected_edges
+                .iter()
+                .next()
+                .map(|(entity, neighbor_nodes, attributes)| {
+                    let title = format!(
+                        "Edge {} - {}",
+                        node_label(neighbor_nodes.v),
+                        node_label(neighbor_nodes.u)
+                    );
+
+                    (entity, title, attributes)
+                })
+        });
+
+    if selected.as_ref().map(|(entity, ..)| *entity) != state.inspecting {
+        state.inspecting = selected.as_ref().map(|(entity, ..)| *entity);
+        state.name.clear();
+        state.value.clear();
+        state.error = None;
+    }
+
+    let mut new_attributes = None;
+
+    egui::Window::new(Tool::Inspector.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let Some((_, title, attributes)) = &selected else {
+                ui.label("Middle-click a node or click an edge to see its attributes.");
+
+                return;
+            };
+
+            let old_attributes =
+                attributes.map_or_else(Default::default, |attributes| attributes.0.clone());
+            let mut attributes = old_attributes.clone();
+            let mut removed = None;
+
+            ui.label(title.as_str());
+            ui.separator();
+
+            egui::Grid::new("attributes").show(ui, |ui| {
+                for (name, value) in attributes.iter_mut() {
+                    ui.label(name);
+
+                    match value {
+                        AttributeValue::Text(text) => {
+                            ui.text_edit_singleline(text);
+                        }
+                        AttributeValue::Number(number) => {
+                            ui.add(egui::DragValue::new(number).speed(0.1));
+                        }
+                        AttributeValue::Bool(value) => {
+                            ui.checkbox(value, "");
+                        }
+                    }
+
+                    if ui.button("Remove").clicked() {
+                        removed = Some(name.clone());
+                    }
+
+                    ui.end_row();
+                }
+            });
+
+            if let Some(name) = removed {
+                attributes.remove(&name);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut state.name).hint_text("name"));
+
+                egui::ComboBox::from_id_source("attribute type")
+                    .selected_text(state.type_name)
+                    .show_ui(ui, |ui| {
+                        for type_name in AttributeValue::TYPE_NAMES {
+                            ui.selectable_value(&mut state.type_name, type_name, type_name);
+                        }
+                    });
+
+                ui.add(egui::TextEdit::singleline(&mut state.value).hint_text("value"));
+            });
+
+            if ui.button("Add attribute").clicked() {
+                let name = state.name.trim();
+
+                let added = if name.is_empty() {
+                    Err("The name can not be empty".to_string())
+                } else if name.contains('=') {
+                    Err("The name can not contain =".to_string())
+                } else {
+                    AttributeValue::parse(state.type_name, &state.value)
+                };
+
+                match added {
+                    Ok(value) => {
+                        attributes.insert(name.to_string(), value);
+                        state.name.clear();
+                        state.value.clear();
+                        state.error = None;
+                    }
+                    Err(error) => state.error = Some(error),
+                }
+            }
+
+            if let Some(error) = state.error.as_ref() {
+                ui.label(error);
+            }
+
+            if attributes != old_attributes {
+                new_attributes = Some(attributes);
+            }
+        });
+
+    if let (Some((entity, ..)), Some(attributes)) = (selected, new_attributes) {
+        if attributes.is_empty() {
+            commands.entity(entity).remove::<Attributes>();
+        } else {
+            commands.entity(entity).insert(Attributes(attributes));
+        }
+    }
+
+    if !open {
+        ui_state.set_open(Tool::Inspector, false);
+    }
+}
@@ -0,0 +1,194 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::matching::{self, Bipartition},
+    resources::{HighlightLayer, HighlightSettings, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+pub enum MatchingResult {
+    Bipartite {
+        sides: [usize; 2],
+    },
+    OddCycle {
+        nodes: Vec<String>,
+    },
+    Matching {
+        algorithm: &'static str,
+        size: usize,
+        unmatched: Vec<String>,
+    },
+}
+
+#[derive(Resource, Default)]
+pub struct MatchingState {
+    pub result: Option<MatchingResult>,
+}
+
+fn labels(snapshot: &GraphSnapshot, nodes: &[usize]) -> Vec<String> {
+    nodes
+        .iter()
+        .map(|&node| snapshot.graph.nodes[node].label.clone())
+        .collect()
+}
+
+fn check_bipartite(
+    snapshot: &GraphSnapshot,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) -> MatchingResult {
+    match matching::bipartition(&snapshot.graph) {
+        Bipartition::Sides(sides) => {
+            for (&entity, &side) in snapshot.nodes.iter().zip(sides.iter()) {
+                highlights
+                    .nodes
+                    .insert(entity, utils::palette_color(side as usize));
+            }
+
+            let right = sides.iter().filter(|&&side| side).count();
+
+            MatchingResult::Bipartite {
+                sides: [sides.len() - right, right],
+            }
+        }
+        Bipartition::OddCycle { nodes, edges } => {
+            for &node in nodes.iter() {
+                highlights
+                    .nodes
+                    .insert(snapshot.nodes[node], highlight_settings.rejected_color);
+            }
+
+            for &edge in edges.iter() {
+                highlights
+                    .edges
+                    .insert(snapshot.edges[edge], highlight_settings.rejected_color);
+            }
+
+            MatchingResult::OddCycle {
+                nodes: labels(snapshot, &nodes),
+            }
+        }
+    }
+}
+
+fn maximum_matching(
+    snapshot: &GraphSnapshot,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) -> MatchingResult {
+    let graph = &snapshot.graph;
+
+    let (algorithm, edges) = match matching::bipartition(graph) {
+        Bipartition::Sides(sides) => ("Hopcroft-Karp", matching::hopcroft_karp(graph, &sides)),
+        Bipartition::OddCycle { .. } => ("Edmonds' blossom", matching::blossom(graph)),
+    };
+
+    for &edge in edges.iter() {
+        highlights
+            .edges
+            .insert(snapshot.edges[edge], highlight_settings.accepted_color);
+
+        for node in [graph.edges[edge].source, graph.edges[edge].target] {
+            highlights
+                .nodes
+                .insert(snapshot.nodes[node], highlight_settings.accepted_color);
+        }
+    }
+
+    let unmatched = matching::unmatched_nodes(graph, &edges);
+
+    for &node in unmatched.iter() {
+        highlights
+            .nodes
+            .insert(snapshot.nodes[node], highlight_settings.current_color);
+    }
+
+    MatchingResult::Matching {
+        algorithm,
+        size: edges.len(),
+        unmatched: labels(snapshot, &unmatched),
+    }
+}
+
+pub fn show_matching_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut matching_state: ResMut<MatchingState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    highlight_settings: Res<HighlightSettings>,
+) {
+    let mut open = ui_state.is_open(Tool::Matching);
+
+    if !open {
+        if matching_state.result.take().is_some() {
+            highlights.clear(Tool::Matching);
+        }
+
+        return;
+    }
+
+    egui::Window::new(Tool::Matching.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                if ui.button("Check bipartite").clicked() {
+                    highlights.clear(Tool::Matching);
+                    matching_state.result = Some(check_bipartite(
+                        &graph_query.snapshot(),
+                        highlights.layer(Tool::Matching),
+                        &highlight_settings,
+                    ));
+                }
+
+                if ui.button("Maximum matching").clicked() {
+                    highlights.clear(Tool::Matching);
+                    matching_state.result = Some(maximum_matching(
+                        &graph_query.snapshot(),
+                        highlights.layer(Tool::Matching),
+                        &highlight_settings,
+                    ));
+                }
+            });
+
+            ui.label("Edge directions and loops are ignored.");
+
+            match &matching_state.result {
+                Some(MatchingResult::Bipartite { sides }) => {
+                    ui.label(format!(
+                        "The graph is bipartite, with {} and {} nodes on each side",
+                        sides[0], sides[1]
+                    ));
+                }
+                Some(MatchingResult::OddCycle { nodes }) => {
+                    ui.label(format!(
+                        "The graph is not bipartite, it has the odd cycle {}",
+                        nodes.join(", ")
+                    ));
+                }
+                Some(MatchingResult::Matching {
+                    algorithm,
+                    size,
+                    unmatched,
+                }) => {
+                    ui.label(format!("{algorithm} matched {size} edges"));
+                    ui.label(format!("Unmatched nodes ({}):", unmatched.len()));
+
+                    egui::ScrollArea::vertical()
+                        .max_height(150.0)
+                        .show(ui, |ui| {
+                            for label in unmatched.iter() {
+                                ui.label(label.as_str());
+                            }
+                        });
+                }
+                None => {}
+            }
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Matching, false);
+    }
+}
@@ -1,4 +1,30 @@
+pub mod background;
+pub mod centrality;
+pub mod collection;
+pub mod coloring;
+pub mod communities;
+pub mod connectivity;
+pub mod diagnostics;
+pub mod edge_editor;
 pub mod edges;
+pub mod export;
+pub mod flow;
+pub mod highlight;
+pub mod inspector;
+pub mod matching;
+pub mod mst;
 pub mod node;
+pub mod node_style;
+pub mod planarity;
+pub mod playback;
+pub mod routing;
 pub mod setup;
+pub mod shortest_paths;
+pub mod statistics;
+pub mod style_rules;
+pub mod topological;
+pub mod tours;
+pub mod transformations;
+pub mod ui;
+pub mod weights;
 pub mod window;
@@ -0,0 +1,301 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::mst::{self, MstAction, MstRun},
+    components::SelectedNode,
+    graph::Graph,
+    resources::{HighlightLayer, HighlightSettings, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::{playback::Playback, weights::WeightTable};
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum MstAlgorithm {
+    Prim,
+    Kruskal,
+}
+
+pub struct MstAnimation {
+    algorithm: MstAlgorithm,
+    snapshot: GraphSnapshot,
+    run: MstRun,
+    step: usize,
+}
+
+impl MstAnimation {
+    fn is_finished(&self) -> bool {
+        self.step == self.run.steps.len()
+    }
+
+    fn weight_so_far(&self) -> f32 {
+        self.run.steps[..self.step]
+            .iter()
+            .filter_map(|step| match step.action {
+                MstAction::Accept(edge) => Some(self.snapshot.graph.weight(edge)),
+                _ => None,
+            })
+            .sum()
+    }
+
+    fn tree_count(&self) -> usize {
+        self.snapshot.graph.nodes.len() - self.run.tree_edges.len()
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct MstState {
+    pub animation: Option<MstAnimation>,
+    pub playback: Playback,
+}
+
+fn edge_name(graph: &Graph, edge: usize) -> String {
+    let graph_edge = &graph.edges[edge];
+
+    format!(
+        "{} - {} ({})",
+        graph.nodes[graph_edge.source].label,
+        graph.nodes[graph_edge.target].label,
+        graph.weight(edge)
+    )
+}
+
+fn highlight(
+    animation: &MstAnimation,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    let GraphSnapshot {
+        graph,
+        nodes,
+        edges,
+    } = &animation.snapshot;
+    let steps = &animation.run.steps[..animation.step];
+
+    for step in steps {
+        match step.action {
+            MstAction::Start(node) => {
+                highlights
+                    .nodes
+                    .insert(nodes[node], highlight_settings.accepted_color);
+            }
+            MstAction::Accept(edge) => {
+                highlights
+                    .edges
+                    .insert(edges[edge], highlight_settings.accepted_color);
+
+                if animation.algorithm == MstAlgorithm::Prim {
+                    for node in [graph.edges[edge].source, graph.edges[edge].target] {
+                        highlights
+                            .nodes
+                            .insert(nodes[node], highlight_settings.accepted_color);
+                    }
+                }
+            }
+            MstAction::Reject(edge) => {
+                highlights
+                    .edges
+                    .insert(edges[edge], highlight_settings.rejected_color);
+            }
+        }
+    }
+
+    let Some(last) = steps.last() else {
+        return;
+    };
+
+    match last.action {
+        MstAction::Start(node) => {
+            highlights
+                .nodes
+                .insert(nodes[node], highlight_settings.current_color);
+        }
+        MstAction::Accept(edge) | MstAction::Reject(edge) => {
+            highlights
+                .edges
+                .insert(edges[edge], highlight_settings.current_color);
+        }
+    }
+
+    if animation.algorithm == MstAlgorithm::Kruskal {
+        let mut sizes = HashMap::new();
+
+        for &component in last.components.iter() {
+            *sizes.entry(component).or_insert(0) += 1;
+        }
+
+        for (node, &component) in last.components.iter().enumerate() {
+            if sizes[&component] > 1 {
+                highlights
+                    .nodes
+                    .insert(nodes[node], utils::palette_color(component));
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_mst_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut mst_state: ResMut<MstState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    selected_nodes: Query<Entity, With<SelectedNode>>,
+    highlight_settings: Res<HighlightSettings>,
+    time: Res<Time>,
+    mut weights: WeightTable,
+) {
+    let mut open = ui_state.is_open(Tool::MinimumSpanningTree);
+
+    if !open {
+        if mst_state.animation.take().is_some() {
+            mst_state.playback.playing = false;
+            highlights.clear(Tool::MinimumSpanningTree);
+        }
+
+        return;
+    }
+
+    let mut changed = false;
+    let mst_state = &mut *mst_state;
+
+    if let Some(animation) = mst_state.animation.as_mut() {
+        changed =
+            mst_state
+                .playback
+                .tick(time.delta(), &mut animation.step, animation.run.steps.len());
+    }
+
+    egui::Window::new(Tool::MinimumSpanningTree.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for (algorithm, name) in [
+                    (MstAlgorithm::Prim, "Run Prim"),
+                    (MstAlgorithm::Kruskal, "Run Kruskal"),
+                ] {
+                    if !ui.button(name).clicked() {
+                        continue;
+                    }
+
+                    let snapshot = graph_query.snapshot();
+
+                    let run = match algorithm {
+                        MstAlgorithm::Prim => {
+                            let start = selected_nodes
+                                .iter()
+                                .find_map(|entity| {
+                                    snapshot.nodes.iter().position(|node| *node == entity)
+                                })
+                                .unwrap_or(0);
+
+                            mst::prim(&snapshot.graph, start)
+                        }
+                        MstAlgorithm::Kruskal => mst::kruskal(&snapshot.graph),
+                    };
+
+                    mst_state.animation = Some(MstAnimation {
+                        algorithm,
+                        snapshot,
+                        run,
+                        step: 0,
+                    });
+                    mst_state.playback.playing = false;
+                    changed = true;
+                }
+            });
+
+            ui.label("Prim starts from the selected node, or from the first one.");
+
+            // A run with the old weights is no longer the minimum one.
+            if weights.show(ui, "Edge weights", false) && mst_state.animation.take().is_some() {
+                mst_state.playback.playing = false;
+                highlights.clear(Tool::MinimumSpanningTree);
+            }
+
+            let Some(animation) = mst_state.animation.as_mut() else {
+                return;
+            };
+
+            ui.separator();
+
+            changed |=
+                mst_state
+                    .playback
+                    .controls(ui, &mut animation.step, animation.run.steps.len());
+
+            if animation.is_finished() {
+                let trees = animation.tree_count();
+
+                ui.label(format!("Total weight: {}", animation.run.total_weight));
+
+                if trees > 1 {
+                    ui.label(format!(
+                        "The graph is disconnected: minimum spanning forest of {trees} trees"
+                    ));
+                }
+            } else {
+                ui.label(format!("Weight so far: {}", animation.weight_so_far()));
+            }
+
+            ui.separator();
+
+            let graph = &animation.snapshot.graph;
+
+            match animation.algorithm {
+                MstAlgorithm::Prim => {
+                    ui.label("Priority queue");
+
+                    let queue = animation
+                        .step
+                        .checked_sub(1)
+                        .map(|step| animation.run.steps[step].queue.as_slice())
+                        .unwrap_or_default();
+
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for &(_, edge) in queue {
+                                ui.label(edge_name(graph, edge));
+                            }
+                        });
+                }
+                MstAlgorithm::Kruskal => {
+                    ui.label("Sorted edges");
+
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for (index, &edge) in animation.run.sorted_edges.iter().enumerate() {
+                                let status = match animation.run.steps[index].action {
+                                    _ if index >= animation.step => "",
+                                    MstAction::Accept(_) => "accepted",
+                                    _ => "rejected",
+                                };
+
+                                ui.label(format!("{} {status}", edge_name(graph, edge)));
+                            }
+                        });
+                }
+            }
+        });
+
+    if !open {
+        ui_state.set_open(Tool::MinimumSpanningTree, false);
+    }
+
+    if changed {
+        if let Some(animation) = mst_state.animation.as_ref() {
+            highlight(
+                animation,
+                highlights.layer(Tool::MinimumSpanningTree),
+                &highlight_settings,
+            );
+        }
+    }
+}
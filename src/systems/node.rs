@@ -1,16 +1,126 @@
-use crate::components::{MovingNode, Node, SelectedNode};
-use crate::resources::{NodeSettings, VisualizerState};
+use std::{env, f32::consts::SQRT_2, path::PathBuf};
+
+use crate::assets::NodeAssets;
+use crate::components::{Label, MovingNode, NeighborNodes, Node, NodeStyle, Radius, SelectedNode};
+use crate::graph::{self, EdgeRoute, NodeShape};
+use crate::resources::{
+    EdgeSettings, Highlights, InteractionMode, NodeIndex, NodeSettings, VisualizerState,
+};
 use crate::utils;
 use bevy::{prelude::*, sprite::MaterialMesh2dBundle};
+use bevy_prototype_lyon::prelude::{shapes, DrawMode, GeometryBuilder, ShapeBundle, StrokeMode};
 use bevy_rapier2d::prelude::*;
 
-use super::edges::{RemoveEdgeEvent, UpdateEdgeEvent};
+use super::edges::{
+    self, CreateOrUnspawnEdgeEvent, DeleteEdgeEvent, GraphChangedEvent, RemoveEdgeEvent,
+    UpdateEdgeEvent,
+};
 
 pub struct ChangeNodeColorEvent {
     pub entity: Entity,
     pub color: Color,
 }
 
+/// Width of the outline drawn around styled nodes.
+const OUTLINE_WIDTH: f32 = 2.0;
+
+pub struct ChangeNodeRadiusEvent {
+    pub entity: Entity,
+    pub radius: f32,
+}
+
+/// Sent when a node is clicked while the interaction mode is
+/// `InteractionMode::PickNode`.
+pub struct NodePickedEvent {
+    pub entity: Entity,
+}
+
+/// Moves every listed node to a new position, e.g. after a layout.
+pub struct MoveNodesEvent {
+    pub positions: Vec<(Entity, Vec2)>,
+}
+
+/// The color a node is drawn with when it is not selected, moved or
+/// highlighted.
+pub fn node_color(style: Option<&NodeStyle>, node_settings: &NodeSettings) -> Color {
+    style.map_or(node_settings.base_color, NodeStyle::fill)
+}
+
+/// The style that draws a node as `NodeSettings` says.
+pub fn default_node_style(node_settings: &NodeSettings) -> graph::NodeStyle {
+    graph::NodeStyle {
+        shape: NodeShape::Circle,
+        radius: node_settings.radius,
+        fill: node_settings.base_color.as_rgba_f32(),
+        outline: None,
+        icon: None,
+    }
+}
+
+/// The size a node is drawn at when no tool resizes it.
+pub fn node_radius(style: Option<&NodeStyle>, node_settings: &NodeSettings) -> f32 {
+    style.map_or(node_settings.radius, |style| style.0.radius)
+}
+
+/// A collider of the given shape with a radius of 1, scaled to the size of
+/// the node with `node_collider_scale`.
+fn node_collider(shape: NodeShape) -> Collider {
+    let corners = shape
+        .corners(1.0)
+        .into_iter()
+        .map(|(x, y)| Vec2::new(x, y))
+        .collect::<Vec<_>>();
+
+    if corners.is_empty() {
+        return Collider::ball(1.0);
+    }
+
+    Collider::convex_hull(&corners).unwrap_or_else(|| Collider::ball(1.0))
+}
+
+/// The collider does not follow the scale of the node on its own, so that
+/// resizing a node only updates this component.
+fn node_collider_scale(radius: f32) -> ColliderScale {
+    ColliderScale::Absolute(Vec2::splat(radius))
+}
+
+/// The scale of a node drawn `radius` wide with the shared unit mesh.
+pub fn node_scale(radius: f32) -> Vec3 {
+    Vec3::new(radius, radius, 1.0)
+}
+
+/// The children of a node are drawn at its real size, so they undo the
+/// scale of the node.
+fn child_transform(radius: f32, z: f32) -> Transform {
+    Transform::from_xyz(0.0, 0.0, z).with_scale(node_scale(radius.recip()))
+}
+
+fn outline_bundle(shape: NodeShape, radius: f32, color: Color) -> ShapeBundle {
+    let draw_mode = DrawMode::Stroke(StrokeMode::new(color, OUTLINE_WIDTH));
+    let transform = child_transform(radius, 0.1);
+    let corners = shape.corners(radius);
+
+    if corners.is_empty() {
+        return GeometryBuilder::build_as(
+            &shapes::Circle {
+                radius,
+                center: Vec2::ZERO,
+            },
+            draw_mode,
+            transform,
+        );
+    }
+
+    GeometryBuilder::build_as(
+        &shapes::Polygon {
+            points: corners.into_iter().map(|(x, y)| Vec2::new(x, y)).collect(),
+            closed: true,
+        },
+        draw_mode,
+        transform,
+    )
+}
+
 fn fix_node_position_if_needed(
     height: f32,
     width: f32,
@@ -40,16 +150,49 @@ fn fix_node_position_if_needed(
     None
 }
 
+pub fn node_bundle(
+    x: f32,
+    y: f32,
+    node_settings: &NodeSettings,
+    node_assets: &mut NodeAssets,
+) -> impl Bundle {
+    (
+        RigidBody::Dynamic,
+        node_collider(NodeShape::Circle),
+        node_collider_scale(node_settings.radius),
+        Radius(node_settings.radius),
+        GravityScale(0.0),
+        Damping {
+            linear_damping: 20.0,
+            ..default()
+        },
+        Velocity {
+            linvel: Vec2::ZERO,
+            ..default()
+        },
+        ExternalImpulse::default(),
+        MaterialMesh2dBundle {
+            mesh: node_assets.mesh(NodeShape::Circle),
+            material: node_assets.material(node_settings.base_color),
+            transform: Transform::from_translation(Vec3::new(x, y, 1.0))
+                .with_scale(node_scale(node_settings.radius)),
+            ..default()
+        },
+        Node,
+    )
+}
+
 pub fn spawn_node(
     mut commands: Commands,
+    mut event_writer: EventWriter<GraphChangedEvent>,
     buttons: Res<Input<MouseButton>>,
     windows: Res<Windows>,
     node_settings: Res<NodeSettings>,
     visualizer_state: Res<VisualizerState>,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut node_assets: NodeAssets,
+    mut next_label: Local<usize>,
 ) {
-    if visualizer_state.is_moving_node {
+    if !visualizer_state.can_edit() {
         return;
     }
 
@@ -66,39 +209,24 @@ pub fn spawn_node(
     };
 
     commands.spawn((
-        RigidBody::Dynamic,
-        Collider::ball(node_settings.radius),
-        GravityScale(0.0),
-        Damping {
-            linear_damping: 20.0,
-            ..default()
-        },
-        Velocity {
-            linvel: Vec2::ZERO,
-            ..default()
-        },
-        MaterialMesh2dBundle {
-            mesh: meshes
-                .add(shape::Circle::new(node_settings.radius).into())
-                .into(),
-            material: materials.add(ColorMaterial::from(node_settings.base_color)),
-            transform: Transform::from_translation(Vec3::new(x, y, 1.0)),
-            ..default()
-        },
-        Node,
+        node_bundle(x, y, &node_settings, &mut node_assets),
+        Label(next_label.to_string()),
     ));
+
+    *next_label += 1;
+
+    event_writer.send(GraphChangedEvent);
 }
 
 pub fn remove_node(
     mut commands: Commands,
-    query: Query<(Entity, &Transform), With<Node>>,
     mut event_writer: EventWriter<RemoveEdgeEvent>,
     buttons: Res<Input<MouseButton>>,
     windows: Res<Windows>,
-    node_settings: Res<NodeSettings>,
-    visualizer_state: ResMut<VisualizerState>,
+    node_index: Res<NodeIndex>,
+    visualizer_state: Res<VisualizerState>,
 ) {
-    if visualizer_state.is_moving_node {
+    if !visualizer_state.can_edit() {
         return;
     }
 
@@ -114,24 +242,8 @@ pub fn remove_node(
         return;
     };
 
-    let mut entity_to_despawn = None;
-
-    for (entity, transform) in query.iter() {
-        if utils::is_mouse_on_node(
-            x,
-            y,
-            transform.translation.x,
-            transform.translation.y,
-            node_settings.radius,
-        ) {
-            entity_to_despawn = Some(entity);
-
-            break;
-        }
-    }
-
-    if let Some(entity_to_despawn) = entity_to_despawn {
-        commands.entity(entity_to_despawn).despawn();
+    if let Some(entity_to_despawn) = node_index.item_at(x, y) {
+        commands.entity(entity_to_despawn).despawn_recursive();
 
         event_writer.send(RemoveEdgeEvent {
             removed_node: entity_to_despawn,
@@ -141,14 +253,14 @@ pub fn remove_node(
 
 pub fn mark_node_to_move(
     mut commands: Commands,
-    query: Query<(Entity, &Transform), With<Node>>,
     mut event_writer: EventWriter<ChangeNodeColorEvent>,
     buttons: Res<Input<MouseButton>>,
     windows: Res<Windows>,
+    node_index: Res<NodeIndex>,
     node_settings: Res<NodeSettings>,
-    visualizer_state: Res<VisualizerState>,
+    mut visualizer_state: ResMut<VisualizerState>,
 ) {
-    if visualizer_state.is_moving_node {
+    if !visualizer_state.can_edit() {
         return;
     }
 
@@ -164,28 +276,10 @@ pub fn mark_node_to_move(
         return;
     };
 
-    let mut node_to_mark = None;
-
-    for (entity, transform) in query.iter() {
-        if utils::is_mouse_on_node(
-            x,
-            y,
-            transform.translation.x,
-            transform.translation.y,
-            node_settings.radius,
-        ) {
-            node_to_mark = Some(entity);
-
-            break;
-        }
-    }
-
-    if let Some(entity) = node_to_mark {
+    if let Some(entity) = node_index.item_at(x, y) {
         commands.entity(entity).insert(MovingNode);
 
-        commands.insert_resource(VisualizerState {
-            is_moving_node: true,
-        });
+        visualizer_state.is_moving_node = true;
 
         event_writer.send(ChangeNodeColorEvent {
             entity,
@@ -227,11 +321,11 @@ pub fn move_node(
 
 pub fn unmark_node_that_was_moving(
     mut commands: Commands,
-    query: Query<(Entity, Option<&SelectedNode>), With<MovingNode>>,
+    query: Query<(Entity, Option<&SelectedNode>, Option<&NodeStyle>), With<MovingNode>>,
     mut event_writer: EventWriter<ChangeNodeColorEvent>,
     buttons: Res<Input<MouseButton>>,
     node_settings: Res<NodeSettings>,
-    visualizer_state: Res<VisualizerState>,
+    mut visualizer_state: ResMut<VisualizerState>,
 ) {
     if !visualizer_state.is_moving_node {
         return;
@@ -241,13 +335,11 @@ pub fn unmark_node_that_was_moving(
         return;
     }
 
-    let (entity, selected_node) = query
+    let (entity, selected_node, style) = query
         .get_single()
         .expect("Unmark node that was moving: no moving entity or more than one");
 
-    commands.insert_resource(VisualizerState {
-        is_moving_node: false,
-    });
+    visualizer_state.is_moving_node = false;
 
     commands.entity(entity).remove::<MovingNode>();
 
@@ -255,21 +347,36 @@ pub fn unmark_node_that_was_moving(
         entity,
         color: match selected_node {
             Some(_) => node_settings.selected_color,
-            None => node_settings.base_color,
+            None => node_color(style, &node_settings),
         },
     });
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn mark_node_to_create_edge(
     mut commands: Commands,
-    query: Query<(Entity, &Transform, Option<&SelectedNode>), With<Node>>,
+    query: Query<
+        (
+            Option<&SelectedNode>,
+            &Transform,
+            &Radius,
+            Option<&NodeStyle>,
+        ),
+        With<Node>,
+    >,
+    selected_nodes: Query<(Entity, Option<&NodeStyle>), With<SelectedNode>>,
     mut event_writer: EventWriter<ChangeNodeColorEvent>,
+    mut create_edge_event_writer: EventWriter<CreateOrUnspawnEdgeEvent>,
+    mut delete_edge_event_writer: EventWriter<DeleteEdgeEvent>,
     windows: Res<Windows>,
     buttons: Res<Input<MouseButton>>,
+    input: Res<Input<KeyCode>>,
+    node_index: Res<NodeIndex>,
     node_settings: Res<NodeSettings>,
+    edge_settings: Res<EdgeSettings>,
     visualizer_state: Res<VisualizerState>,
 ) {
-    if visualizer_state.is_moving_node {
+    if !visualizer_state.can_edit() {
         return;
     }
 
@@ -285,40 +392,137 @@ pub fn mark_node_to_create_edge(
         return;
     };
 
-    for (entity, transform, selected_node) in query.iter() {
-        if !utils::is_mouse_on_node(
-            x,
-            y,
-            transform.translation.x,
-            transform.translation.y,
-            node_settings.radius,
-        ) {
-            continue;
-        }
+    let Some(entity) = node_index.item_at(x, y) else {
+        return;
+    };
+
+    let Ok((selected_node, transform, radius, style)) = query.get(entity) else {
+        return;
+    };
 
-        if selected_node.is_some() {
-            commands.entity(entity).remove::<SelectedNode>();
-        } else {
-            commands.entity(entity).insert(SelectedNode);
+    // Shift + middle click removes an edge between the selected node and
+    // this one (or a loop, when it is the selected node) instead.
+    if input.any_pressed([KeyCode::LShift, KeyCode::RShift]) {
+        if let Some((selected, selected_style)) = selected_nodes.iter().next() {
+            commands.entity(selected).remove::<SelectedNode>();
+
+            event_writer.send(ChangeNodeColorEvent {
+                entity: selected,
+                color: node_color(selected_style, &node_settings),
+            });
+
+            delete_edge_event_writer.send(DeleteEdgeEvent {
+                v: selected,
+                u: entity,
+            });
+
+            return;
         }
+    }
+
+    if selected_node.is_some() && edge_settings.multigraph {
+        commands.entity(entity).remove::<SelectedNode>();
 
         event_writer.send(ChangeNodeColorEvent {
             entity,
-            color: match selected_node {
-                Some(_) => node_settings.base_color,
-                None => node_settings.selected_color,
-            },
+            color: node_color(style, &node_settings),
+        });
+
+        let neighbor_nodes = NeighborNodes {
+            v: entity,
+            u: entity,
+            pos_v: *transform,
+            pos_u: *transform,
+        };
+
+        create_edge_event_writer.send(CreateOrUnspawnEdgeEvent {
+            path: edges::neighbor_nodes_path(
+                &neighbor_nodes,
+                edge_settings.directed,
+                radius.0,
+                0.0,
+                EdgeRoute::Straight,
+            ),
+            neighbor_nodes,
+            directed: edge_settings.directed,
         });
 
-        break;
+        return;
+    }
+
+    if selected_node.is_some() {
+        commands.entity(entity).remove::<SelectedNode>();
+    } else {
+        commands.entity(entity).insert(SelectedNode);
+    }
+
+    event_writer.send(ChangeNodeColorEvent {
+        entity,
+        color: match selected_node {
+            Some(_) => node_color(style, &node_settings),
+            None => node_settings.selected_color,
+        },
+    });
+}
+
+pub fn pick_node(
+    mut event_writer: EventWriter<NodePickedEvent>,
+    buttons: Res<Input<MouseButton>>,
+    windows: Res<Windows>,
+    node_index: Res<NodeIndex>,
+    mut visualizer_state: ResMut<VisualizerState>,
+) {
+    if visualizer_state.interaction_mode != InteractionMode::PickNode
+        || visualizer_state.is_pointer_over_ui
+    {
+        return;
+    }
+
+    if !buttons.just_released(MouseButton::Left) {
+        return;
+    }
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+
+    let Some((x, y)) = utils::get_mouse_coordinates(window) else {
+        return;
+    };
+
+    if let Some(entity) = node_index.item_at(x, y) {
+        visualizer_state.interaction_mode = InteractionMode::Edit;
+
+        event_writer.send(NodePickedEvent { entity });
+    }
+}
+
+pub fn move_nodes(
+    mut query: Query<&mut Transform, With<Node>>,
+    mut event_reader: EventReader<MoveNodesEvent>,
+    mut event_writer: EventWriter<UpdateEdgeEvent>,
+) {
+    for ev in event_reader.iter() {
+        for &(entity, position) in ev.positions.iter() {
+            let Ok(mut transform) = query.get_mut(entity) else {
+                continue;
+            };
+
+            transform.translation.x = position.x;
+            transform.translation.y = position.y;
+
+            event_writer.send(UpdateEdgeEvent {
+                changed_node: entity,
+                transform: *transform,
+            });
+        }
     }
 }
 
 pub fn fix_off_screen_node_positions(
-    mut query: Query<(Entity, &mut Transform), With<Node>>,
+    mut query: Query<(Entity, &mut Transform, &Radius), With<Node>>,
     mut event_writer: EventWriter<UpdateEdgeEvent>,
     windows: Res<Windows>,
-    node_settings: Res<NodeSettings>,
 ) {
     let window = windows
         .get_primary()
@@ -327,13 +531,13 @@ pub fn fix_off_screen_node_positions(
     let height = window.physical_height();
     let width = window.physical_width();
 
-    for (entity, mut transform) in query.iter_mut() {
+    for (entity, mut transform, radius) in query.iter_mut() {
         let Some((new_x, new_y)) = fix_node_position_if_needed(
             height as f32,
             width as f32,
             transform.translation.x,
             transform.translation.y,
-            node_settings.radius,
+            radius.0,
         ) else {
             continue;
         };
@@ -351,17 +555,39 @@ pub fn fix_off_screen_node_positions(
 pub fn change_node_color(
     mut commands: Commands,
     mut event_reader: EventReader<ChangeNodeColorEvent>,
-    mut materials: ResMut<Assets<ColorMaterial>>,
+    mut node_assets: NodeAssets,
 ) {
     for ev in event_reader.iter() {
-        commands
-            .entity(ev.entity)
-            .insert(materials.add(ColorMaterial::from(ev.color)));
+        if let Some(mut entity) = commands.get_entity(ev.entity) {
+            entity.insert(node_assets.material(ev.color));
+        }
+    }
+}
+
+/// Resizes the node, and redraws its edges so that arrows still end on its
+/// border. Its mesh and collider follow in `restyle_nodes`.
+pub fn change_node_radius(
+    mut commands: Commands,
+    query: Query<&Transform, With<Node>>,
+    mut event_reader: EventReader<ChangeNodeRadiusEvent>,
+    mut event_writer: EventWriter<UpdateEdgeEvent>,
+) {
+    for ev in event_reader.iter() {
+        let Ok(transform) = query.get(ev.entity) else {
+            continue;
+        };
+
+        commands.entity(ev.entity).insert(Radius(ev.radius));
+
+        event_writer.send(UpdateEdgeEvent {
+            changed_node: ev.entity,
+            transform: *transform,
+        });
     }
 }
 
 pub fn emit_update_edge_event_after_node_collision(
-    query: Query<(Entity, &Velocity, &Transform), With<Node>>,
+    query: Query<(Entity, &Velocity, &Transform), (With<Node>, Changed<Transform>)>,
     mut event_writer: EventWriter<UpdateEdgeEvent>,
 ) {
     for (entity, velocity, transform) in query.iter() {
@@ -373,3 +599,145 @@ pub fn emit_update_edge_event_after_node_collision(
         }
     }
 }
+
+#[allow(clippy::type_complexity)]
+pub fn update_node_index(
+    query: Query<
+        (Entity, &Transform, &Radius, Option<&NodeStyle>),
+        (
+            With<Node>,
+            Or<(Changed<Transform>, Changed<Radius>, Changed<NodeStyle>)>,
+        ),
+    >,
+    removed_nodes: RemovedComponents<Node>,
+    mut node_index: ResMut<NodeIndex>,
+) {
+    for entity in removed_nodes.iter() {
+        node_index.remove(entity);
+    }
+
+    for (entity, transform, radius, style) in query.iter() {
+        node_index.insert(
+            entity,
+            transform.translation.x,
+            transform.translation.y,
+            radius.0,
+            style.map_or(NodeShape::Circle, |style| style.0.shape),
+        );
+    }
+}
+
+/// Icons are loaded by the asset server, which looks in the assets folder,
+/// so relative paths are taken from the working directory instead.
+fn icon_path(icon: &str) -> PathBuf {
+    env::current_dir().map_or_else(|_| PathBuf::from(icon), |dir| dir.join(icon))
+}
+
+/// Draws the nodes whose size or style changed: their mesh and collider
+/// take the shape of the node, the mesh is scaled to its size, and its
+/// outline and icon are drawn again as children of it. A new or removed style also sets the size and the fill
+/// color.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn restyle_nodes(
+    mut commands: Commands,
+    mut query: Query<
+        (
+            Entity,
+            &mut Transform,
+            &mut Radius,
+            Option<&NodeStyle>,
+            Option<ChangeTrackers<NodeStyle>>,
+            Option<&SelectedNode>,
+            Option<&MovingNode>,
+        ),
+        With<Node>,
+    >,
+    removed_styles: RemovedComponents<NodeStyle>,
+    mut event_writer: EventWriter<UpdateEdgeEvent>,
+    mut node_assets: NodeAssets,
+    asset_server: Res<AssetServer>,
+    highlights: Res<Highlights>,
+    node_settings: Res<NodeSettings>,
+) {
+    let removed_styles = removed_styles.iter().collect::<Vec<_>>();
+
+    for (entity, mut transform, mut radius, style, style_tracker, selected_node, moving_node) in
+        query.iter_mut()
+    {
+        let style_changed = style_tracker.map_or_else(
+            || removed_styles.contains(&entity),
+            |tracker| tracker.is_changed(),
+        );
+
+        if !style_changed && !radius.is_changed() {
+            continue;
+        }
+
+        // New nodes without a style already have the right mesh.
+        if radius.is_added() && !style_changed {
+            continue;
+        }
+
+        if style_changed && radius.0 != node_radius(style, &node_settings) {
+            radius.0 = node_radius(style, &node_settings);
+
+            event_writer.send(UpdateEdgeEvent {
+                changed_node: entity,
+                transform: *transform,
+            });
+        }
+
+        let shape = style.map_or(NodeShape::Circle, |style| style.0.shape);
+        let mut node = commands.entity(entity);
+
+        transform.scale = node_scale(radius.0);
+        node.insert(node_collider_scale(radius.0));
+
+        // Resizing a node without a style, as the tools do, changes nothing
+        // else.
+        if !style_changed && style.is_none() {
+            continue;
+        }
+
+        if style_changed {
+            node.insert((node_assets.mesh(shape), node_collider(shape)));
+
+            let color = highlights.node_color(entity).unwrap_or_else(|| {
+                if moving_node.is_some() {
+                    node_settings.moving_color
+                } else if selected_node.is_some() {
+                    node_settings.selected_color
+                } else {
+                    node_color(style, &node_settings)
+                }
+            });
+
+            node.insert(node_assets.material(color));
+        }
+
+        node.despawn_descendants();
+
+        let Some(style) = style else {
+            continue;
+        };
+
+        node.with_children(|parent| {
+            if let Some(outline) = style.outline() {
+                parent.spawn(outline_bundle(shape, radius.0, outline));
+            }
+
+            if let Some(icon) = style.0.icon.as_ref() {
+                parent.spawn(SpriteBundle {
+                    texture: asset_server.load(icon_path(icon)),
+                    sprite: Sprite {
+                        // The square with its corners on the circle of the node.
+                        custom_size: Some(Vec2::splat(radius.0 * SQRT_2)),
+                        ..default()
+                    },
+                    transform: child_transform(radius.0, 0.2),
+                    ..default()
+                });
+            }
+        });
+    }
+}
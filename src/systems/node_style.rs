@@ -0,0 +1,138 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    components::{Label, NodeStyle, SelectedNode},
+    graph::NodeShape,
+    resources::{NodeSettings, Tool, UiState},
+};
+
+use super::node;
+
+#[derive(Resource, Default)]
+pub struct NodeStyleState {
+    /// The node whose icon path is being typed, so that the text is only
+    /// reset when another node is selected.
+    editing: Option<Entity>,
+    icon: String,
+    error: Option<String>,
+}
+
+pub fn show_node_style_window(
+    mut commands: Commands,
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut node_style_state: ResMut<NodeStyleState>,
+    selected_nodes: Query<(Entity, &Label, Option<&NodeStyle>), With<SelectedNode>>,
+    node_settings: Res<NodeSettings>,
+) {
+    let mut open = ui_state.is_open(Tool::NodeStyle);
+    let state = &mut *node_style_state;
+
+    if !open {
+        state.editing = None;
+
+        return;
+    }
+
+    let selected = selected_nodes.iter().next();
+
+    if selected.map(|(entity, ..)| entity) != state.editing {
+        state.editing = selected.map(|(entity, ..)| entity);
+        state.icon = selected
+            .and_then(|(.., style)| style?.0.icon.clone())
+            .unwrap_or_default();
+        state.error = None;
+    }
+
+    let mut new_style = None;
+
+    egui::Window::new(Tool::NodeStyle.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let Some((_, label, style)) = selected else {
+                ui.label("Middle-click a node to select it and change how it is drawn.");
+
+                return;
+            };
+
+            let old_style = style.map_or_else(
+                || node::default_node_style(&node_settings),
+                |style| style.0.clone(),
+            );
+            let mut style = old_style.clone();
+
+            ui.label(format!("Node {}", label.0));
+
+            ui.horizontal(|ui| {
+                for shape in NodeShape::ALL {
+                    ui.radio_value(&mut style.shape, shape, shape.name());
+                }
+            });
+
+            ui.add(egui::Slider::new(&mut style.radius, 6.0..=40.0).text("size"));
+
+            ui.horizontal(|ui| {
+                ui.label("Fill");
+                ui.color_edit_button_rgba_unmultiplied(&mut style.fill);
+            });
+
+            ui.horizontal(|ui| {
+                let mut has_outline = style.outline.is_some();
+
+                ui.checkbox(&mut has_outline, "Outline");
+
+                match (has_outline, style.outline.as_mut()) {
+                    (true, Some(outline)) => {
+                        ui.color_edit_button_rgba_unmultiplied(outline);
+                    }
+                    (true, None) => style.outline = Some([0.0, 0.0, 0.0, 1.0]),
+                    (false, _) => style.outline = None,
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Icon");
+                ui.text_edit_singleline(&mut state.icon);
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("Set icon").clicked() {
+                    let path = state.icon.trim();
+
+                    if Path::new(path).is_file() {
+                        style.icon = Some(path.to_string());
+                        state.error = None;
+                    } else {
+                        state.error = Some(format!("Can not find the image {path}"));
+                    }
+                }
+
+                if ui
+                    .add_enabled(style.icon.is_some(), egui::Button::new("Remove icon"))
+                    .clicked()
+                {
+                    style.icon = None;
+                }
+            });
+
+            if let Some(error) = state.error.as_ref() {
+                ui.label(error);
+            }
+
+            if style != old_style {
+                new_style = Some(style);
+            }
+        });
+
+    if let (Some((entity, ..)), Some(style)) = (selected, new_style) {
+        commands.entity(entity).insert(NodeStyle(style));
+    }
+
+    if !open {
+        ui_state.set_open(Tool::NodeStyle, false);
+    }
+}
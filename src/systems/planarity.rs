@@ -0,0 +1,335 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::planarity::{self, KuratowskiKind, KuratowskiSubgraph},
+    layout,
+    resources::{HighlightLayer, HighlightSettings, Highlights, NodeSettings, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+};
+
+use super::{
+    background::BackgroundTask,
+    edges::{GraphChangedEvent, UpdateEdgeEvent},
+    node::MoveNodesEvent,
+};
+
+/// Seconds between two counts of the crossings while nodes move, since
+/// counting them goes through every pair of edges.
+const RECOUNT_DELAY: f32 = 0.5;
+
+pub enum PlanarityResult {
+    Planar,
+    NotPlanar {
+        kind: KuratowskiKind,
+        branch_nodes: Vec<Entity>,
+        other_nodes: Vec<Entity>,
+        edges: Vec<Entity>,
+    },
+}
+
+pub struct KuratowskiSearch {
+    snapshot: GraphSnapshot,
+    task: BackgroundTask<Option<KuratowskiSubgraph>>,
+}
+
+#[derive(Resource)]
+pub struct PlanarityState {
+    pub result: Option<PlanarityResult>,
+    pub search: Option<KuratowskiSearch>,
+    pub show_crossings: bool,
+    /// Crossings of the current drawing, counted again when the graph
+    /// changes, and at most every `RECOUNT_DELAY` while nodes move.
+    crossings: Option<usize>,
+    crossing_edges: Vec<Entity>,
+    recount: Option<Timer>,
+    /// The highlights last set by this window, so that they are only written
+    /// when they change.
+    highlighted_nodes: HashMap<Entity, Color>,
+    highlighted_edges: HashMap<Entity, Color>,
+}
+
+impl Default for PlanarityState {
+    fn default() -> Self {
+        Self {
+            result: None,
+            search: None,
+            show_crossings: true,
+            crossings: None,
+            crossing_edges: Vec::new(),
+            recount: None,
+            highlighted_nodes: HashMap::new(),
+            highlighted_edges: HashMap::new(),
+        }
+    }
+}
+
+/// Kuratowski subgraphs in red with the nodes of K5 or K3,3 in orange, and
+/// the edges crossing in the current drawing in gold.
+fn planarity_highlights(
+    state: &PlanarityState,
+    highlight_settings: &HighlightSettings,
+) -> (HashMap<Entity, Color>, HashMap<Entity, Color>) {
+    let mut nodes = HashMap::new();
+    let mut edges = HashMap::new();
+
+    if state.show_crossings {
+        for &edge in state.crossing_edges.iter() {
+            edges.insert(edge, highlight_settings.current_color);
+        }
+    }
+
+    if let Some(PlanarityResult::NotPlanar {
+        branch_nodes,
+        other_nodes,
+        edges: subgraph_edges,
+        ..
+    }) = state.result.as_ref()
+    {
+        for &edge in subgraph_edges.iter() {
+            edges.insert(edge, highlight_settings.rejected_color);
+        }
+
+        for &node in other_nodes.iter() {
+            nodes.insert(node, highlight_settings.rejected_color);
+        }
+
+        for &node in branch_nodes.iter() {
+            nodes.insert(node, highlight_settings.critical_color);
+        }
+    }
+
+    (nodes, edges)
+}
+
+fn set_highlights(
+    state: &mut PlanarityState,
+    nodes: HashMap<Entity, Color>,
+    edges: HashMap<Entity, Color>,
+    highlights: &mut HighlightLayer,
+) {
+    highlights.nodes = nodes.clone();
+    highlights.edges = edges.clone();
+
+    state.highlighted_nodes = nodes;
+    state.highlighted_edges = edges;
+}
+
+/// The result of the test, with the nodes and edges of the Kuratowski
+/// subgraph found in the snapshot.
+fn planarity_result(
+    snapshot: &GraphSnapshot,
+    subgraph: Option<KuratowskiSubgraph>,
+) -> PlanarityResult {
+    match subgraph {
+        None => PlanarityResult::Planar,
+        Some(subgraph) => {
+            let mut subdivided = vec![false; snapshot.nodes.len()];
+
+            for &edge in subgraph.edges.iter() {
+                subdivided[snapshot.graph.edges[edge].source] = true;
+                subdivided[snapshot.graph.edges[edge].target] = true;
+            }
+
+            for &node in subgraph.branch_nodes.iter() {
+                subdivided[node] = false;
+            }
+
+            PlanarityResult::NotPlanar {
+                kind: subgraph.kind,
+                branch_nodes: subgraph
+                    .branch_nodes
+                    .iter()
+                    .map(|&node| snapshot.nodes[node])
+                    .collect(),
+                other_nodes: (0..snapshot.nodes.len())
+                    .filter(|&node| subdivided[node])
+                    .map(|node| snapshot.nodes[node])
+                    .collect(),
+                edges: subgraph
+                    .edges
+                    .iter()
+                    .map(|&edge| snapshot.edges[edge])
+                    .collect(),
+            }
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_planarity_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut planarity_state: ResMut<PlanarityState>,
+    mut highlights: ResMut<Highlights>,
+    mut event_reader: EventReader<GraphChangedEvent>,
+    mut update_edge_event_reader: EventReader<UpdateEdgeEvent>,
+    mut event_writer: EventWriter<MoveNodesEvent>,
+    graph_query: GraphQuery,
+    windows: Res<Windows>,
+    node_settings: Res<NodeSettings>,
+    highlight_settings: Res<HighlightSettings>,
+    time: Res<Time>,
+) {
+    let graph_changed = event_reader.iter().last().is_some();
+    let drawing_changed = update_edge_event_reader.iter().last().is_some();
+    let mut open = ui_state.is_open(Tool::Planarity);
+    let state = &mut *planarity_state;
+
+    if !open {
+        if state.crossings.take().is_some() {
+            state.result = None;
+            state.search = None;
+            state.recount = None;
+            state.crossing_edges.clear();
+            state.highlighted_nodes.clear();
+            state.highlighted_edges.clear();
+            highlights.clear(Tool::Planarity);
+        }
+
+        return;
+    }
+
+    // A Kuratowski subgraph may lose its edges, and a planar graph may gain
+    // some, so the test has to be run again.
+    if graph_changed {
+        state.result = None;
+        state.search = None;
+    }
+
+    if let Some(result) = state.search.as_mut().and_then(|search| search.task.poll()) {
+        let search = state.search.take().unwrap();
+
+        match result {
+            Ok(subgraph) => state.result = Some(planarity_result(&search.snapshot, subgraph)),
+            Err(_) => error!("The planarity test stopped unexpectedly"),
+        }
+    }
+
+    if drawing_changed && state.recount.is_none() {
+        state.recount = Some(Timer::from_seconds(RECOUNT_DELAY, TimerMode::Once));
+    }
+
+    let recount = state
+        .recount
+        .as_mut()
+        .map_or(false, |timer| timer.tick(time.delta()).finished());
+
+    if graph_changed || recount || state.crossings.is_none() {
+        state.recount = None;
+
+        let snapshot = graph_query.snapshot();
+        let crossings = planarity::crossings(&snapshot.graph);
+
+        state.crossings = Some(crossings.len());
+        state.crossing_edges = crossings
+            .iter()
+            .flat_map(|&(a, b)| [snapshot.edges[a], snapshot.edges[b]])
+            .collect();
+        state.crossing_edges.sort_unstable();
+        state.crossing_edges.dedup();
+    }
+
+    egui::Window::new(Tool::Planarity.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            let idle = state.search.is_none();
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(idle, egui::Button::new("Test planarity"))
+                    .clicked()
+                {
+                    let snapshot = graph_query.snapshot();
+                    let graph = snapshot.graph.clone();
+
+                    state.result = None;
+                    state.search = Some(KuratowskiSearch {
+                        snapshot,
+                        task: BackgroundTask::start(move |progress, cancel| {
+                            planarity::kuratowski_subgraph(&graph, progress, cancel)
+                        }),
+                    });
+                }
+
+                if ui
+                    .add_enabled(!idle, egui::Button::new("Cancel"))
+                    .clicked()
+                {
+                    state.search = None;
+                }
+            });
+
+            if let Some(search) = state.search.as_ref() {
+                ui.label(format!(
+                    "Looking for a Kuratowski subgraph: {} subgraphs tested in {:.1} s",
+                    search.task.progress(),
+                    search.task.elapsed().as_secs_f32()
+                ));
+            }
+
+            ui.label("Edge directions, loops and parallel edges are ignored.");
+
+            match state.result.as_ref() {
+                Some(PlanarityResult::Planar) => {
+                    ui.label("The graph is planar.");
+
+                    if ui.button("Lay out without crossings").clicked() {
+                        let snapshot = graph_query.snapshot();
+                        let window = windows
+                            .get_primary()
+                            .expect("Can not get the primary window");
+                        let margin = node_settings.radius * 4.0;
+
+                        let mut graph = snapshot.graph.clone();
+
+                        if layout::planar(
+                            &mut graph,
+                            window.width() - margin,
+                            window.height() - margin,
+                        ) {
+                            event_writer.send(MoveNodesEvent {
+                                positions: snapshot
+                                    .nodes
+                                    .iter()
+                                    .zip(graph.nodes.iter())
+                                    .map(|(&entity, node)| (entity, Vec2::new(node.x, node.y)))
+                                    .collect(),
+                            });
+                        }
+                    }
+                }
+                Some(PlanarityResult::NotPlanar {
+                    kind,
+                    branch_nodes,
+                    ..
+                }) => {
+                    ui.label(format!(
+                        "The graph is not planar: it contains a subdivision of {}, whose {} nodes are marked in orange.",
+                        kind.name(),
+                        branch_nodes.len()
+                    ));
+                }
+                None => {}
+            }
+
+            ui.separator();
+            ui.label(format!(
+                "Crossings in the current drawing, with every edge as a straight line: {}",
+                state.crossings.unwrap_or(0)
+            ));
+            ui.checkbox(&mut state.show_crossings, "Highlight crossing edges");
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Planarity, false);
+    }
+
+    let (nodes, edges) = planarity_highlights(state, &highlight_settings);
+
+    // Writing the highlights every frame would make them be applied again
+    // every frame.
+    if nodes != state.highlighted_nodes || edges != state.highlighted_edges {
+        set_highlights(state, nodes, edges, highlights.layer(Tool::Planarity));
+    }
+}
@@ -0,0 +1,93 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_egui::egui;
+
+/// Step controls shared by the windows that animate an algorithm run.
+pub struct Playback {
+    pub playing: bool,
+    pub timer: Timer,
+}
+
+impl Default for Playback {
+    fn default() -> Self {
+        Self {
+            playing: false,
+            timer: Timer::from_seconds(0.5, TimerMode::Repeating),
+        }
+    }
+}
+
+impl Playback {
+    /// Moves to the next step when playing and the timer finishes. Returns
+    /// whether the step changed.
+    pub fn tick(&mut self, delta: Duration, step: &mut usize, steps: usize) -> bool {
+        if !self.playing {
+            return false;
+        }
+
+        let changed = self.timer.tick(delta).just_finished() && *step < steps;
+
+        if changed {
+            *step += 1;
+        }
+
+        if *step == steps {
+            self.playing = false;
+        }
+
+        changed
+    }
+
+    /// Shows the Step, Play, Finish and Reset buttons and the speed slider.
+    /// Returns whether the step changed.
+    pub fn controls(&mut self, ui: &mut egui::Ui, step: &mut usize, steps: usize) -> bool {
+        let mut changed = false;
+
+        ui.horizontal(|ui| {
+            let finished = *step == steps;
+
+            if ui
+                .add_enabled(!finished, egui::Button::new("Step"))
+                .clicked()
+            {
+                *step += 1;
+                changed = true;
+            }
+
+            let play = if self.playing { "Pause" } else { "Play" };
+
+            if ui.add_enabled(!finished, egui::Button::new(play)).clicked() {
+                self.playing = !self.playing;
+                self.timer.reset();
+            }
+
+            if ui
+                .add_enabled(!finished, egui::Button::new("Finish"))
+                .clicked()
+            {
+                *step = steps;
+                changed = true;
+            }
+
+            if ui.button("Reset").clicked() {
+                *step = 0;
+                self.playing = false;
+                changed = true;
+            }
+        });
+
+        let mut seconds = self.timer.duration().as_secs_f32();
+
+        if ui
+            .add(egui::Slider::new(&mut seconds, 0.05..=2.0).text("seconds per step"))
+            .changed()
+        {
+            self.timer.set_duration(Duration::from_secs_f32(seconds));
+        }
+
+        ui.label(format!("Step {step} of {steps}"));
+
+        changed
+    }
+}
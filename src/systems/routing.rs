@@ -0,0 +1,449 @@
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+use bevy_egui::{egui, EguiContext};
+use bevy_prototype_lyon::prelude::*;
+
+use crate::{
+    components::{Bend, Corners, Directed, NeighborNodes, Node, Radius, Route},
+    graph::{EdgeRoute, NodeShape},
+    resources::{EdgeSettings, NodeIndex, NodeSettings, Tool, UiState},
+};
+
+use super::edges::{
+    self, control_point, distance_to_segment, neighbor_nodes_path, polyline_edge_path,
+    relative_control_point, UpdateEdgeEvent, PARALLEL_SPACING,
+};
+
+/// Room left between an orthogonal edge and the nodes it goes around.
+const CLEARANCE: f32 = 6.0;
+
+/// How many steps of `PARALLEL_SPACING` the middle segment of an
+/// orthogonal edge may be moved aside, each way, to get around nodes.
+const DETOURS: i32 = 12;
+
+/// Size of the handles shown on the edges while the routing window is open.
+const HANDLE_SIZE: f32 = 12.0;
+
+#[derive(Resource)]
+pub struct RoutingState {
+    pub show_handles: bool,
+}
+
+impl Default for RoutingState {
+    fn default() -> Self {
+        Self { show_handles: true }
+    }
+}
+
+/// A node an orthogonal edge goes around: its center, radius and shape.
+pub type Obstacle = (Vec2, f32, NodeShape);
+
+fn segments_cross(a: Vec2, b: Vec2, c: Vec2, d: Vec2) -> bool {
+    let side = |p: Vec2, q: Vec2, r: Vec2| (q - p).perp_dot(r - p).signum();
+
+    side(a, b, c) != side(a, b, d) && side(c, d, a) != side(c, d, b)
+}
+
+/// Whether the segment from `a` to `b` comes closer than `CLEARANCE` to the
+/// outline of the node, or goes through it.
+fn segment_hits(a: Vec2, b: Vec2, (center, radius, shape): Obstacle) -> bool {
+    let corners = shape
+        .corners(radius)
+        .into_iter()
+        .map(|(x, y)| center + Vec2::new(x, y))
+        .collect::<Vec<_>>();
+
+    if corners.is_empty() {
+        return distance_to_segment(a, b, center) < radius + CLEARANCE;
+    }
+
+    let inside = |point: Vec2| shape.contains(point.x - center.x, point.y - center.y, radius);
+
+    if inside(a) || inside(b) {
+        return true;
+    }
+
+    (0..corners.len()).any(|index| {
+        let (c, d) = (corners[index], corners[(index + 1) % corners.len()]);
+
+        segments_cross(a, b, c, d)
+            || distance_to_segment(a, b, c) < CLEARANCE
+            || distance_to_segment(c, d, a) < CLEARANCE
+            || distance_to_segment(c, d, b) < CLEARANCE
+    })
+}
+
+/// How far from the straight line between its nodes an orthogonal edge
+/// bent by `bend` may go, which bounds the nodes it has to go around.
+fn detour_reach(bend: f32) -> f32 {
+    bend.abs() + DETOURS as f32 * PARALLEL_SPACING + CLEARANCE
+}
+
+/// Corners of a route from `start` to `end` made of horizontal and vertical
+/// segments, going around the given nodes when it can. Of the routes
+/// crossing the fewest nodes, the shortest one with the fewest turns is
+/// taken. A `bend` moves the middle segment aside, to the left of the
+/// direction from start to end, so that parallel edges stay apart.
+pub fn orthogonal_corners(start: Vec2, end: Vec2, bend: f32, obstacles: &[Obstacle]) -> Vec<Vec2> {
+    let mut candidates = Vec::new();
+
+    if bend == 0.0 {
+        if start.x == end.x || start.y == end.y {
+            candidates.push(vec![start, end]);
+        }
+
+        candidates.push(vec![start, Vec2::new(end.x, start.y), end]);
+        candidates.push(vec![start, Vec2::new(start.x, end.y), end]);
+    }
+
+    // The side of each coordinate is taken from the left of the edge, so
+    // that it flips with the direction of the edge like `bend` does.
+    let left = (end - start).normalize_or_zero().perp();
+    let side = |component: f32, other: f32| {
+        if component != 0.0 {
+            component.signum()
+        } else {
+            other.signum()
+        }
+    };
+
+    let middle =
+        (start + end) / 2.0 + Vec2::new(bend * side(left.x, left.y), bend * side(left.y, left.x));
+
+    for step in -DETOURS..=DETOURS {
+        let offset = step as f32 * PARALLEL_SPACING;
+        let (x, y) = (middle.x + offset, middle.y + offset);
+
+        candidates.push(vec![start, Vec2::new(x, start.y), Vec2::new(x, end.y), end]);
+        candidates.push(vec![start, Vec2::new(start.x, y), Vec2::new(end.x, y), end]);
+    }
+
+    let cost = |corners: &Vec<Vec2>| {
+        let segments = corners.windows(2);
+        let hits = obstacles
+            .iter()
+            .filter(|&&obstacle| {
+                segments
+                    .clone()
+                    .any(|pair| segment_hits(pair[0], pair[1], obstacle))
+            })
+            .count();
+        let length = segments.map(|pair| pair[0].distance(pair[1])).sum::<f32>();
+
+        hits as f32 * 1.0e6 + length + corners.len() as f32 * PARALLEL_SPACING
+    };
+
+    candidates
+        .into_iter()
+        .min_by(|a, b| cost(a).total_cmp(&cost(b)))
+        .unwrap_or_else(|| vec![start, end])
+}
+
+/// Draws the edges whose route or bend changed, and routes the orthogonal
+/// edges again when a node moves, appears or disappears close enough to
+/// change their route. The nodes they go around are looked up in the node
+/// index.
+#[allow(clippy::too_many_arguments)]
+pub fn route_edges(
+    mut commands: Commands,
+    mut edges: Query<(
+        Entity,
+        &NeighborNodes,
+        &mut Path,
+        Option<&Directed>,
+        Option<&Bend>,
+        Option<&Route>,
+    )>,
+    changed_edges: Query<Entity, (With<NeighborNodes>, Or<(Changed<Route>, Changed<Bend>)>)>,
+    nodes: Query<&Radius, With<Node>>,
+    added_nodes: Query<(Entity, &Transform, &Radius), Added<Node>>,
+    removed_nodes: RemovedComponents<Node>,
+    mut event_reader: EventReader<UpdateEdgeEvent>,
+    node_index: Res<NodeIndex>,
+    node_settings: Res<NodeSettings>,
+    mut positions: Local<HashMap<Entity, (Vec2, f32)>>,
+) {
+    // Where nodes were and are now, with their radius. An orthogonal edge
+    // near any of these places may have to go another way.
+    let mut moved = Vec::new();
+
+    for (entity, transform, radius) in added_nodes.iter() {
+        let position = (transform.translation.truncate(), radius.0);
+
+        positions.insert(entity, position);
+        moved.push(position);
+    }
+
+    for ev in event_reader.iter() {
+        let Ok(radius) = nodes.get(ev.changed_node) else {
+            continue;
+        };
+
+        let position = (ev.transform.translation.truncate(), radius.0);
+
+        moved.extend(positions.insert(ev.changed_node, position));
+        moved.push(position);
+    }
+
+    for entity in removed_nodes.iter() {
+        moved.extend(positions.remove(&entity));
+    }
+
+    let changed = changed_edges.iter().collect::<HashSet<_>>();
+
+    if moved.is_empty() && changed.is_empty() {
+        return;
+    }
+
+    for (entity, neighbor_nodes, mut path, directed, bend, route) in edges.iter_mut() {
+        let route = route.map_or(EdgeRoute::Straight, |route| route.0);
+        let orthogonal = route == EdgeRoute::Orthogonal && neighbor_nodes.v != neighbor_nodes.u;
+        let bend = bend.map_or(0.0, |bend| bend.0);
+
+        let start = neighbor_nodes.pos_v.translation.truncate();
+        let end = neighbor_nodes.pos_u.translation.truncate();
+        let reach = detour_reach(bend);
+        let (min, max) = (start.min(end) - reach, start.max(end) + reach);
+
+        let near = |&(position, radius): &(Vec2, f32)| {
+            position.cmpge(min - radius).all() && position.cmple(max + radius).all()
+        };
+
+        if !changed.contains(&entity) && !(orthogonal && moved.iter().any(near)) {
+            continue;
+        }
+
+        let radius = nodes
+            .get(neighbor_nodes.u)
+            .map_or(node_settings.radius, |radius| radius.0);
+
+        if !orthogonal {
+            *path = neighbor_nodes_path(neighbor_nodes, directed.is_some(), radius, bend, route);
+
+            continue;
+        }
+
+        let obstacles = node_index
+            .items_in((min.x, min.y), (max.x, max.y))
+            .filter(|&(node, _)| node != neighbor_nodes.v && node != neighbor_nodes.u)
+            .map(|(_, (x, y, radius, shape))| (Vec2::new(x, y), radius, shape))
+            .collect::<Vec<_>>();
+
+        let corners = orthogonal_corners(start, end, bend, &obstacles);
+
+        *path = polyline_edge_path(&corners, directed.is_some(), radius);
+
+        commands.entity(entity).insert(Corners(corners));
+    }
+}
+
+/// Control points of a curve with the same bulge as a parallel edge bent
+/// by `bend`, or of the default curve when the edge is not bent.
+fn curve_controls(bend: f32, length: f32) -> [(f32, f32); 2] {
+    if bend == 0.0 || length == 0.0 {
+        return EdgeRoute::DEFAULT_CONTROLS;
+    }
+
+    // The middle of a cubic curve is three quarters of the way to its
+    // control points.
+    let across = bend / (0.75 * length);
+
+    [(1.0 / 3.0, across), (2.0 / 3.0, across)]
+}
+
+fn route_menu(ui: &mut egui::Ui, route: &mut Option<EdgeRoute>, curve: EdgeRoute) {
+    for (text, choice) in [
+        ("Straight", EdgeRoute::Straight),
+        ("Curved", curve),
+        ("Orthogonal", EdgeRoute::Orthogonal),
+    ] {
+        if ui.button(text).clicked() {
+            *route = Some(choice);
+            ui.close_menu();
+        }
+    }
+}
+
+/// A small round handle at `position`, in screen coordinates.
+fn handle(ctx: &egui::Context, id: impl std::hash::Hash, position: egui::Pos2) -> egui::Response {
+    egui::Area::new(id)
+        .fixed_pos(position - egui::Vec2::splat(HANDLE_SIZE / 2.0))
+        .show(ctx, |ui| {
+            let (rect, response) = ui.allocate_exact_size(
+                egui::Vec2::splat(HANDLE_SIZE),
+                egui::Sense::click_and_drag(),
+            );
+
+            let color = if response.hovered() || response.dragged() {
+                egui::Color32::GOLD
+            } else {
+                egui::Color32::WHITE
+            };
+
+            ui.painter().circle(
+                rect.center(),
+                HANDLE_SIZE / 2.0 - 1.0,
+                color,
+                egui::Stroke::new(1.0, egui::Color32::BLACK),
+            );
+
+            response
+        })
+        .inner
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_routing_window(
+    mut commands: Commands,
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut routing_state: ResMut<RoutingState>,
+    mut edge_settings: ResMut<EdgeSettings>,
+    edges: Query<(
+        Entity,
+        &NeighborNodes,
+        Option<&Bend>,
+        Option<&Route>,
+        Option<&Corners>,
+    )>,
+    radii: Query<&Radius>,
+    windows: Res<Windows>,
+    node_settings: Res<NodeSettings>,
+) {
+    let mut open = ui_state.is_open(Tool::Routing);
+    let state = &mut *routing_state;
+
+    if !open {
+        return;
+    }
+
+    let mut route_all = None;
+
+    egui::Window::new(Tool::Routing.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.label("New edges");
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut edge_settings.route, EdgeRoute::Straight, "Straight");
+                ui.radio_value(&mut edge_settings.route, EdgeRoute::CURVED, "Curved");
+                ui.radio_value(
+                    &mut edge_settings.route,
+                    EdgeRoute::Orthogonal,
+                    "Orthogonal",
+                );
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("Route all edges:");
+                route_menu(ui, &mut route_all, EdgeRoute::CURVED);
+            });
+
+            ui.checkbox(&mut state.show_handles, "Show handles");
+            ui.label(
+                "Drag the middle of an edge to curve it, and the control points of a curve to \
+                 change its shape. Right-click a handle to choose how its edge is routed.",
+            );
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Routing, false);
+    }
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+
+    let (window_center_x, window_center_y) = (window.width() / 2.0, window.height() / 2.0);
+    let to_screen =
+        |position: Vec2| egui::pos2(position.x + window_center_x, window_center_y - position.y);
+    let to_world = |delta: egui::Vec2| Vec2::new(delta.x, -delta.y);
+
+    let ctx = egui_context.ctx_mut();
+    let painter = ctx.layer_painter(egui::LayerId::background());
+    let guide = egui::Stroke::new(1.0, egui::Color32::GRAY);
+
+    for (entity, neighbor_nodes, bend, route, corners) in edges.iter() {
+        if neighbor_nodes.v == neighbor_nodes.u {
+            continue;
+        }
+
+        let start = neighbor_nodes.pos_v.translation.truncate();
+        let end = neighbor_nodes.pos_u.translation.truncate();
+        let bend = bend.map_or(0.0, |bend| bend.0);
+        let route = route.map_or(EdgeRoute::Straight, |route| route.0);
+        let curve_controls = curve_controls(bend, start.distance(end));
+        let curve = EdgeRoute::Curved {
+            controls: curve_controls,
+        };
+
+        if let Some(route) = route_all {
+            commands.entity(entity).insert(Route(match route {
+                EdgeRoute::Curved { .. } => curve,
+                route => route,
+            }));
+
+            continue;
+        }
+
+        if !state.show_handles {
+            continue;
+        }
+
+        let radius = radii
+            .get(neighbor_nodes.u)
+            .map_or(node_settings.radius, |radius| radius.0);
+        let middle = edges::edge_middle(neighbor_nodes, radius, bend, route, corners);
+
+        let mut new_route = None;
+
+        let response = handle(ctx, ("route_handle", entity, 0), to_screen(middle))
+            .context_menu(|ui| route_menu(ui, &mut new_route, curve));
+
+        // Moving both control points moves the middle of the curve three
+        // quarters as far.
+        if response.dragged() && route != EdgeRoute::Orthogonal {
+            let controls = match route {
+                EdgeRoute::Curved { controls } => controls,
+                _ => curve_controls,
+            };
+
+            let (along, across) = relative_control_point(
+                Vec2::ZERO,
+                end - start,
+                to_world(response.drag_delta()) * 4.0 / 3.0,
+            );
+
+            new_route = Some(EdgeRoute::Curved {
+                controls: controls.map(|control| (control.0 + along, control.1 + across)),
+            });
+        }
+
+        if let EdgeRoute::Curved { controls } = route {
+            for (index, &control) in controls.iter().enumerate() {
+                let point = control_point(start, end, control);
+                let anchor = if index == 0 { start } else { end };
+
+                painter.line_segment([to_screen(anchor), to_screen(point)], guide);
+
+                let response = handle(ctx, ("route_handle", entity, index + 1), to_screen(point))
+                    .context_menu(|ui| route_menu(ui, &mut new_route, curve));
+
+                if response.dragged() {
+                    let mut controls = controls;
+
+                    controls[index] =
+                        relative_control_point(start, end, point + to_world(response.drag_delta()));
+
+                    new_route = Some(EdgeRoute::Curved { controls });
+                }
+            }
+        }
+
+        if let Some(route) = new_route {
+            commands.entity(entity).insert(Route(route));
+        }
+    }
+}
@@ -0,0 +1,301 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::shortest_paths::{self, AllPairs, NegativeCycle},
+    resources::{HighlightLayer, HighlightSettings, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::weights::WeightTable;
+
+pub struct Distances {
+    algorithm: &'static str,
+    snapshot: GraphSnapshot,
+    all_pairs: AllPairs,
+    eccentricities: Vec<f32>,
+}
+
+impl Distances {
+    fn radius(&self) -> f32 {
+        self.eccentricities
+            .iter()
+            .copied()
+            .fold(f32::INFINITY, f32::min)
+    }
+
+    fn diameter(&self) -> f32 {
+        self.eccentricities.iter().copied().fold(0.0, f32::max)
+    }
+
+    /// Nodes with the smallest eccentricity, none when every node has some
+    /// node it can not reach.
+    fn centre(&self) -> Vec<usize> {
+        let radius = self.radius();
+
+        if !radius.is_finite() {
+            return Vec::new();
+        }
+
+        (0..self.eccentricities.len())
+            .filter(|&node| self.eccentricities[node] == radius)
+            .collect()
+    }
+}
+
+pub enum ShortestPathsResult {
+    Distances(Distances),
+    NegativeCycle {
+        algorithm: &'static str,
+        nodes: Vec<String>,
+    },
+}
+
+#[derive(Resource, Default)]
+pub struct ShortestPathsState {
+    pub result: Option<ShortestPathsResult>,
+    pub hovered: Option<(usize, usize)>,
+}
+
+fn distance_text(distance: f32) -> String {
+    if distance.is_finite() {
+        distance.to_string()
+    } else {
+        "-".to_string()
+    }
+}
+
+fn run(
+    snapshot: GraphSnapshot,
+    floyd_warshall: bool,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) -> ShortestPathsResult {
+    let graph = &snapshot.graph;
+
+    let (algorithm, all_pairs) = if floyd_warshall {
+        ("Floyd-Warshall", shortest_paths::floyd_warshall(graph))
+    } else if shortest_paths::has_negative_weights(graph) {
+        ("Bellman-Ford", shortest_paths::repeated_bellman_ford(graph))
+    } else {
+        ("Dijkstra", Ok(shortest_paths::repeated_dijkstra(graph)))
+    };
+
+    match all_pairs {
+        Ok(all_pairs) => {
+            let distances = Distances {
+                algorithm,
+                eccentricities: all_pairs.eccentricities(),
+                snapshot,
+                all_pairs,
+            };
+
+            highlight(&distances, None, highlights, highlight_settings);
+
+            ShortestPathsResult::Distances(distances)
+        }
+        Err(NegativeCycle { nodes, edges }) => {
+            highlights.clear();
+
+            for &node in nodes.iter() {
+                highlights
+                    .nodes
+                    .insert(snapshot.nodes[node], highlight_settings.rejected_color);
+            }
+
+            for &edge in edges.iter() {
+                highlights
+                    .edges
+                    .insert(snapshot.edges[edge], highlight_settings.rejected_color);
+            }
+
+            ShortestPathsResult::NegativeCycle {
+                algorithm,
+                nodes: nodes
+                    .iter()
+                    .map(|&node| graph.nodes[node].label.clone())
+                    .collect(),
+            }
+        }
+    }
+}
+
+/// Shows the path of the hovered cell, or the centre nodes when no cell is
+/// hovered.
+fn highlight(
+    distances: &Distances,
+    hovered: Option<(usize, usize)>,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    let snapshot = &distances.snapshot;
+
+    let Some((source, target)) = hovered else {
+        for node in distances.centre() {
+            highlights
+                .nodes
+                .insert(snapshot.nodes[node], highlight_settings.current_color);
+        }
+
+        return;
+    };
+
+    if let Some((nodes, edges)) = distances.all_pairs.path(source, target) {
+        for &node in nodes.iter() {
+            highlights
+                .nodes
+                .insert(snapshot.nodes[node], highlight_settings.accepted_color);
+        }
+
+        for &edge in edges.iter() {
+            highlights
+                .edges
+                .insert(snapshot.edges[edge], highlight_settings.accepted_color);
+        }
+    }
+
+    highlights
+        .nodes
+        .insert(snapshot.nodes[source], utils::palette_color(0));
+    highlights
+        .nodes
+        .insert(snapshot.nodes[target], utils::palette_color(1));
+}
+
+pub fn show_shortest_paths_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut shortest_paths_state: ResMut<ShortestPathsState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    highlight_settings: Res<HighlightSettings>,
+    mut weights: WeightTable,
+) {
+    let mut open = ui_state.is_open(Tool::ShortestPaths);
+    let state = &mut *shortest_paths_state;
+
+    if !open {
+        if state.result.take().is_some() {
+            state.hovered = None;
+            highlights.clear(Tool::ShortestPaths);
+        }
+
+        return;
+    }
+
+    egui::Window::new(Tool::ShortestPaths.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                for (floyd_warshall, text) in [
+                    (true, "Floyd-Warshall"),
+                    (false, "Dijkstra / Bellman-Ford"),
+                ] {
+                    if ui.button(text).clicked() {
+                        state.result = Some(run(
+                            graph_query.snapshot(),
+                            floyd_warshall,
+                            highlights.layer(Tool::ShortestPaths),
+                            &highlight_settings,
+                        ));
+                        state.hovered = None;
+                    }
+                }
+            });
+
+            ui.label("Edge weights are the lengths (1 when missing). Bellman-Ford is used instead of Dijkstra when some weight is negative.");
+
+            if weights.show(ui, "Edge weights", false) && state.result.take().is_some() {
+                state.hovered = None;
+                highlights.clear(Tool::ShortestPaths);
+            }
+
+            let distances = match &state.result {
+                Some(ShortestPathsResult::Distances(distances)) => distances,
+                Some(ShortestPathsResult::NegativeCycle { algorithm, nodes }) => {
+                    ui.label(format!(
+                        "{algorithm} found the negative cycle {}",
+                        nodes.join(", ")
+                    ));
+
+                    return;
+                }
+                None => return,
+            };
+
+            let graph = &distances.snapshot.graph;
+            let centre = distances
+                .centre()
+                .iter()
+                .map(|&node| graph.nodes[node].label.as_str())
+                .collect::<Vec<_>>();
+
+            ui.label(format!("Distances found with {}", distances.algorithm));
+
+            if distances.radius().is_finite() {
+                let diameter = distances.diameter();
+
+                ui.label(format!(
+                    "Radius: {}, diameter: {}",
+                    distances.radius(),
+                    if diameter.is_finite() {
+                        diameter.to_string()
+                    } else {
+                        "infinite".to_string()
+                    }
+                ));
+                ui.label(format!("Centre: {}", centre.join(", ")));
+            } else {
+                ui.label("Radius and diameter are infinite: no node reaches every other node");
+            }
+
+            ui.label("Hover a distance to show its path. The last column is the eccentricity.");
+
+            let mut hovered = None;
+
+            egui::ScrollArea::both().max_height(300.0).show(ui, |ui| {
+                egui::Grid::new("distance_matrix")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("");
+
+                        for node in graph.nodes.iter() {
+                            ui.strong(node.label.as_str());
+                        }
+
+                        ui.strong("ecc");
+                        ui.end_row();
+
+                        for (source, row) in distances.all_pairs.distances.iter().enumerate() {
+                            ui.strong(graph.nodes[source].label.as_str());
+
+                            for (target, &distance) in row.iter().enumerate() {
+                                if ui.label(distance_text(distance)).hovered() {
+                                    hovered = Some((source, target));
+                                }
+                            }
+
+                            ui.label(distance_text(distances.eccentricities[source]));
+                            ui.end_row();
+                        }
+                    });
+            });
+
+            if hovered != state.hovered {
+                state.hovered = hovered;
+                highlight(
+                    distances,
+                    hovered,
+                    highlights.layer(Tool::ShortestPaths),
+                    &highlight_settings,
+                );
+            }
+        });
+
+    if !open {
+        ui_state.set_open(Tool::ShortestPaths, false);
+    }
+}
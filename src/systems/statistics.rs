@@ -0,0 +1,305 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::{connectivity, matching, planarity, topological},
+    components::{NeighborNodes, Node},
+    graph::Graph,
+    resources::UiState,
+    snapshot::GraphQuery,
+};
+
+use super::background::BackgroundTask;
+
+pub struct DegreeSummary {
+    min: usize,
+    max: usize,
+    mean: f32,
+    /// Number of nodes of every degree, from 0 to `max`.
+    histogram: Vec<usize>,
+}
+
+pub struct StructuralProperties {
+    nodes: usize,
+    edges: usize,
+    directed: bool,
+    components: usize,
+    /// `None` when no edge is directed.
+    acyclic: Option<bool>,
+    bipartite: bool,
+    planar: bool,
+    girth: Option<usize>,
+}
+
+impl StructuralProperties {
+    fn compute(graph: &Graph) -> Self {
+        let all_directed = graph.edges.iter().all(|edge| edge.directed);
+
+        Self {
+            nodes: graph.nodes.len(),
+            edges: graph.edges.len(),
+            directed: graph.is_directed(),
+            components: connectivity::component_count(&connectivity::connected_components(graph)),
+            acyclic: graph
+                .is_directed()
+                .then(|| all_directed && topological::kahn(graph).cycle.is_none()),
+            bipartite: matches!(
+                matching::bipartition(graph),
+                matching::Bipartition::Sides(_)
+            ),
+            planar: planarity::is_planar(graph),
+            girth: connectivity::girth(graph),
+        }
+    }
+
+    /// A forest has exactly one edge less than nodes in every component;
+    /// any more edges close a cycle.
+    fn is_forest(&self) -> bool {
+        self.edges + self.components == self.nodes
+    }
+}
+
+/// Kept up to date as nodes and edges are spawned and despawned. Degrees
+/// are counted as edges come and go. The structural properties need the
+/// whole graph, so they are only computed while the panel is shown, on
+/// their own thread and one run at a time; the panel shows the last ones
+/// found until the run for the latest changes is done.
+#[derive(Resource, Default)]
+pub struct GraphStatistics {
+    degrees: HashMap<Entity, usize>,
+    edges: HashMap<Entity, (Entity, Entity)>,
+    degree_summary: Option<DegreeSummary>,
+    properties: Option<StructuralProperties>,
+    /// Whether the graph changed since the last run was started.
+    stale: bool,
+    task: Option<BackgroundTask<StructuralProperties>>,
+}
+
+impl GraphStatistics {
+    fn add_edge(&mut self, edge: Entity, v: Entity, u: Entity) {
+        if let Some((old_v, old_u)) = self.edges.insert(edge, (v, u)) {
+            self.remove_endpoints(old_v, old_u);
+        }
+
+        *self.degrees.entry(v).or_insert(0) += 1;
+        *self.degrees.entry(u).or_insert(0) += 1;
+    }
+
+    fn remove_edge(&mut self, edge: Entity) {
+        if let Some((v, u)) = self.edges.remove(&edge) {
+            self.remove_endpoints(v, u);
+        }
+    }
+
+    fn remove_endpoints(&mut self, v: Entity, u: Entity) {
+        for node in [v, u] {
+            if let Some(degree) = self.degrees.get_mut(&node) {
+                *degree = degree.saturating_sub(1);
+            }
+        }
+    }
+
+    fn summarize_degrees(&self) -> Option<DegreeSummary> {
+        let min = *self.degrees.values().min()?;
+        let max = *self.degrees.values().max()?;
+        let total = self.degrees.values().sum::<usize>();
+        let mut histogram = vec![0; max + 1];
+
+        for &degree in self.degrees.values() {
+            histogram[degree] += 1;
+        }
+
+        Some(DegreeSummary {
+            min,
+            max,
+            mean: total as f32 / self.degrees.len() as f32,
+            histogram,
+        })
+    }
+
+    fn density(&self) -> f32 {
+        let n = self.degrees.len();
+        let directed = self
+            .properties
+            .as_ref()
+            .map_or(false, |properties| properties.directed);
+
+        let mut pairs = (n * n.saturating_sub(1)) as f32;
+
+        if !directed {
+            pairs /= 2.0;
+        }
+
+        if pairs == 0.0 {
+            0.0
+        } else {
+            self.edges.len() as f32 / pairs
+        }
+    }
+}
+
+/// Runs after the commands of the frame are applied, so that the entities
+/// spawned or despawned during the frame are counted.
+pub fn update_statistics(
+    mut statistics: ResMut<GraphStatistics>,
+    ui_state: Res<UiState>,
+    added_nodes: Query<Entity, Added<Node>>,
+    added_edges: Query<(Entity, &NeighborNodes), Added<NeighborNodes>>,
+    removed_nodes: RemovedComponents<Node>,
+    removed_edges: RemovedComponents<NeighborNodes>,
+    graph_query: GraphQuery,
+) {
+    let mut changed = false;
+
+    for edge in removed_edges.iter() {
+        statistics.remove_edge(edge);
+        changed = true;
+    }
+
+    for node in removed_nodes.iter() {
+        statistics.degrees.remove(&node);
+        changed = true;
+    }
+
+    for node in added_nodes.iter() {
+        statistics.degrees.entry(node).or_insert(0);
+        changed = true;
+    }
+
+    for (edge, neighbor_nodes) in added_edges.iter() {
+        statistics.add_edge(edge, neighbor_nodes.v, neighbor_nodes.u);
+        changed = true;
+    }
+
+    if changed {
+        statistics.degree_summary = statistics.summarize_degrees();
+        statistics.stale = true;
+    }
+
+    if let Some(result) = statistics.task.as_mut().and_then(|task| task.poll()) {
+        statistics.task = None;
+
+        match result {
+            Ok(properties) => statistics.properties = Some(properties),
+            Err(_) => error!("The statistics of the graph could not be computed"),
+        }
+    }
+
+    let outdated = statistics.stale || statistics.properties.is_none();
+
+    if !ui_state.show_statistics || !outdated || statistics.task.is_some() {
+        return;
+    }
+
+    let graph = graph_query.snapshot().graph;
+
+    statistics.stale = false;
+    statistics.task = Some(BackgroundTask::start(move |_, _| {
+        StructuralProperties::compute(&graph)
+    }));
+}
+
+pub fn show_statistics_panel(
+    mut egui_context: ResMut<EguiContext>,
+    ui_state: Res<UiState>,
+    statistics: Res<GraphStatistics>,
+) {
+    if !ui_state.show_statistics {
+        return;
+    }
+
+    let yes_no = |value: bool| if value { "yes" } else { "no" };
+
+    egui::SidePanel::right("statistics")
+        .resizable(false)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.heading("Statistics");
+
+            egui::Grid::new("statistics_counts")
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.label("Nodes");
+                    ui.label(statistics.degrees.len().to_string());
+                    ui.end_row();
+
+                    ui.label("Edges");
+                    ui.label(statistics.edges.len().to_string());
+                    ui.end_row();
+
+                    ui.label("Density");
+                    ui.label(format!("{:.3}", statistics.density()));
+                    ui.end_row();
+
+                    if let Some(summary) = statistics.degree_summary.as_ref() {
+                        ui.label("Degree");
+                        ui.label(format!(
+                            "{} to {}, mean {:.2}",
+                            summary.min, summary.max, summary.mean
+                        ));
+                        ui.end_row();
+                    }
+
+                    let Some(properties) = statistics.properties.as_ref() else {
+                        return;
+                    };
+
+                    ui.label("Components");
+                    ui.label(properties.components.to_string());
+                    ui.end_row();
+
+                    let forest = properties.is_forest();
+
+                    ui.label("Tree");
+                    ui.label(yes_no(forest && properties.components == 1));
+                    ui.end_row();
+
+                    ui.label("Forest");
+                    ui.label(yes_no(forest));
+                    ui.end_row();
+
+                    ui.label("DAG");
+                    ui.label(properties.acyclic.map_or("undirected", yes_no));
+                    ui.end_row();
+
+                    ui.label("Bipartite");
+                    ui.label(yes_no(properties.bipartite));
+                    ui.end_row();
+
+                    ui.label("Planar");
+                    ui.label(yes_no(properties.planar));
+                    ui.end_row();
+
+                    ui.label("Girth");
+                    ui.label(
+                        properties
+                            .girth
+                            .map_or("no cycle".to_string(), |girth| girth.to_string()),
+                    );
+                    ui.end_row();
+                });
+
+            if statistics.task.is_some() {
+                ui.weak("Updating the properties...");
+            }
+
+            let Some(summary) = statistics.degree_summary.as_ref() else {
+                return;
+            };
+
+            ui.separator();
+            ui.strong("Degree histogram");
+
+            let most = summary.histogram.iter().copied().max().unwrap_or(0).max(1);
+
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .show(ui, |ui| {
+                    for (degree, &count) in summary.histogram.iter().enumerate() {
+                        ui.add(
+                            egui::ProgressBar::new(count as f32 / most as f32)
+                                .text(format!("{degree}: {count}")),
+                        );
+                    }
+                });
+        });
+}
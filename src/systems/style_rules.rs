@@ -0,0 +1,578 @@
+use std::{cmp::Ordering, collections::BTreeSet};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    components::{
+        Attributes, EdgeRuleStyle, EdgeStyle, NeighborNodes, Node, NodeRuleStyle, NodeStyle,
+    },
+    graph::{self, AttributeValue, Dash},
+    resources::{EdgeSettings, NodeSettings, Tool, UiState},
+    utils,
+};
+
+use super::{edge_editor, node};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuleTarget {
+    Nodes,
+    Edges,
+}
+
+impl RuleTarget {
+    pub const ALL: [RuleTarget; 2] = [RuleTarget::Nodes, RuleTarget::Edges];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Nodes => "Nodes",
+            Self::Edges => "Edges",
+        }
+    }
+}
+
+/// Colors given in turn to the values of an attribute.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Palette {
+    Bright,
+    Pastel,
+    Dark,
+}
+
+impl Palette {
+    pub const ALL: [Palette; 3] = [Palette::Bright, Palette::Pastel, Palette::Dark];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Self::Bright => "Bright",
+            Self::Pastel => "Pastel",
+            Self::Dark => "Dark",
+        }
+    }
+
+    pub fn color(self, index: usize) -> Color {
+        // The hues of `utils::palette_color`, lighter or darker.
+        let hue = (index as f32 * 137.508) % 360.0;
+
+        match self {
+            Self::Bright => utils::palette_color(index),
+            Self::Pastel => Color::hsl(hue, 0.6, 0.8),
+            Self::Dark => Color::hsl(hue, 0.7, 0.35),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+}
+
+impl Comparison {
+    pub const ALL: [Comparison; 4] = [
+        Comparison::Equal,
+        Comparison::NotEqual,
+        Comparison::Less,
+        Comparison::Greater,
+    ];
+
+    pub fn symbol(self) -> &'static str {
+        match self {
+            Self::Equal => "==",
+            Self::NotEqual => "!=",
+            Self::Less => "<",
+            Self::Greater => ">",
+        }
+    }
+
+    /// Whether the value compares this way to the text. Numbers are compared
+    /// as numbers when the text is one, and everything else as text.
+    fn holds(self, value: &AttributeValue, text: &str) -> bool {
+        let ordering = match (value, text.trim().parse::<f64>()) {
+            (AttributeValue::Number(number), Ok(other)) => number.partial_cmp(&other),
+            _ => Some(value.to_string().as_str().cmp(text)),
+        };
+
+        match self {
+            Self::Equal => ordering == Some(Ordering::Equal),
+            Self::NotEqual => ordering != Some(Ordering::Equal),
+            Self::Less => ordering == Some(Ordering::Less),
+            Self::Greater => ordering == Some(Ordering::Greater),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum RuleEffect {
+    /// A color of the palette for every distinct value of the attribute.
+    Color(Palette),
+    /// The radius of the nodes or the width of the edges, from `min` for the
+    /// smallest number the attribute has to `max` for the largest.
+    Size { min: f32, max: f32 },
+    /// The edges whose attribute compares to `value` are drawn with `dash`.
+    Dash {
+        comparison: Comparison,
+        value: String,
+        dash: Dash,
+    },
+}
+
+impl RuleEffect {
+    /// One effect of every kind that can style the target.
+    fn defaults(target: RuleTarget) -> Vec<RuleEffect> {
+        let mut effects = vec![RuleEffect::Color(Palette::Bright)];
+
+        match target {
+            RuleTarget::Nodes => effects.push(RuleEffect::Size {
+                min: 8.0,
+                max: 30.0,
+            }),
+            RuleTarget::Edges => {
+                effects.push(RuleEffect::Size { min: 1.0, max: 8.0 });
+                effects.push(RuleEffect::Dash {
+                    comparison: Comparison::Equal,
+                    value: String::new(),
+                    dash: Dash::Dashed,
+                });
+            }
+        }
+
+        effects
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Self::Color(_) => "Color",
+            Self::Size { .. } => "Size",
+            Self::Dash { .. } => "Dash",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct StyleRule {
+    pub target: RuleTarget,
+    pub attribute: String,
+    pub effect: RuleEffect,
+}
+
+impl StyleRule {
+    fn describe(&self) -> String {
+        let (target, attribute) = (self.target.name(), &self.attribute);
+
+        match &self.effect {
+            RuleEffect::Color(palette) => {
+                format!("{target}: color by {attribute} ({})", palette.name())
+            }
+            RuleEffect::Size { min, max } => {
+                format!("{target}: size by {attribute} from {min:.0} to {max:.0}")
+            }
+            RuleEffect::Dash {
+                comparison,
+                value,
+                dash,
+            } => format!(
+                "{target}: {} where {attribute} {} {value:?}",
+                dash.name(),
+                comparison.symbol()
+            ),
+        }
+    }
+}
+
+/// The rules in the order they are applied, so that a later rule wins over
+/// an earlier one.
+#[derive(Resource, Default)]
+pub struct StyleRules {
+    pub rules: Vec<StyleRule>,
+}
+
+#[derive(Resource)]
+pub struct StyleRulesState {
+    target: RuleTarget,
+    attribute: String,
+    effect: RuleEffect,
+}
+
+impl Default for StyleRulesState {
+    fn default() -> Self {
+        Self {
+            target: RuleTarget::Nodes,
+            attribute: String::new(),
+            effect: RuleEffect::Color(Palette::Bright),
+        }
+    }
+}
+
+/// What the rules say about a node or an edge.
+#[derive(Default)]
+struct RuledStyle {
+    color: Option<Color>,
+    size: Option<f32>,
+    dash: Option<Dash>,
+}
+
+impl RuledStyle {
+    fn is_empty(&self) -> bool {
+        self.color.is_none() && self.size.is_none() && self.dash.is_none()
+    }
+}
+
+/// Applies the rules of the target to the attributes of every one of its
+/// nodes or edges.
+fn evaluate(
+    rules: &[StyleRule],
+    target: RuleTarget,
+    items: &[Option<&graph::Attributes>],
+) -> Vec<RuledStyle> {
+    let mut styles = items
+        .iter()
+        .map(|_| RuledStyle::default())
+        .collect::<Vec<_>>();
+
+    for rule in rules.iter().filter(|rule| rule.target == target) {
+        let values = items
+            .iter()
+            .map(|attributes| attributes.and_then(|attributes| attributes.get(&rule.attribute)))
+            .collect::<Vec<_>>();
+
+        match &rule.effect {
+            RuleEffect::Color(palette) => {
+                let distinct = values
+                    .iter()
+                    .flatten()
+                    .map(|value| value.to_string())
+                    .collect::<BTreeSet<_>>();
+
+                for (style, value) in styles.iter_mut().zip(values.iter()) {
+                    if let Some(value) = value {
+                        let index = distinct.range(..value.to_string()).count();
+
+                        style.color = Some(palette.color(index));
+                    }
+                }
+            }
+            RuleEffect::Size { min, max } => {
+                let numbers = values
+                    .iter()
+                    .map(|value| match value {
+                        Some(AttributeValue::Number(number)) => Some(*number),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
+                let (low, high) = numbers.iter().flatten().fold(
+                    (f64::INFINITY, f64::NEG_INFINITY),
+                    |(low, high), &number| (low.min(number), high.max(number)),
+                );
+
+                for (style, number) in styles.iter_mut().zip(numbers) {
+                    if let Some(number) = number {
+                        let t = if high > low {
+                            ((number - low) / (high - low)) as f32
+                        } else {
+                            0.5
+                        };
+
+                        style.size = Some(min + (max - min) * t);
+                    }
+                }
+            }
+            RuleEffect::Dash {
+                comparison,
+                value: text,
+                dash,
+            } => {
+                for (style, value) in styles.iter_mut().zip(values.iter()) {
+                    if value.map_or(false, |value| comparison.holds(value, text)) {
+                        style.dash = Some(*dash);
+                    }
+                }
+            }
+        }
+    }
+
+    styles
+}
+
+/// Styles the nodes and edges by the rules whenever the rules, the
+/// attributes or the graph change. Nodes and edges no rule applies to
+/// anymore get back the style they had before.
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn apply_style_rules(
+    mut commands: Commands,
+    nodes: Query<
+        (
+            Entity,
+            Option<&Attributes>,
+            Option<&NodeStyle>,
+            Option<&NodeRuleStyle>,
+        ),
+        With<Node>,
+    >,
+    edges: Query<
+        (
+            Entity,
+            Option<&Attributes>,
+            Option<&EdgeStyle>,
+            Option<&EdgeRuleStyle>,
+        ),
+        With<NeighborNodes>,
+    >,
+    changed: Query<(), Or<(Changed<Attributes>, Added<Node>, Added<NeighborNodes>)>>,
+    removed_attributes: RemovedComponents<Attributes>,
+    style_rules: Res<StyleRules>,
+    node_settings: Res<NodeSettings>,
+    edge_settings: Res<EdgeSettings>,
+) {
+    if !style_rules.is_changed() && changed.is_empty() && removed_attributes.iter().next().is_none()
+    {
+        return;
+    }
+
+    let nodes = nodes.iter().collect::<Vec<_>>();
+    let attributes = nodes
+        .iter()
+        .map(|(_, attributes, ..)| attributes.map(|attributes| &attributes.0))
+        .collect::<Vec<_>>();
+    let ruled = evaluate(&style_rules.rules, RuleTarget::Nodes, &attributes);
+
+    for ((entity, _, style, rule_style), ruled) in nodes.into_iter().zip(ruled) {
+        let style = style.map(|style| &style.0);
+        // A style that is not the one the rules gave was edited since, and
+        // is the one to go back to.
+        let before = match rule_style {
+            Some(rule_style) if style == Some(&rule_style.applied) => rule_style.before.clone(),
+            _ => style.cloned(),
+        };
+        let mut entity = commands.entity(entity);
+
+        if ruled.is_empty() {
+            if rule_style.is_some() {
+                entity.remove::<NodeRuleStyle>();
+
+                match before {
+                    Some(before) if style != Some(&before) => {
+                        entity.insert(NodeStyle(before));
+                    }
+                    Some(_) => {}
+                    None => {
+                        entity.remove::<NodeStyle>();
+                    }
+                }
+            }
+
+            continue;
+        }
+
+        let mut applied = before
+            .clone()
+            .unwrap_or_else(|| node::default_node_style(&node_settings));
+
+        if let Some(color) = ruled.color {
+            applied.fill = color.as_rgba_f32();
+        }
+
+        // Whole pixels keep nodes whose values are close from being styled
+        // again, and drawn again, for a size change nobody can see.
+        if let Some(size) = ruled.size {
+            applied.radius = size.round();
+        }
+
+        if style != Some(&applied) {
+            entity.insert(NodeStyle(applied.clone()));
+        }
+
+        entity.insert(NodeRuleStyle { before, applied });
+    }
+
+    let edges = edges.iter().collect::<Vec<_>>();
+    let attributes = edges
+        .iter()
+        .map(|(_, attributes, ..)| attributes.map(|attributes| &attributes.0))
+        .collect::<Vec<_>>();
+    let ruled = evaluate(&style_rules.rules, RuleTarget::Edges, &attributes);
+
+    for ((entity, _, style, rule_style), ruled) in edges.into_iter().zip(ruled) {
+        let before = match rule_style {
+            Some(rule_style) if style == Some(&rule_style.applied) => rule_style.before,
+            _ => style.copied(),
+        };
+        let mut entity = commands.entity(entity);
+
+        if ruled.is_empty() {
+            if rule_style.is_some() {
+                // Edges keep a style once they have one, which draws them as
+                // `EdgeSettings` says.
+                let before =
+                    before.unwrap_or_else(|| edge_editor::default_edge_style(&edge_settings));
+
+                entity.remove::<EdgeRuleStyle>();
+
+                if style != Some(&before) {
+                    entity.insert(before);
+                }
+            }
+
+            continue;
+        }
+
+        let mut applied = before.unwrap_or_else(|| edge_editor::default_edge_style(&edge_settings));
+
+        if let Some(color) = ruled.color {
+            applied.color = color;
+        }
+
+        if let Some(size) = ruled.size {
+            applied.width = size;
+        }
+
+        if let Some(dash) = ruled.dash {
+            applied.dash = dash;
+        }
+
+        if style != Some(&applied) {
+            entity.insert(applied);
+        }
+
+        entity.insert(EdgeRuleStyle { before, applied });
+    }
+}
+
+pub fn show_style_rules_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut style_rules: ResMut<StyleRules>,
+    mut style_rules_state: ResMut<StyleRulesState>,
+) {
+    let mut open = ui_state.is_open(Tool::StyleRules);
+
+    if !open {
+        return;
+    }
+
+    let state = &mut *style_rules_state;
+    let mut rules = style_rules.rules.clone();
+
+    egui::Window::new(Tool::StyleRules.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            if rules.is_empty() {
+                ui.label(
+                    "Rules draw the nodes and edges by their attributes. \
+                     A later rule wins over an earlier one.",
+                );
+            }
+
+            let mut removed = None;
+
+            for (index, rule) in rules.iter().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.label(rule.describe());
+
+                    if ui.button("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+
+            if let Some(index) = removed {
+                rules.remove(index);
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                for target in RuleTarget::ALL {
+                    ui.radio_value(&mut state.target, target, target.name());
+                }
+            });
+
+            ui.horizontal(|ui| {
+                ui.label("Attribute");
+                ui.text_edit_singleline(&mut state.attribute);
+            });
+
+            let effects = RuleEffect::defaults(state.target);
+
+            if !effects
+                .iter()
+                .any(|effect| effect.name() == state.effect.name())
+            {
+                state.effect = effects[0].clone();
+            }
+
+            ui.horizontal(|ui| {
+                for effect in effects {
+                    let selected = effect.name() == state.effect.name();
+
+                    if ui.radio(selected, effect.name()).clicked() && !selected {
+                        state.effect = effect;
+                    }
+                }
+            });
+
+            let sizes = match state.target {
+                RuleTarget::Nodes => 6.0..=40.0,
+                RuleTarget::Edges => 1.0..=12.0,
+            };
+
+            match &mut state.effect {
+                RuleEffect::Color(palette) => {
+                    ui.horizontal(|ui| {
+                        for other in Palette::ALL {
+                            ui.radio_value(palette, other, other.name());
+                        }
+                    });
+                }
+                RuleEffect::Size { min, max } => {
+                    ui.add(egui::Slider::new(min, sizes.clone()).text("smallest"));
+                    ui.add(egui::Slider::new(max, sizes).text("largest"));
+                }
+                RuleEffect::Dash {
+                    comparison,
+                    value,
+                    dash,
+                } => {
+                    ui.horizontal(|ui| {
+                        egui::ComboBox::from_id_source("rule comparison")
+                            .selected_text(comparison.symbol())
+                            .show_ui(ui, |ui| {
+                                for other in Comparison::ALL {
+                                    ui.selectable_value(comparison, other, other.symbol());
+                                }
+                            });
+
+                        ui.text_edit_singleline(value);
+                    });
+
+                    ui.horizontal(|ui| {
+                        for other in Dash::ALL {
+                            ui.radio_value(dash, other, other.name());
+                        }
+                    });
+                }
+            }
+
+            let attribute = state.attribute.trim();
+
+            if ui
+                .add_enabled(!attribute.is_empty(), egui::Button::new("Add rule"))
+                .clicked()
+            {
+                rules.push(StyleRule {
+                    target: state.target,
+                    attribute: attribute.to_string(),
+                    effect: state.effect.clone(),
+                });
+            }
+        });
+
+    if rules != style_rules.rules {
+        style_rules.rules = rules;
+    }
+
+    if !open {
+        ui_state.set_open(Tool::StyleRules, false);
+    }
+}
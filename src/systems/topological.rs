@@ -0,0 +1,254 @@
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::topological::{self, KahnRun},
+    layout,
+    resources::{HighlightLayer, HighlightSettings, Highlights, NodeSettings, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+};
+
+use super::{node::MoveNodesEvent, playback::Playback};
+
+pub struct TopologicalAnimation {
+    snapshot: GraphSnapshot,
+    run: KahnRun,
+    step: usize,
+}
+
+impl TopologicalAnimation {
+    fn is_finished(&self) -> bool {
+        self.step == self.run.steps.len()
+    }
+
+    fn ordered_nodes(&self) -> Vec<bool> {
+        let mut ordered = vec![false; self.snapshot.graph.nodes.len()];
+
+        for &node in self.run.order[..self.step].iter() {
+            ordered[node] = true;
+        }
+
+        ordered
+    }
+
+    fn node_names(&self, nodes: &[usize]) -> String {
+        nodes
+            .iter()
+            .map(|&node| self.snapshot.graph.nodes[node].label.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct TopologicalState {
+    pub animation: Option<TopologicalAnimation>,
+    pub playback: Playback,
+}
+
+fn highlight(
+    animation: &TopologicalAnimation,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    let GraphSnapshot {
+        graph,
+        nodes,
+        edges,
+    } = &animation.snapshot;
+    let ordered = animation.ordered_nodes();
+
+    for &node in animation.run.order[..animation.step].iter() {
+        highlights
+            .nodes
+            .insert(nodes[node], highlight_settings.accepted_color);
+    }
+
+    for (index, edge) in graph.edges.iter().enumerate() {
+        if edge.directed && ordered[edge.source] {
+            highlights
+                .edges
+                .insert(edges[index], highlight_settings.accepted_color);
+        }
+    }
+
+    if let Some(&node) = animation.run.order[..animation.step].last() {
+        highlights
+            .nodes
+            .insert(nodes[node], highlight_settings.current_color);
+    }
+
+    if !animation.is_finished() {
+        return;
+    }
+
+    if let Some(cycle) = &animation.run.cycle {
+        for &node in cycle.nodes.iter() {
+            highlights
+                .nodes
+                .insert(nodes[node], highlight_settings.rejected_color);
+        }
+
+        for &edge in cycle.edges.iter() {
+            highlights
+                .edges
+                .insert(edges[edge], highlight_settings.rejected_color);
+        }
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_topological_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut topological_state: ResMut<TopologicalState>,
+    mut highlights: ResMut<Highlights>,
+    mut event_writer: EventWriter<MoveNodesEvent>,
+    graph_query: GraphQuery,
+    windows: Res<Windows>,
+    node_settings: Res<NodeSettings>,
+    highlight_settings: Res<HighlightSettings>,
+    time: Res<Time>,
+) {
+    let mut open = ui_state.is_open(Tool::TopologicalSort);
+
+    if !open {
+        if topological_state.animation.take().is_some() {
+            topological_state.playback.playing = false;
+            highlights.clear(Tool::TopologicalSort);
+        }
+
+        return;
+    }
+
+    let mut changed = false;
+    let state = &mut *topological_state;
+
+    if let Some(animation) = state.animation.as_mut() {
+        changed = state
+            .playback
+            .tick(time.delta(), &mut animation.step, animation.run.steps.len());
+    }
+
+    egui::Window::new(Tool::TopologicalSort.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            if ui.button("Run Kahn's algorithm").clicked() {
+                let snapshot = graph_query.snapshot();
+                let run = topological::kahn(&snapshot.graph);
+
+                state.animation = Some(TopologicalAnimation {
+                    snapshot,
+                    run,
+                    step: 0,
+                });
+                state.playback.playing = false;
+                changed = true;
+            }
+
+            ui.label("Only directed edges are taken into account.");
+
+            let Some(animation) = state.animation.as_mut() else {
+                return;
+            };
+
+            ui.separator();
+
+            changed |= state
+                .playback
+                .controls(ui, &mut animation.step, animation.run.steps.len());
+
+            let (in_degrees, queue) = match animation.step.checked_sub(1) {
+                Some(step) => (
+                    &animation.run.steps[step].in_degrees,
+                    &animation.run.steps[step].queue,
+                ),
+                None => (&animation.run.in_degrees, &animation.run.queue),
+            };
+
+            ui.label(format!(
+                "Order: {}",
+                animation.node_names(&animation.run.order[..animation.step])
+            ));
+            ui.label(format!("Queue: {}", animation.node_names(queue)));
+
+            if animation.is_finished() {
+                match &animation.run.cycle {
+                    Some(cycle) => {
+                        ui.label(format!(
+                            "The graph has a cycle: {}",
+                            animation.node_names(&cycle.nodes)
+                        ));
+                    }
+                    None => {
+                        ui.label("Every node is ordered.");
+
+                        if ui.button("Arrange left to right").clicked() {
+                            let window = windows
+                                .get_primary()
+                                .expect("Can not get the primary window");
+                            let margin = node_settings.radius * 4.0;
+
+                            let mut graph = animation.snapshot.graph.clone();
+
+                            layout::layered(
+                                &mut graph,
+                                &animation.run.order,
+                                window.width() - margin,
+                                window.height() - margin,
+                            );
+
+                            event_writer.send(MoveNodesEvent {
+                                positions: animation
+                                    .snapshot
+                                    .nodes
+                                    .iter()
+                                    .zip(graph.nodes.iter())
+                                    .map(|(&entity, node)| (entity, Vec2::new(node.x, node.y)))
+                                    .collect(),
+                            });
+                        }
+                    }
+                }
+            }
+
+            ui.separator();
+            ui.label("In-degrees");
+
+            let ordered = animation.ordered_nodes();
+
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new("in_degrees").striped(true).show(ui, |ui| {
+                        for (node, in_degree) in in_degrees.iter().enumerate() {
+                            ui.label(animation.snapshot.graph.nodes[node].label.as_str());
+
+                            if ordered[node] {
+                                ui.label("ordered");
+                            } else {
+                                ui.label(in_degree.to_string());
+                            }
+
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+
+    if !open {
+        ui_state.set_open(Tool::TopologicalSort, false);
+    }
+
+    if changed {
+        if let Some(animation) = state.animation.as_ref() {
+            highlight(
+                animation,
+                highlights.layer(Tool::TopologicalSort),
+                &highlight_settings,
+            );
+        }
+    }
+}
@@ -0,0 +1,406 @@
+use std::time::Duration;
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::{
+        euler::{self, EulerError, EulerKind, EulerRun, EulerStep},
+        hamiltonian::{self, HamiltonianResult},
+    },
+    resources::{HighlightLayer, HighlightSettings, Highlights, Tool, UiState},
+    snapshot::{GraphQuery, GraphSnapshot},
+    utils,
+};
+
+use super::{background::BackgroundTask, playback::Playback};
+
+/// Hamiltonian searches are only offered up to this number of nodes.
+const MAX_HAMILTONIAN_NODES: usize = 32;
+
+pub struct EulerAnimation {
+    snapshot: GraphSnapshot,
+    run: EulerRun,
+    step: usize,
+}
+
+pub struct HamiltonianSearch {
+    cycle: bool,
+    snapshot: GraphSnapshot,
+    task: BackgroundTask<HamiltonianResult>,
+}
+
+pub struct HamiltonianReport {
+    cycle: bool,
+    snapshot: GraphSnapshot,
+    result: HamiltonianResult,
+    tried: u64,
+    elapsed: Duration,
+}
+
+#[derive(Resource, Default)]
+pub struct ToursState {
+    pub euler: Option<Result<EulerAnimation, EulerError>>,
+    pub playback: Playback,
+    pub search: Option<HamiltonianSearch>,
+    pub report: Option<HamiltonianReport>,
+}
+
+fn highlight_euler(
+    animation: &EulerAnimation,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    let EulerAnimation {
+        snapshot,
+        run,
+        step,
+    } = animation;
+
+    let mut walk = Vec::new();
+    let mut closed = 0;
+    let mut current = run.nodes.first().copied();
+
+    for &step in run.steps[..*step].iter() {
+        match step {
+            EulerStep::Walk(edge) => {
+                let graph_edge = &snapshot.graph.edges[edge];
+                let from = current.unwrap_or(graph_edge.source);
+
+                walk.push((edge, from));
+                current = Some(if graph_edge.source == from {
+                    graph_edge.target
+                } else {
+                    graph_edge.source
+                });
+            }
+            EulerStep::Close(_) => {
+                // Edges are added to the trail from its end, so the first one
+                // closed is the last one of the trail.
+                if let Some((edge, from)) = walk.pop() {
+                    highlights
+                        .edges
+                        .insert(snapshot.edges[edge], highlight_settings.accepted_color);
+                    highlights
+                        .edge_labels
+                        .insert(snapshot.edges[edge], (run.trail.len() - closed).to_string());
+
+                    closed += 1;
+                    current = Some(from);
+                }
+            }
+        }
+    }
+
+    for &(edge, _) in walk.iter() {
+        highlights
+            .edges
+            .insert(snapshot.edges[edge], highlight_settings.current_color);
+    }
+
+    if *step == run.steps.len() {
+        let (start, end) = (run.nodes[0], run.nodes[run.nodes.len() - 1]);
+
+        highlights
+            .nodes
+            .insert(snapshot.nodes[start], utils::palette_color(0));
+        highlights
+            .nodes
+            .insert(snapshot.nodes[end], utils::palette_color(1));
+    } else if let Some(node) = current {
+        highlights
+            .nodes
+            .insert(snapshot.nodes[node], highlight_settings.current_color);
+    }
+}
+
+fn highlight_hamiltonian(
+    report: &HamiltonianReport,
+    highlights: &mut HighlightLayer,
+    highlight_settings: &HighlightSettings,
+) {
+    highlights.clear();
+
+    let HamiltonianResult::Found { nodes, edges } = &report.result else {
+        return;
+    };
+
+    for &node in nodes.iter() {
+        highlights.nodes.insert(
+            report.snapshot.nodes[node],
+            highlight_settings.accepted_color,
+        );
+    }
+
+    highlights
+        .nodes
+        .insert(report.snapshot.nodes[nodes[0]], utils::palette_color(0));
+
+    for (position, &edge) in edges.iter().enumerate() {
+        highlights.edges.insert(
+            report.snapshot.edges[edge],
+            highlight_settings.accepted_color,
+        );
+        highlights
+            .edge_labels
+            .insert(report.snapshot.edges[edge], (position + 1).to_string());
+    }
+}
+
+fn start_search(snapshot: GraphSnapshot, cycle: bool) -> HamiltonianSearch {
+    let graph = snapshot.graph.clone();
+
+    HamiltonianSearch {
+        cycle,
+        snapshot,
+        task: BackgroundTask::start(move |progress, cancel| {
+            hamiltonian::search(&graph, cycle, progress, cancel)
+        }),
+    }
+}
+
+fn kind_name(cycle: bool) -> &'static str {
+    if cycle {
+        "cycle"
+    } else {
+        "path"
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_tours_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut tours_state: ResMut<ToursState>,
+    mut highlights: ResMut<Highlights>,
+    graph_query: GraphQuery,
+    highlight_settings: Res<HighlightSettings>,
+    time: Res<Time>,
+) {
+    let mut open = ui_state.is_open(Tool::Tours);
+    let state = &mut *tours_state;
+
+    if !open {
+        if state.euler.is_some() || state.search.is_some() || state.report.is_some() {
+            *state = ToursState::default();
+            highlights.clear(Tool::Tours);
+        }
+
+        return;
+    }
+
+    let mut changed = false;
+
+    if let Some(result) = state.search.as_mut().and_then(|search| search.task.poll()) {
+        let search = state.search.take().unwrap();
+
+        match result {
+            Ok(result) => {
+                let report = HamiltonianReport {
+                    cycle: search.cycle,
+                    tried: search.task.progress(),
+                    elapsed: search.task.elapsed(),
+                    snapshot: search.snapshot,
+                    result,
+                };
+
+                highlight_hamiltonian(&report, highlights.layer(Tool::Tours), &highlight_settings);
+
+                state.report = Some(report);
+            }
+            Err(_) => error!("The Hamiltonian search stopped unexpectedly"),
+        }
+    }
+
+    if let Some(Ok(animation)) = state.euler.as_mut() {
+        changed |=
+            state
+                .playback
+                .tick(time.delta(), &mut animation.step, animation.run.steps.len());
+    }
+
+    egui::Window::new(Tool::Tours.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.heading("Eulerian");
+
+            if ui.button("Run Hierholzer").clicked() {
+                let snapshot = graph_query.snapshot();
+
+                state.euler = Some(
+                    euler::hierholzer(&snapshot.graph).map(|run| EulerAnimation {
+                        snapshot,
+                        run,
+                        step: 0,
+                    }),
+                );
+                state.report = None;
+                state.playback.playing = false;
+                highlights.clear(Tool::Tours);
+                changed = true;
+            }
+
+            ui.label("Edge directions are used only when every edge is directed.");
+
+            match state.euler.as_mut() {
+                Some(Ok(animation)) => {
+                    let kind = match animation.run.kind {
+                        EulerKind::Circuit => "circuit",
+                        EulerKind::Path => "path",
+                    };
+                    let graph = if animation.run.directed {
+                        "directed"
+                    } else {
+                        "undirected"
+                    };
+
+                    ui.label(format!(
+                        "The {graph} graph has an Eulerian {kind} of {} edges",
+                        animation.run.trail.len()
+                    ));
+
+                    changed |= state.playback.controls(
+                        ui,
+                        &mut animation.step,
+                        animation.run.steps.len(),
+                    );
+
+                    match animation.step.checked_sub(1).map(|step| animation.run.steps[step]) {
+                        Some(EulerStep::Walk(_)) => {
+                            ui.label("Following an unused edge");
+                        }
+                        Some(EulerStep::Close(node)) => {
+                            ui.label(format!(
+                                "{} has no unused edges left, going back",
+                                animation.snapshot.graph.nodes[node].label
+                            ));
+                        }
+                        None => {}
+                    }
+
+                    if animation.step == animation.run.steps.len() {
+                        let labels = animation
+                            .run
+                            .nodes
+                            .iter()
+                            .map(|&node| animation.snapshot.graph.nodes[node].label.as_str())
+                            .collect::<Vec<_>>();
+
+                        egui::ScrollArea::vertical()
+                            .max_height(100.0)
+                            .show(ui, |ui| {
+                                ui.label(labels.join(", "));
+                            });
+                    }
+                }
+                Some(Err(error)) => {
+                    ui.label(format!("No Eulerian path: {error}"));
+                }
+                None => {}
+            }
+
+            ui.separator();
+            ui.heading("Hamiltonian");
+
+            ui.horizontal(|ui| {
+                let idle = state.search.is_none();
+
+                for cycle in [false, true] {
+                    let text = format!("Find {}", kind_name(cycle));
+
+                    if !ui.add_enabled(idle, egui::Button::new(text)).clicked() {
+                        continue;
+                    }
+
+                    let snapshot = graph_query.snapshot();
+
+                    if snapshot.graph.nodes.len() > MAX_HAMILTONIAN_NODES {
+                        warn!(
+                            "The Hamiltonian search is limited to graphs with at most {MAX_HAMILTONIAN_NODES} nodes"
+                        );
+
+                        continue;
+                    }
+
+                    state.search = Some(start_search(snapshot, cycle));
+                    state.euler = None;
+                    state.report = None;
+                    state.playback.playing = false;
+                    highlights.clear(Tool::Tours);
+                }
+
+                if ui
+                    .add_enabled(!idle, egui::Button::new("Cancel"))
+                    .clicked()
+                {
+                    if let Some(search) = state.search.as_ref() {
+                        search.task.cancel();
+                    }
+                }
+            });
+
+            ui.label(format!(
+                "Directed edges are followed forward only. The search works on graphs with up to {MAX_HAMILTONIAN_NODES} nodes."
+            ));
+
+            if let Some(search) = state.search.as_ref() {
+                ui.label(format!(
+                    "Searching for a Hamiltonian {}: {} partial paths tried in {:.1} s",
+                    kind_name(search.cycle),
+                    search.task.progress(),
+                    search.task.elapsed().as_secs_f32()
+                ));
+            }
+
+            let Some(report) = state.report.as_ref() else {
+                return;
+            };
+
+            let kind = kind_name(report.cycle);
+
+            match &report.result {
+                HamiltonianResult::Found { nodes, .. } => {
+                    let labels = nodes
+                        .iter()
+                        .map(|&node| report.snapshot.graph.nodes[node].label.as_str())
+                        .collect::<Vec<_>>();
+
+                    ui.label(format!("Found a Hamiltonian {kind}:"));
+
+                    egui::ScrollArea::vertical()
+                        .max_height(100.0)
+                        .show(ui, |ui| {
+                            ui.label(labels.join(", "));
+                        });
+                }
+                HamiltonianResult::NotFound => {
+                    ui.label(format!("The graph has no Hamiltonian {kind}"));
+                }
+                HamiltonianResult::Cancelled => {
+                    ui.label("The search was cancelled");
+                }
+            }
+
+            ui.label(format!(
+                "{} partial paths tried in {:.1} s",
+                report.tried,
+                report.elapsed.as_secs_f32()
+            ));
+        });
+
+    if !open {
+        ui_state.set_open(Tool::Tours, false);
+    }
+
+    if changed {
+        if let Some(Ok(animation)) = state.euler.as_ref() {
+            highlight_euler(
+                animation,
+                highlights.layer(Tool::Tours),
+                &highlight_settings,
+            );
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::{fs, path::Path};
+
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContext};
+
+use crate::{
+    algorithms::transform::{self, Template},
+    components::{Label, Node},
+    formats::Format,
+    graph::Graph,
+    layout,
+    resources::{
+        EdgeIndex, GraphCollection, HighlightSettings, Highlights, InteractionMode, NodeSettings,
+        Tool, UiState, VisualizerState,
+    },
+    snapshot::GraphQuery,
+};
+
+use super::{collection::ShowGraphEvent, node::NodePickedEvent};
+
+#[derive(Resource)]
+pub struct TransformationsState {
+    /// Nodes picked for the induced subgraph, or the two ends of the edge
+    /// to contract or subdivide.
+    pub selection: Vec<Entity>,
+    pub picking: bool,
+    /// Replace the current graph instead of adding the result to the
+    /// collection as a new graph.
+    pub in_place: bool,
+    pub template: Template,
+    pub template_size: usize,
+    pub path: String,
+    pub error: Option<String>,
+    highlighted: bool,
+}
+
+impl Default for TransformationsState {
+    fn default() -> Self {
+        Self {
+            selection: Vec::new(),
+            picking: false,
+            in_place: true,
+            template: Template::Path,
+            template_size: 2,
+            path: String::new(),
+            error: None,
+            highlighted: false,
+        }
+    }
+}
+
+/// A transformation picked in the window. The graph is only read from the
+/// canvas once a button is clicked.
+enum Transformation {
+    Complement,
+    Transpose,
+    LineGraph,
+    InducedSubgraph,
+    ContractEdge(Entity),
+    SubdivideEdge(Entity),
+    CartesianProduct,
+    TensorProduct,
+    DisjointUnion(Graph),
+}
+
+fn read_graph(path: &str) -> Result<Graph, String> {
+    let path = Path::new(path.trim());
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Can not read {}: {err}", path.display()))?;
+    let format = Format::from_path(path).unwrap_or(Format::Graph6);
+
+    format
+        .read(&contents)
+        .map_err(|err| format!("Can not load {}: {err}", path.display()))?
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("{} does not contain any graph", path.display()))
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn show_transformations_window(
+    mut egui_context: ResMut<EguiContext>,
+    mut ui_state: ResMut<UiState>,
+    mut transformations_state: ResMut<TransformationsState>,
+    mut visualizer_state: ResMut<VisualizerState>,
+    mut highlights: ResMut<Highlights>,
+    mut collection: ResMut<GraphCollection>,
+    mut event_reader: EventReader<NodePickedEvent>,
+    mut event_writer: EventWriter<ShowGraphEvent>,
+    graph_query: GraphQuery,
+    edge_index: Res<EdgeIndex>,
+    labels: Query<&Label, With<Node>>,
+    windows: Res<Windows>,
+    node_settings: Res<NodeSettings>,
+    highlight_settings: Res<HighlightSettings>,
+) {
+    let mut open = ui_state.is_open(Tool::Transformations);
+    let state = &mut *transformations_state;
+
+    if !open {
+        state.selection.clear();
+        state.picking = false;
+
+        if std::mem::take(&mut state.highlighted) {
+            highlights.clear(Tool::Transformations);
+        }
+
+        if visualizer_state.interaction_mode == InteractionMode::PickNode {
+            visualizer_state.interaction_mode = InteractionMode::Edit;
+        }
+
+        return;
+    }
+
+    for ev in event_reader.iter() {
+        if !state.picking {
+            continue;
+        }
+
+        if let Some(index) = state.selection.iter().position(|&node| node == ev.entity) {
+            state.selection.remove(index);
+        } else {
+            state.selection.push(ev.entity);
+        }
+    }
+
+    // Picking a node goes back to editing, but the selection can take any
+    // number of nodes, so the tool keeps picking until Done is pressed.
+    if state.picking && visualizer_state.interaction_mode == InteractionMode::Edit {
+        visualizer_state.interaction_mode = InteractionMode::PickNode;
+    }
+
+    if visualizer_state.interaction_mode != InteractionMode::PickNode {
+        state.picking = false;
+    }
+
+    state.selection.retain(|&node| labels.contains(node));
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+    let margin = node_settings.radius * 4.0;
+    let (width, height) = (window.width() - margin, window.height() - margin);
+
+    let selected_edge = match state.selection[..] {
+        [a, b] => edge_index.edge_between(a, b),
+        _ => None,
+    };
+
+    let mut transformation = None;
+
+    egui::Window::new(Tool::Transformations.name())
+        .open(&mut open)
+        .show(egui_context.ctx_mut(), |ui| {
+            ui.horizontal(|ui| {
+                ui.radio_value(&mut state.in_place, true, "Replace the current graph");
+                ui.radio_value(&mut state.in_place, false, "Open as a new graph");
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                if ui.button("Complement").clicked() {
+                    transformation = Some(Transformation::Complement);
+                }
+
+                if ui
+                    .add_enabled(graph_query.is_directed(), egui::Button::new("Transpose"))
+                    .clicked()
+                {
+                    transformation = Some(Transformation::Transpose);
+                }
+
+                if ui.button("Line graph").clicked() {
+                    transformation = Some(Transformation::LineGraph);
+                }
+            });
+
+            ui.separator();
+            ui.label(format!(
+                "Selected nodes: {}",
+                if state.selection.is_empty() {
+                    "none".to_string()
+                } else {
+                    state
+                        .selection
+                        .iter()
+                        .filter_map(|&node| labels.get(node).ok())
+                        .map(|label| label.0.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                }
+            ));
+
+            ui.horizontal(|ui| {
+                if state.picking {
+                    if ui.button("Done").clicked() {
+                        state.picking = false;
+                        visualizer_state.interaction_mode = InteractionMode::Edit;
+                    }
+                } else if ui.button("Pick nodes").clicked() {
+                    state.picking = true;
+                    visualizer_state.interaction_mode = InteractionMode::PickNode;
+                }
+
+                if ui.button("Clear").clicked() {
+                    state.selection.clear();
+                }
+            });
+
+            if state.picking {
+                ui.label("Click nodes to add them to the selection or remove them from it.");
+            }
+
+            ui.horizontal(|ui| {
+                if ui
+                    .add_enabled(
+                        !state.selection.is_empty(),
+                        egui::Button::new("Induced subgraph"),
+                    )
+                    .clicked()
+                {
+                    transformation = Some(Transformation::InducedSubgraph);
+                }
+
+                if let Some(edge) = selected_edge {
+                    if ui.button("Contract edge").clicked() {
+                        transformation = Some(Transformation::ContractEdge(edge));
+                    }
+
+                    if ui.button("Subdivide edge").clicked() {
+                        transformation = Some(Transformation::SubdivideEdge(edge));
+                    }
+                }
+            });
+
+            if selected_edge.is_none() {
+                ui.label("Select the two ends of an edge to contract or subdivide it.");
+            }
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                egui::ComboBox::from_label("template")
+                    .selected_text(state.template.name())
+                    .show_ui(ui, |ui| {
+                        for template in Template::ALL {
+                            ui.selectable_value(&mut state.template, template, template.name());
+                        }
+                    });
+
+                ui.add(egui::Slider::new(&mut state.template_size, 1..=8).text("nodes"));
+            });
+
+            ui.horizontal(|ui| {
+                if ui.button("Cartesian product").clicked() {
+                    transformation = Some(Transformation::CartesianProduct);
+                }
+
+                if ui.button("Tensor product").clicked() {
+                    transformation = Some(Transformation::TensorProduct);
+                }
+            });
+
+            ui.separator();
+            ui.horizontal(|ui| {
+                ui.label("File");
+                ui.text_edit_singleline(&mut state.path);
+            });
+
+            if ui
+                .add_enabled(
+                    !state.path.trim().is_empty(),
+                    egui::Button::new("Disjoint union"),
+                )
+                .clicked()
+            {
+                match read_graph(&state.path) {
+                    Ok(mut other) => {
+                        // Most formats have no positions, so the other graph
+                        // is laid out on its own before being put next to
+                        // this one.
+                        if !other.has_positions() {
+                            layout::force_directed(&mut other, width / 2.0, height, 300);
+                        }
+
+                        state.error = None;
+                        transformation = Some(Transformation::DisjointUnion(other));
+                    }
+                    Err(err) => state.error = Some(err),
+                }
+            }
+
+            if let Some(error) = state.error.as_ref() {
+                ui.label(error);
+            }
+        });
+
+    let result = transformation.and_then(|transformation| {
+        let snapshot = graph_query.snapshot();
+        let graph = &snapshot.graph;
+        let edge_position = |entity: Entity| snapshot.edges.iter().position(|&edge| edge == entity);
+
+        let result = match transformation {
+            Transformation::Complement => transform::complement(graph),
+            Transformation::Transpose => transform::transpose(graph),
+            Transformation::LineGraph => transform::line_graph(graph),
+            Transformation::InducedSubgraph => {
+                let selection = state
+                    .selection
+                    .iter()
+                    .filter_map(|&entity| snapshot.nodes.iter().position(|&node| node == entity))
+                    .collect::<Vec<_>>();
+
+                transform::induced_subgraph(graph, &selection)
+            }
+            Transformation::ContractEdge(edge) => {
+                transform::contract_edge(graph, edge_position(edge)?)
+            }
+            Transformation::SubdivideEdge(edge) => {
+                transform::subdivide_edge(graph, edge_position(edge)?)
+            }
+            Transformation::CartesianProduct => {
+                transform::cartesian_product(graph, &state.template.build(state.template_size))
+            }
+            Transformation::TensorProduct => {
+                transform::tensor_product(graph, &state.template.build(state.template_size))
+            }
+            Transformation::DisjointUnion(other) => transform::disjoint_union(graph, &other),
+        };
+
+        Some((snapshot.graph, result))
+    });
+
+    if let Some((current_graph, mut result)) = result {
+        layout::fit(&mut result, width, height);
+
+        // The canvas may have been edited since the graph was shown, so its
+        // current state is stored before a new graph is added.
+        if state.in_place || collection.graphs.is_empty() {
+            let current = collection.current;
+
+            match collection.graphs.get_mut(current) {
+                Some(stored) => *stored = result,
+                None => collection.graphs.push(result),
+            }
+        } else {
+            let current = collection.current;
+
+            collection.graphs[current] = current_graph;
+            collection.graphs.push(result);
+            collection.current = collection.graphs.len() - 1;
+        }
+
+        state.selection.clear();
+        event_writer.send(ShowGraphEvent { layout: false });
+    }
+
+    if !open {
+        ui_state.set_open(Tool::Transformations, false);
+    }
+
+    // Nothing to write while no nodes are selected, which would otherwise
+    // make the highlights be applied again every frame.
+    if state.selection.is_empty() && !std::mem::take(&mut state.highlighted) {
+        return;
+    }
+
+    let highlights = highlights.layer(Tool::Transformations);
+
+    highlights.clear();
+
+    for &node in state.selection.iter() {
+        highlights
+            .nodes
+            .insert(node, highlight_settings.accepted_color);
+    }
+
+    state.highlighted = !state.selection.is_empty();
+}
@@ -0,0 +1,122 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Color32, FontId},
+    EguiContext,
+};
+
+use crate::{
+    components::{Bend, Corners, Label, NeighborNodes, Node, Radius, Route, Weight},
+    graph::{self, EdgeRoute},
+    resources::{Highlights, NodeSettings, Tool, UiState, VisualizerState},
+};
+
+use super::edges::edge_middle;
+
+pub fn update_pointer_state(
+    mut egui_context: ResMut<EguiContext>,
+    mut visualizer_state: ResMut<VisualizerState>,
+) {
+    let ctx = egui_context.ctx_mut();
+
+    visualizer_state.is_pointer_over_ui = ctx.is_pointer_over_area() || ctx.wants_pointer_input();
+    visualizer_state.is_typing = ctx.wants_keyboard_input();
+}
+
+pub fn show_menu_bar(mut egui_context: ResMut<EguiContext>, mut ui_state: ResMut<UiState>) {
+    egui::TopBottomPanel::top("menu_bar").show(egui_context.ctx_mut(), |ui| {
+        egui::menu::bar(ui, |ui| {
+            ui.menu_button("View", |ui| {
+                ui.checkbox(&mut ui_state.show_labels, "Labels");
+                ui.checkbox(&mut ui_state.show_statistics, "Statistics");
+            });
+
+            ui.menu_button("Tools", |ui| {
+                for tool in Tool::ALL {
+                    let mut open = ui_state.is_open(tool);
+
+                    if ui.checkbox(&mut open, tool.name()).changed() {
+                        ui_state.set_open(tool, open);
+                    }
+                }
+            });
+        });
+    });
+}
+
+pub fn draw_labels(
+    mut egui_context: ResMut<EguiContext>,
+    nodes: Query<(&Transform, &Label, &Radius), With<Node>>,
+    edges: Query<(
+        Entity,
+        &NeighborNodes,
+        Option<&Label>,
+        Option<&Weight>,
+        Option<&Bend>,
+        Option<&Route>,
+        Option<&Corners>,
+    )>,
+    windows: Res<Windows>,
+    highlights: Res<Highlights>,
+    node_settings: Res<NodeSettings>,
+    ui_state: Res<UiState>,
+) {
+    if !ui_state.show_labels {
+        return;
+    }
+
+    let window = windows
+        .get_primary()
+        .expect("Can not get the primary window");
+
+    let (window_center_x, window_center_y) = (window.width() / 2.0, window.height() / 2.0);
+    let to_screen =
+        |position: Vec2| egui::pos2(position.x + window_center_x, window_center_y - position.y);
+
+    let painter = egui_context
+        .ctx_mut()
+        .layer_painter(egui::LayerId::background());
+    let font = FontId::proportional(node_settings.radius);
+
+    for (transform, label, radius) in nodes.iter() {
+        painter.text(
+            to_screen(transform.translation.truncate()),
+            Align2::CENTER_CENTER,
+            &label.0,
+            FontId::proportional(radius.0),
+            Color32::BLACK,
+        );
+    }
+
+    for (entity, neighbor_nodes, label, weight, bend, route, corners) in edges.iter() {
+        let text = match highlights.edge_label(entity) {
+            Some(text) => Some(text.clone()),
+            None => graph::edge_text(
+                label.map(|label| label.0.as_str()),
+                weight.map(|weight| weight.0),
+            ),
+        };
+
+        let Some(text) = text else {
+            continue;
+        };
+
+        let radius = nodes
+            .get(neighbor_nodes.u)
+            .map_or(node_settings.radius, |(_, _, radius)| radius.0);
+        let middle = edge_middle(
+            neighbor_nodes,
+            radius,
+            bend.map_or(0.0, |bend| bend.0),
+            route.map_or(EdgeRoute::Straight, |route| route.0),
+            corners,
+        );
+
+        painter.text(
+            to_screen(middle + Vec2::Y * node_settings.radius),
+            Align2::CENTER_CENTER,
+            text,
+            font.clone(),
+            Color32::WHITE,
+        );
+    }
+}
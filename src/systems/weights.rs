@@ -0,0 +1,84 @@
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_egui::egui;
+
+use crate::components::{Directed, Label, NeighborNodes, Weight};
+
+/// The weights of the edges, for the windows of the tools that read them.
+#[derive(SystemParam)]
+pub struct WeightTable<'w, 's> {
+    commands: Commands<'w, 's>,
+    edges: Query<
+        'w,
+        's,
+        (
+            Entity,
+            &'static NeighborNodes,
+            Option<&'static Weight>,
+            Option<&'static Directed>,
+        ),
+    >,
+    labels: Query<'w, 's, &'static Label, Without<NeighborNodes>>,
+}
+
+impl<'w, 's> WeightTable<'w, 's> {
+    /// A collapsed list of the edges under `heading`, each with its weight.
+    /// Edges without a weight count as 1. Returns whether a weight was
+    /// changed.
+    pub fn show(&mut self, ui: &mut egui::Ui, heading: &str, non_negative: bool) -> bool {
+        let mut changed = false;
+
+        egui::CollapsingHeader::new(heading).show(ui, |ui| {
+            let node_label = |node| {
+                self.labels
+                    .get(node)
+                    .map_or_else(|_| "?".to_string(), |label| label.0.clone())
+            };
+
+            let mut rows = self
+                .edges
+                .iter()
+                .map(|(entity, neighbor_nodes, weight, directed)| {
+                    let arrow = if directed.is_some() { "->" } else { "-" };
+                    let name = format!(
+                        "{} {arrow} {}",
+                        node_label(neighbor_nodes.v),
+                        node_label(neighbor_nodes.u)
+                    );
+
+                    (name, entity, weight.map_or(1.0, |weight| weight.0))
+                })
+                .collect::<Vec<_>>();
+
+            rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+            if rows.is_empty() {
+                ui.label("The graph has no edges.");
+            }
+
+            egui::ScrollArea::vertical()
+                .max_height(200.0)
+                .show(ui, |ui| {
+                    egui::Grid::new(heading).show(ui, |ui| {
+                        for (edge, entity, mut weight) in rows {
+                            ui.label(edge);
+
+                            let mut drag_value = egui::DragValue::new(&mut weight).speed(0.1);
+
+                            if non_negative {
+                                drag_value = drag_value.clamp_range(0.0..=f32::MAX);
+                            }
+
+                            if ui.add(drag_value).changed() {
+                                self.commands.entity(entity).insert(Weight(weight));
+                                changed = true;
+                            }
+
+                            ui.end_row();
+                        }
+                    });
+                });
+        });
+
+        changed
+    }
+}
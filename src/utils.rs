@@ -1,4 +1,4 @@
-use bevy::window::Window;
+use bevy::{prelude::Color, window::Window};
 
 pub fn get_mouse_coordinates(window: &Window) -> Option<(f32, f32)> {
     let mouse_position = window.cursor_position()?;
@@ -10,9 +10,12 @@ pub fn get_mouse_coordinates(window: &Window) -> Option<(f32, f32)> {
     ))
 }
 
-pub fn is_mouse_on_node(mouse_x: f32, mouse_y: f32, node_x: f32, node_y: f32, radius: f32) -> bool {
-    let distance_between_node_and_mouse =
-        ((mouse_x - node_x).powi(2) + (mouse_y - node_y).powi(2)).sqrt();
+pub fn palette_color(index: usize) -> Color {
+    // Golden angle steps keep consecutive colors far apart on the hue wheel.
+    Color::hsl((index as f32 * 137.508) % 360.0, 0.75, 0.6)
+}
 
-    distance_between_node_and_mouse <= radius
+/// Blue for 0 through green to red for 1.
+pub fn gradient_color(t: f32) -> Color {
+    Color::hsl(240.0 * (1.0 - t.clamp(0.0, 1.0)), 0.75, 0.6)
 }